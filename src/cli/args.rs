@@ -0,0 +1,132 @@
+use std::{collections::BTreeMap, str::FromStr};
+
+use super::CliError;
+
+/// Options taking a value, `--name value` or `--name=value`.
+const OPTIONS: [&str; 9] = [
+    "delimiter",
+    "target",
+    "features",
+    "model",
+    "clusters",
+    "seed",
+    "learning-rate",
+    "iterations",
+    "output",
+];
+
+const FLAGS: [&str; 4] = ["json", "header", "no-header", "help"];
+
+/// Positional arguments and `--` options of one invocation.
+#[derive(Debug, Clone, Default)]
+pub struct Args {
+    positional: Vec<String>,
+    options: BTreeMap<String, String>,
+}
+
+impl Args {
+    pub fn parse(args: &[String]) -> Result<Args, CliError> {
+        let mut parsed = Args::default();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let name = match arg.strip_prefix("--") {
+                Some(name) => name,
+                None => {
+                    parsed.positional.push(arg.clone());
+                    continue;
+                }
+            };
+
+            let (name, value) = match name.split_once('=') {
+                Some((name, value)) => (name, Some(value.to_string())),
+                None => (name, None),
+            };
+            if FLAGS.contains(&name) && value.is_none() {
+                parsed.options.insert(name.to_string(), String::new());
+            } else if OPTIONS.contains(&name) {
+                let value = match value.or_else(|| args.next().cloned()) {
+                    Some(value) => value,
+                    None => return Err(CliError::Usage(format!("--{} needs a value", name))),
+                };
+                parsed.options.insert(name.to_string(), value);
+            } else {
+                return Err(CliError::Usage(format!("unknown option --{}", name)));
+            }
+        }
+        Ok(parsed)
+    }
+
+    /// The `k`-th positional argument, the command itself being the first.
+    pub fn positional(&self, k: usize) -> Option<&str> {
+        self.positional.get(k).map(|p| p.as_str())
+    }
+
+    pub fn required(&self, k: usize, what: &str) -> Result<&str, CliError> {
+        match self.positional(k) {
+            Some(value) => Ok(value),
+            None => Err(CliError::Usage(format!("missing {}", what))),
+        }
+    }
+
+    pub fn flag(&self, name: &str) -> bool {
+        self.options.contains_key(name)
+    }
+
+    pub fn option(&self, name: &str) -> Option<&str> {
+        self.options.get(name).map(|v| v.as_str())
+    }
+
+    pub fn parsed<T: FromStr>(&self, name: &str) -> Result<Option<T>, CliError> {
+        match self.option(name) {
+            Some(value) => match value.parse() {
+                Ok(value) => Ok(Some(value)),
+                Err(_) => Err(CliError::Usage(format!(
+                    "invalid value '{}' for --{}",
+                    value, name
+                ))),
+            },
+            None => Ok(None),
+        }
+    }
+
+    /// Whether the CSV input has a header row, `default` unless set.
+    pub fn header(&self, default: bool) -> bool {
+        if self.flag("header") {
+            true
+        } else if self.flag("no-header") {
+            false
+        } else {
+            default
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Args;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let parsed = Args::parse(&args(&[
+            "fit",
+            "--target=y",
+            "linear",
+            "--json",
+            "--seed",
+            "3",
+        ]))
+        .unwrap();
+        assert_eq!(parsed.positional(1), Some("linear"));
+        assert_eq!(parsed.option("target"), Some("y"));
+        assert_eq!(parsed.parsed::<u64>("seed").unwrap(), Some(3));
+        assert!(parsed.flag("json") && parsed.header(true));
+
+        assert!(Args::parse(&args(&["det", "--verbose"])).is_err());
+        assert!(Args::parse(&args(&["fit", "--model"])).is_err());
+        assert!(parsed.parsed::<u64>("target").is_err());
+    }
+}
@@ -0,0 +1,49 @@
+use std::io::Read;
+
+use crate::data::{csv::parse_table, table::Series};
+
+use super::{csv_options, output::Report, read_input, Args, CliError};
+
+pub fn describe(args: &Args, input: &mut dyn Read) -> Result<Report, CliError> {
+    let text = read_input(args.positional(1), input)?;
+    let summary = parse_table(&text, &csv_options(args, true)?)?.describe()?;
+
+    let mut index = vec![];
+    let mut columns = vec![];
+    let mut values = vec![];
+    for name in summary.column_names() {
+        match summary.column(name)? {
+            Series::Numeric(column) => {
+                columns.push(name.clone());
+                values.push(column.clone());
+            }
+            Series::Text(labels) => index = labels.clone(),
+            Series::Categorical { .. } => {}
+        }
+    }
+
+    let rows = (0..index.len())
+        .map(|i| values.iter().map(|column| column[i]).collect())
+        .collect();
+    Ok(Report::Table {
+        index,
+        columns,
+        rows,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cli::run_with;
+
+    #[test]
+    fn test_describe() {
+        let text = run_with(&["describe"], "x,name\n1,a\n3,b\n").unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0].trim(), "x");
+        assert!(lines[2].starts_with(" mean ") && lines[2].ends_with(" 2"));
+
+        let json = run_with(&["describe", "--json"], "x,name\n1,a\n3,b\n").unwrap();
+        assert!(json.starts_with("{\"x\":{\"25%\":1.5,"));
+    }
+}
@@ -0,0 +1,109 @@
+use std::io::Read;
+
+use crate::{
+    error::CustomErrors,
+    matrix::{
+        decomposition::{determinant, inverse, solve as solve_system, svd as decompose},
+        eigen::symmetric_eigen,
+    },
+};
+
+use super::{output::Report, read_matrix, Args, CliError};
+
+pub fn det(args: &Args, input: &mut dyn Read) -> Result<Report, CliError> {
+    let x = read_matrix(args, 1, input)?;
+    Ok(Report::Scalar(determinant(&x)?))
+}
+
+pub fn inv(args: &Args, input: &mut dyn Read) -> Result<Report, CliError> {
+    let x = read_matrix(args, 1, input)?;
+    Ok(Report::Matrix(inverse(&x)?))
+}
+
+pub fn solve(args: &Args, input: &mut dyn Read) -> Result<Report, CliError> {
+    args.required(2, "right-hand side")?;
+    if args.positional(1) == Some("-") && args.positional(2) == Some("-") {
+        return Err(CliError::Usage(
+            "only one input can be read from stdin".to_string(),
+        ));
+    }
+    let a = read_matrix(args, 1, input)?;
+    let b: Vec<f64> = read_matrix(args, 2, input)?
+        .rows
+        .into_iter()
+        .flatten()
+        .collect();
+    Ok(Report::Vector(solve_system(&a, &b)?))
+}
+
+pub fn eig(args: &Args, input: &mut dyn Read) -> Result<Report, CliError> {
+    let x = read_matrix(args, 1, input)?;
+    let (values, vectors) = match symmetric_eigen(&x) {
+        Ok(eigen) => eigen,
+        Err(CustomErrors::BadType(_)) => {
+            return Err(CliError::Usage(
+                "eig only supports symmetric matrices".to_string(),
+            ))
+        }
+        Err(err) => return Err(err.into()),
+    };
+    Ok(Report::Fields(vec![
+        ("values".to_string(), Report::Vector(values)),
+        ("vectors".to_string(), Report::Matrix(vectors)),
+    ]))
+}
+
+pub fn svd(args: &Args, input: &mut dyn Read) -> Result<Report, CliError> {
+    let x = read_matrix(args, 1, input)?;
+    let (u, s, v) = decompose(&x)?;
+    Ok(Report::Fields(vec![
+        ("u".to_string(), Report::Matrix(u)),
+        ("s".to_string(), Report::Vector(s)),
+        ("v".to_string(), Report::Matrix(v)),
+    ]))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        cli::{run_with, CliError},
+        error::{CustomErrors, SingularMatrixError},
+        test_util::temp_path,
+    };
+
+    #[test]
+    fn test_linear_algebra_commands() {
+        assert_eq!(run_with(&["det"], "0,2\n3,4\n").unwrap(), "-6\n");
+        assert_eq!(
+            run_with(&["inv", "--json"], "2,0\n0,4\n").unwrap(),
+            "[[0.5,0.0],[0.0,0.25]]\n"
+        );
+        assert_eq!(
+            run_with(&["inv"], "1,2\n2,4\n").unwrap_err(),
+            CliError::Failed(CustomErrors::SingularMatrix(SingularMatrixError))
+        );
+
+        let path = temp_path("linalg_test_rhs.csv");
+        std::fs::write(&path, "4\n8\n").unwrap();
+        let rhs = path.to_str().unwrap();
+        assert_eq!(
+            run_with(&["solve", "-", rhs], "2,0\n0,4\n").unwrap(),
+            "2\n2\n"
+        );
+        std::fs::remove_file(&path).unwrap();
+        assert!(run_with(&["solve", "-", "-"], "").is_err());
+
+        let eig = run_with(&["eig", "--json"], "2,0\n0,3\n").unwrap();
+        assert_eq!(
+            eig,
+            "{\"values\":[3.0,2.0],\"vectors\":[[0.0,1.0],[1.0,0.0]]}\n"
+        );
+        assert!(matches!(
+            run_with(&["eig"], "1,2\n0,1\n"),
+            Err(CliError::Usage(_))
+        ));
+
+        let svd = run_with(&["svd"], "3,0\n0,-2\n").unwrap();
+        assert!(svd.contains("s:\n  3\n  2\n"));
+    }
+}
@@ -0,0 +1,184 @@
+//! The `pikus` command-line tool.
+
+mod args;
+mod describe;
+mod linalg;
+mod models;
+mod output;
+mod repl;
+
+use std::{
+    fmt, fs,
+    io::{self, Read, Write},
+};
+
+use crate::{
+    data::csv::{parse_csv, CsvOptions},
+    error::CustomErrors,
+    matrix::Matrix,
+};
+
+use args::Args;
+
+pub const USAGE: &str = "\
+Usage: pikus <command> [arguments] [options]
+
+Linear algebra, on a headerless CSV matrix read from a file or stdin (`-`):
+  det [matrix]                 determinant
+  inv [matrix]                 inverse
+  solve <matrix> <rhs>         solution of A x = b, b as a single column or row
+  eig [matrix]                 eigenvalues and eigenvectors of a symmetric matrix
+  svd [matrix]                 singular value decomposition A = U diag(s) V^T
+
+Models, on CSV data with a header row:
+  fit <linear|logistic|kmeans> <data> --model <file>
+      --target <column>        column to predict, not used by kmeans
+      --features <a,b,..>      feature columns, by default all but the target
+      --clusters <k>           number of kmeans clusters
+      --seed <n>               kmeans seed
+      --learning-rate <lr>     logistic regression step size
+      --iterations <n>         logistic regression gradient steps
+  predict <model> [data]       predictions for every row of the data
+      --output <file>          write them to a CSV file instead
+
+Statistics:
+  describe [data]              count, mean, std, min, quartiles and max of numeric columns
+
+Interactive:
+  repl                         matrix expressions such as `inv(A) * b`, `help` lists them
+
+Options:
+  --json                       print JSON instead of text
+  --header, --no-header        whether the CSV input starts with a header row
+  --delimiter <c>              CSV field delimiter, `,` by default
+  --help                       print this message
+";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CliError {
+    /// Bad command line, the message says what is wrong.
+    Usage(String),
+    Failed(CustomErrors),
+}
+
+impl From<CustomErrors> for CliError {
+    fn from(err: CustomErrors) -> Self {
+        CliError::Failed(err)
+    }
+}
+
+impl From<io::Error> for CliError {
+    fn from(err: io::Error) -> Self {
+        CliError::Failed(err.into())
+    }
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CliError::Usage(message) => write!(f, "{}, see `pikus --help`", message),
+            CliError::Failed(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+/// Contents of the file at `path`, or of `input` for `-` or no path.
+fn read_input(path: Option<&str>, input: &mut dyn Read) -> Result<String, CliError> {
+    match path {
+        None | Some("-") => {
+            let mut text = String::new();
+            input.read_to_string(&mut text)?;
+            Ok(text)
+        }
+        Some(path) => Ok(fs::read_to_string(path)?),
+    }
+}
+
+fn csv_options(args: &Args, header: bool) -> Result<CsvOptions, CliError> {
+    let mut options = CsvOptions::new().with_header(args.header(header));
+    if let Some(delimiter) = args.option("delimiter") {
+        let mut chars = delimiter.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => options = options.with_delimiter(c),
+            _ => {
+                return Err(CliError::Usage(
+                    "the delimiter must be a single character".to_string(),
+                ))
+            }
+        }
+    }
+    Ok(options)
+}
+
+/// Matrix in the CSV named by the `k`-th positional argument, without a header by default.
+fn read_matrix(args: &Args, k: usize, input: &mut dyn Read) -> Result<Matrix<f64>, CliError> {
+    let text = read_input(args.positional(k), input)?;
+    Ok(parse_csv(&text, &csv_options(args, false)?)?.features)
+}
+
+/// Runs the command in `args` (without the program name), reading `-` inputs
+/// from `input` and printing the result to `output`.
+pub fn run(args: &[String], input: &mut dyn Read, output: &mut dyn Write) -> Result<(), CliError> {
+    let args = Args::parse(args)?;
+    let command = match args.positional(0) {
+        Some(command) => command,
+        None if args.flag("help") => "help",
+        None => return Err(CliError::Usage("missing command".to_string())),
+    };
+    if command == "help" || args.flag("help") {
+        output.write_all(USAGE.as_bytes())?;
+        return Ok(());
+    }
+
+    if command == "repl" {
+        return repl::repl(&args, input, output);
+    }
+
+    let report = match command {
+        "det" => linalg::det(&args, input)?,
+        "inv" => linalg::inv(&args, input)?,
+        "solve" => linalg::solve(&args, input)?,
+        "eig" => linalg::eig(&args, input)?,
+        "svd" => linalg::svd(&args, input)?,
+        "fit" => models::fit(&args, input)?,
+        "predict" => models::predict(&args, input)?,
+        "describe" => describe::describe(&args, input)?,
+        other => return Err(CliError::Usage(format!("unknown command '{}'", other))),
+    };
+
+    if args.flag("json") {
+        writeln!(output, "{}", report.to_json())?;
+    } else {
+        output.write_all(report.to_text().as_bytes())?;
+    }
+    Ok(())
+}
+
+/// Runs `args` with `input` on stdin and returns what was printed.
+#[cfg(test)]
+pub(crate) fn run_with(args: &[&str], input: &str) -> Result<String, CliError> {
+    let args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+    let mut output = vec![];
+    run(&args, &mut input.as_bytes(), &mut output)?;
+    Ok(String::from_utf8(output).unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{run_with, CliError, USAGE};
+
+    #[test]
+    fn test_run() {
+        assert_eq!(run_with(&["--help"], "").unwrap(), USAGE);
+        assert_eq!(
+            run_with(&["frobnicate"], "").unwrap_err(),
+            CliError::Usage("unknown command 'frobnicate'".to_string())
+        );
+        assert!(matches!(run_with(&[], ""), Err(CliError::Usage(_))));
+
+        let err = run_with(&["det", "-"], "1,2\n3\n").unwrap_err();
+        assert_eq!(err.to_string(), "Malformed input on line 2.");
+        let err = run_with(&["det", "missing.csv"], "").unwrap_err();
+        assert!(err.to_string().starts_with("I/O error"));
+    }
+}
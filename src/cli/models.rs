@@ -0,0 +1,273 @@
+use std::io::Read;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    data::csv::{parse_csv, write_predictions, CsvData},
+    error::{CustomErrors, MismatchError},
+    matrix::Matrix,
+    metrics::accuracy,
+    models::{classifier::logreg::LogisticRegression, cluster::KMeans, linear::LinearRegression},
+    persist::{load_json, save_json},
+};
+
+use super::{csv_options, output::Report, read_input, Args, CliError};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum Model {
+    Linear(LinearRegression),
+    Logistic(LogisticRegression),
+    KMeans(KMeans),
+}
+
+/// A fitted model with the columns it was trained on, names are empty for
+/// data without a header.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SavedModel {
+    features: Vec<String>,
+    n_features: usize,
+    model: Model,
+}
+
+fn column(data: &CsvData, column: &str, header: bool) -> Result<usize, CliError> {
+    let index = if header {
+        data.feature_names.iter().position(|n| n == column)
+    } else {
+        column.parse().ok().filter(|j| *j < data.features.n)
+    };
+    match index {
+        Some(index) => Ok(index),
+        None => Err(CliError::Usage(format!("unknown column '{}'", column))),
+    }
+}
+
+fn columns(x: &Matrix<f64>, indices: &[usize]) -> Matrix<f64> {
+    Matrix {
+        rows: x
+            .rows
+            .iter()
+            .map(|r| indices.iter().map(|j| r[*j]).collect())
+            .collect(),
+        m: x.m,
+        n: indices.len(),
+    }
+}
+
+pub fn fit(args: &Args, input: &mut dyn Read) -> Result<Report, CliError> {
+    let kind = args.required(1, "model type, one of linear, logistic or kmeans")?;
+    let path = args.required(2, "data file")?;
+    let model_path = match args.option("model") {
+        Some(model_path) => model_path,
+        None => return Err(CliError::Usage("missing --model <file>".to_string())),
+    };
+    let header = args.header(true);
+    let data = parse_csv(&read_input(Some(path), input)?, &csv_options(args, true)?)?;
+
+    let target = match (kind, args.option("target")) {
+        ("kmeans", _) => None,
+        (_, Some(target)) => Some(column(&data, target, header)?),
+        (_, None) => return Err(CliError::Usage("missing --target <column>".to_string())),
+    };
+    let features = match args.option("features") {
+        Some(list) => list
+            .split(',')
+            .map(|c| column(&data, c.trim(), header))
+            .collect::<Result<Vec<usize>, CliError>>()?,
+        None => (0..data.features.n)
+            .filter(|j| Some(*j) != target)
+            .collect(),
+    };
+    let x = columns(&data.features, &features);
+    let y: Vec<f64> = match target {
+        Some(t) => data.features.rows.iter().map(|r| r[t]).collect(),
+        None => vec![],
+    };
+
+    let (model, mut report) = match kind {
+        "linear" => {
+            let mut model = LinearRegression::new();
+            model.fit(&x, &y)?;
+            let report = vec![
+                ("intercept".to_string(), Report::Scalar(model.intercept())),
+                (
+                    "coefficients".to_string(),
+                    Report::Vector(model.coefficients().clone()),
+                ),
+                ("r2".to_string(), Report::Scalar(model.score(&x, &y)?)),
+            ];
+            (Model::Linear(model), report)
+        }
+        "logistic" => {
+            let mut model = LogisticRegression::new();
+            if let Some(lr) = args.parsed("learning-rate")? {
+                model = model.with_learning_rate(lr);
+            }
+            if let Some(n) = args.parsed("iterations")? {
+                model = model.with_iterations(n);
+            }
+            let losses = model.fit(x.clone(), y.clone())?;
+            let report = vec![
+                (
+                    "loss".to_string(),
+                    Report::Scalar(losses.last().copied().unwrap_or(f64::NAN)),
+                ),
+                (
+                    "accuracy".to_string(),
+                    Report::Scalar(accuracy(&y, &model.predict(&x)?)?),
+                ),
+            ];
+            (Model::Logistic(model), report)
+        }
+        "kmeans" => {
+            let k = match args.parsed("clusters")? {
+                Some(k) => k,
+                None => return Err(CliError::Usage("missing --clusters <k>".to_string())),
+            };
+            let mut model = KMeans::new(k);
+            if let Some(seed) = args.parsed("seed")? {
+                model = model.with_seed(seed);
+            }
+            let result = model.fit(&x)?;
+            let mut sizes = vec![0.0; k];
+            for label in &result.labels {
+                sizes[*label] += 1.0;
+            }
+            let report = vec![
+                ("inertia".to_string(), Report::Scalar(result.inertia)),
+                ("sizes".to_string(), Report::Vector(sizes)),
+                (
+                    "centroids".to_string(),
+                    Report::Matrix(Matrix {
+                        rows: result.centroids,
+                        m: k,
+                        n: x.n,
+                    }),
+                ),
+            ];
+            (Model::KMeans(model), report)
+        }
+        other => return Err(CliError::Usage(format!("unknown model '{}'", other))),
+    };
+
+    let saved = SavedModel {
+        features: features
+            .iter()
+            .filter_map(|j| data.feature_names.get(*j).cloned())
+            .collect(),
+        n_features: features.len(),
+        model,
+    };
+    save_json(model_path, &saved)?;
+    report.insert(
+        0,
+        ("model".to_string(), Report::Text(model_path.to_string())),
+    );
+    Ok(Report::Fields(report))
+}
+
+pub fn predict(args: &Args, input: &mut dyn Read) -> Result<Report, CliError> {
+    let saved: SavedModel = load_json(args.required(1, "model file")?)?;
+    let header = args.header(true);
+    let data = parse_csv(
+        &read_input(args.positional(2), input)?,
+        &csv_options(args, true)?,
+    )?;
+
+    // columns are matched by name when both sides have them
+    let x = if header && !saved.features.is_empty() {
+        let indices = saved
+            .features
+            .iter()
+            .map(|name| column(&data, name, true))
+            .collect::<Result<Vec<usize>, CliError>>()?;
+        columns(&data.features, &indices)
+    } else if data.features.n == saved.n_features {
+        data.features
+    } else {
+        return Err(CustomErrors::Mismatch(MismatchError).into());
+    };
+
+    let predictions = match &saved.model {
+        Model::Linear(model) => model.predict(&x)?,
+        Model::Logistic(model) => model.predict(&x)?,
+        Model::KMeans(model) => model.predict(&x)?.iter().map(|l| *l as f64).collect(),
+    };
+
+    match args.option("output") {
+        Some(path) => {
+            write_predictions(path, &predictions, "prediction")?;
+            Ok(Report::Text(format!(
+                "wrote {} predictions to {}",
+                predictions.len(),
+                path
+            )))
+        }
+        None => Ok(Report::Vector(predictions)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        cli::{run_with, CliError},
+        test_util::temp_path,
+    };
+
+    #[test]
+    fn test_fit_predict() {
+        let (data_path, model_path) = (
+            temp_path("models_test_fit_predict.csv"),
+            temp_path("models_test_fit_predict.json"),
+        );
+        let (data, model) = (data_path.to_str().unwrap(), model_path.to_str().unwrap());
+        std::fs::write(data, "a,b,y\n0,1,3\n1,0,3\n1,1,5\n2,1,7\n").unwrap();
+
+        // y = 1 + 2a + 2b
+        let fit = run_with(
+            &[
+                "fit", "linear", data, "--target", "y", "--model", model, "--json",
+            ],
+            "",
+        )
+        .unwrap();
+        assert!(fit.contains("\"r2\":1.0"));
+        let pred = run_with(&["predict", model, "-"], "b,a\n0,0\n1,3\n").unwrap();
+        let pred: Vec<f64> = pred.lines().map(|l| l.parse().unwrap()).collect();
+        assert!((pred[0] - 1.0).abs() < 1e-10 && (pred[1] - 9.0).abs() < 1e-10);
+
+        run_with(
+            &[
+                "fit",
+                "kmeans",
+                data,
+                "--features",
+                "a,b",
+                "--clusters",
+                "2",
+                "--seed",
+                "0",
+                "--model",
+                model,
+            ],
+            "",
+        )
+        .unwrap();
+        let labels = run_with(&["predict", model, "--no-header", "-"], "0,1\n2,1\n").unwrap();
+        assert_eq!(labels.lines().count(), 2);
+
+        assert!(matches!(
+            run_with(&["fit", "linear", data, "--model", model], ""),
+            Err(CliError::Usage(_))
+        ));
+        assert_eq!(
+            run_with(
+                &["fit", "linear", data, "--target", "z", "--model", model],
+                ""
+            )
+            .unwrap_err(),
+            CliError::Usage("unknown column 'z'".to_string())
+        );
+        std::fs::remove_file(&data_path).unwrap();
+        std::fs::remove_file(&model_path).unwrap();
+    }
+}
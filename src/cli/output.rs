@@ -0,0 +1,177 @@
+use serde_json::{json, Map, Value};
+
+use crate::matrix::Matrix;
+
+/// Result of a command, printed as text or JSON.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Report {
+    Scalar(f64),
+    Vector(Vec<f64>),
+    Matrix(Matrix<f64>),
+    /// Values by row label and column name.
+    Table {
+        index: Vec<String>,
+        columns: Vec<String>,
+        rows: Vec<Vec<f64>>,
+    },
+    Fields(Vec<(String, Report)>),
+    Text(String),
+}
+
+fn number(v: f64) -> Value {
+    // JSON has no NaN or infinity
+    match serde_json::Number::from_f64(v) {
+        Some(n) => Value::Number(n),
+        None => Value::Null,
+    }
+}
+
+/// Cells padded to the widest one in their column, right aligned.
+fn aligned(cells: &[Vec<String>]) -> String {
+    let width = cells.iter().map(|r| r.len()).max().unwrap_or(0);
+    let widths: Vec<usize> = (0..width)
+        .map(|j| {
+            cells
+                .iter()
+                .filter_map(|r| r.get(j))
+                .map(|c| c.len())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let mut out = String::new();
+    for row in cells {
+        let line: Vec<String> = row
+            .iter()
+            .zip(widths.iter())
+            .map(|(c, w)| format!("{:>w$}", c, w = w))
+            .collect();
+        out.push_str(line.join("  ").trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+impl Report {
+    pub fn to_json(&self) -> Value {
+        match self {
+            Report::Scalar(v) => number(*v),
+            Report::Vector(v) => Value::Array(v.iter().map(|x| number(*x)).collect()),
+            Report::Matrix(x) => Value::Array(
+                x.rows
+                    .iter()
+                    .map(|r| Value::Array(r.iter().map(|v| number(*v)).collect()))
+                    .collect(),
+            ),
+            Report::Table {
+                index,
+                columns,
+                rows,
+            } => {
+                let mut table = Map::new();
+                for (j, column) in columns.iter().enumerate() {
+                    let mut values = Map::new();
+                    for (label, row) in index.iter().zip(rows.iter()) {
+                        values.insert(label.clone(), number(row[j]));
+                    }
+                    table.insert(column.clone(), Value::Object(values));
+                }
+                Value::Object(table)
+            }
+            Report::Fields(fields) => {
+                let mut object = Map::new();
+                for (name, report) in fields {
+                    object.insert(name.clone(), report.to_json());
+                }
+                Value::Object(object)
+            }
+            Report::Text(text) => json!(text),
+        }
+    }
+
+    pub fn to_text(&self) -> String {
+        match self {
+            Report::Scalar(v) => format!("{}\n", v),
+            Report::Vector(v) => v.iter().map(|x| format!("{}\n", x)).collect(),
+            Report::Matrix(x) => aligned(
+                &x.rows
+                    .iter()
+                    .map(|r| r.iter().map(|v| v.to_string()).collect())
+                    .collect::<Vec<Vec<String>>>(),
+            ),
+            Report::Table {
+                index,
+                columns,
+                rows,
+            } => {
+                let mut cells = vec![];
+                cells.push(
+                    std::iter::once(String::new())
+                        .chain(columns.iter().cloned())
+                        .collect(),
+                );
+                for (label, row) in index.iter().zip(rows.iter()) {
+                    cells.push(
+                        std::iter::once(label.clone())
+                            .chain(row.iter().map(|v| v.to_string()))
+                            .collect(),
+                    );
+                }
+                aligned(&cells)
+            }
+            Report::Fields(fields) => {
+                let mut out = String::new();
+                for (name, report) in fields {
+                    match report {
+                        Report::Scalar(_) | Report::Text(_) => {
+                            out.push_str(&format!("{}: {}", name, report.to_text()))
+                        }
+                        _ => {
+                            out.push_str(&format!("{}:\n", name));
+                            for line in report.to_text().lines() {
+                                out.push_str(&format!("  {}\n", line));
+                            }
+                        }
+                    }
+                }
+                out
+            }
+            Report::Text(text) => format!("{}\n", text),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::matrix::Matrix;
+
+    use super::Report;
+
+    #[test]
+    fn test_render() {
+        let report = Report::Fields(vec![
+            ("det".to_string(), Report::Scalar(-2.5)),
+            (
+                "x".to_string(),
+                Report::Matrix(Matrix::new(vec![vec![1.0, -10.0], vec![100.0, f64::NAN]]).unwrap()),
+            ),
+        ]);
+        assert_eq!(report.to_text(), "det: -2.5\nx:\n    1  -10\n  100  NaN\n");
+        assert_eq!(
+            report.to_json().to_string(),
+            "{\"det\":-2.5,\"x\":[[1.0,-10.0],[100.0,null]]}"
+        );
+
+        let table = Report::Table {
+            index: vec!["mean".to_string()],
+            columns: vec!["a".to_string(), "b".to_string()],
+            rows: vec![vec![0.5, 2.0]],
+        };
+        assert_eq!(table.to_text(), "        a  b\nmean  0.5  2\n");
+        assert_eq!(
+            table.to_json().to_string(),
+            "{\"a\":{\"mean\":0.5},\"b\":{\"mean\":2.0}}"
+        );
+    }
+}
@@ -0,0 +1,126 @@
+use std::io::{BufRead, BufReader, Read, Write};
+
+use crate::expr::{Session, Value};
+
+use super::{output::Report, Args, CliError};
+
+const PROMPT: &str = ">> ";
+
+const HELP: &str = "\
+Statements are expressions or assignments:
+  A = [1 2; 3 4]               matrix, elements separated by spaces or commas
+  b = [5; 6]
+  inv(A) * b                   the result is kept in `ans`
+  det(A), trace(A), eye(3), A', A^2, 2 * A - 1
+  roots(\"x^2 - 4\")             roots of a polynomial of order 1 or 2
+Commands:
+  vars                         variable names
+  history                      numbered list of the statements entered
+  !n                           runs statement n of the history again
+  exit, quit                   leaves, as does the end of input
+";
+
+fn report(value: &Value) -> Report {
+    match value {
+        Value::Scalar(x) => Report::Scalar(*x),
+        Value::Matrix(x) => Report::Matrix(x.clone()),
+    }
+}
+
+/// Reads statements from `input` line by line until `exit` or the end of input.
+/// Errors in a statement are printed, they do not end the session.
+pub fn repl(args: &Args, input: &mut dyn Read, output: &mut dyn Write) -> Result<(), CliError> {
+    let mut session = Session::new();
+    let mut lines = BufReader::new(input).lines();
+
+    loop {
+        write!(output, "{}", PROMPT)?;
+        output.flush()?;
+        let line = match lines.next() {
+            Some(line) => line?,
+            None => {
+                writeln!(output)?;
+                return Ok(());
+            }
+        };
+
+        let mut line = line.trim().to_string();
+        match line.as_str() {
+            "" => continue,
+            "exit" | "quit" => return Ok(()),
+            "help" => {
+                output.write_all(HELP.as_bytes())?;
+                continue;
+            }
+            "vars" => {
+                for name in session.names() {
+                    writeln!(output, "{}", name)?;
+                }
+                continue;
+            }
+            "history" => {
+                for (i, entry) in session.history().iter().enumerate() {
+                    writeln!(output, "{:>4}  {}", i + 1, entry)?;
+                }
+                continue;
+            }
+            _ => {}
+        }
+        if let Some(k) = line.strip_prefix('!') {
+            let entry = k
+                .parse::<usize>()
+                .ok()
+                .and_then(|k| session.history().get(k.wrapping_sub(1)));
+            match entry {
+                Some(entry) => {
+                    line = entry.clone();
+                    writeln!(output, "{}", line)?;
+                }
+                None => {
+                    writeln!(output, "error: no statement {} in the history", k)?;
+                    continue;
+                }
+            }
+        }
+
+        match session.eval(&line) {
+            Ok(value) if args.flag("json") => writeln!(output, "{}", report(&value).to_json())?,
+            Ok(value) => output.write_all(report(&value).to_text().as_bytes())?,
+            Err(err) => writeln!(output, "{}\nerror: {}", err.render(&line), err)?,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cli::run_with;
+
+    #[test]
+    fn test_repl() {
+        let input = "A = [2 0; 0 4]\nb = [4; 8]\ninv(A) * b\ndet(A\n!3\nhistory\nexit\n2\n";
+        let output = run_with(&["repl"], input).unwrap();
+        let expected = "\
+>> 2  0
+0  4
+>> 4
+8
+>> 2
+2
+>> det(A
+     ^
+error: expected ')'
+>> inv(A) * b
+2
+2
+>>    1  A = [2 0; 0 4]
+   2  b = [4; 8]
+   3  inv(A) * b
+   4  det(A
+   5  inv(A) * b
+>> ";
+        assert_eq!(output, expected);
+
+        let json = run_with(&["repl", "--json"], "roots(\"x^2 - 4\")\n").unwrap();
+        assert_eq!(json, ">> [[2.0],[-2.0]]\n>> \n");
+    }
+}
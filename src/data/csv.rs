@@ -0,0 +1,461 @@
+use std::{fs, path::Path};
+
+use crate::{
+    error::{BadTypeError, CustomErrors, EmptyVectorError, MismatchError, ParseError},
+    matrix::Matrix,
+};
+
+use super::table::{Series, Table};
+
+/// A column picked by position or by header name.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Column {
+    Index(usize),
+    Name(String),
+}
+
+impl From<usize> for Column {
+    fn from(index: usize) -> Self {
+        Column::Index(index)
+    }
+}
+
+impl From<&str> for Column {
+    fn from(name: &str) -> Self {
+        Column::Name(name.to_string())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CsvOptions {
+    header: bool,
+    delimiter: char,
+    quote: char,
+    features: Option<Vec<Column>>,
+    target: Option<Column>,
+    na_values: Vec<String>,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        CsvOptions::new()
+    }
+}
+
+impl CsvOptions {
+    /// Comma separated with a header row, every column is a feature and empty
+    /// or `NA` fields are missing.
+    pub fn new() -> CsvOptions {
+        CsvOptions {
+            header: true,
+            delimiter: ',',
+            quote: '"',
+            features: None,
+            target: None,
+            na_values: vec!["".to_string(), "NA".to_string()],
+        }
+    }
+
+    pub fn with_header(mut self, header: bool) -> CsvOptions {
+        self.header = header;
+        self
+    }
+
+    pub fn with_delimiter(mut self, delimiter: char) -> CsvOptions {
+        self.delimiter = delimiter;
+        self
+    }
+
+    pub fn with_quote(mut self, quote: char) -> CsvOptions {
+        self.quote = quote;
+        self
+    }
+
+    /// Feature columns in the given order, by default every column but the target.
+    pub fn with_features<C: Into<Column> + Clone>(mut self, columns: &[C]) -> CsvOptions {
+        self.features = Some(columns.iter().cloned().map(Into::into).collect());
+        self
+    }
+
+    /// Column read as string labels instead of numbers.
+    pub fn with_target<C: Into<Column>>(mut self, column: C) -> CsvOptions {
+        self.target = Some(column.into());
+        self
+    }
+
+    /// Field values read as NaN, on top of the ones `f64` parses as NaN.
+    pub fn with_na_values(mut self, values: &[&str]) -> CsvOptions {
+        self.na_values = values.iter().map(|v| v.to_string()).collect();
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvData {
+    pub features: Matrix<f64>,
+    /// Header names of the feature columns, empty without a header.
+    pub feature_names: Vec<String>,
+    pub targets: Option<Vec<String>>,
+}
+
+/// Records of `text` with the line each one starts on. Quoted fields may hold
+/// delimiters, newlines and doubled quotes.
+fn records(
+    text: &str,
+    delimiter: char,
+    quote: char,
+) -> Result<Vec<(usize, Vec<String>)>, CustomErrors> {
+    let mut records = vec![];
+    let mut record = vec![];
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut line = 1;
+    let mut start = 1;
+
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            if c == quote {
+                if chars.peek() == Some(&quote) {
+                    field.push(quote);
+                    chars.next();
+                } else {
+                    in_quotes = false;
+                }
+            } else {
+                if c == '\n' {
+                    line += 1;
+                }
+                field.push(c);
+            }
+        } else if c == quote && field.is_empty() {
+            in_quotes = true;
+        } else if c == delimiter {
+            record.push(std::mem::take(&mut field));
+        } else if c == '\n' {
+            if field.ends_with('\r') {
+                field.pop();
+            }
+            record.push(std::mem::take(&mut field));
+            // skip blank lines
+            if record.len() > 1 || !record[0].is_empty() {
+                records.push((start, std::mem::take(&mut record)));
+            } else {
+                record.clear();
+            }
+            line += 1;
+            start = line;
+        } else {
+            field.push(c);
+        }
+    }
+
+    if in_quotes {
+        return Err(CustomErrors::Parse(ParseError { line: start }));
+    }
+    if !record.is_empty() || !field.is_empty() {
+        record.push(field);
+        records.push((start, record));
+    }
+    Ok(records)
+}
+
+fn resolve(column: &Column, names: &[String], width: usize) -> Result<usize, CustomErrors> {
+    let index = match column {
+        Column::Index(index) => *index,
+        Column::Name(name) => match names.iter().position(|n| n == name) {
+            Some(index) => index,
+            None => return Err(CustomErrors::BadType(BadTypeError)),
+        },
+    };
+    if index >= width {
+        return Err(CustomErrors::BadType(BadTypeError));
+    }
+    Ok(index)
+}
+
+/// Parses CSV text into features and optional string targets. A row with the
+/// wrong number of fields or a feature that is not a number is reported with
+/// its line.
+pub fn parse_csv(text: &str, options: &CsvOptions) -> Result<CsvData, CustomErrors> {
+    let mut records = records(text, options.delimiter, options.quote)?.into_iter();
+
+    let names = if options.header {
+        match records.next() {
+            Some((_, names)) => names.iter().map(|n| n.trim().to_string()).collect(),
+            None => return Err(CustomErrors::EmptyVector(EmptyVectorError)),
+        }
+    } else {
+        vec![]
+    };
+
+    let rows: Vec<(usize, Vec<String>)> = records.collect();
+    let width = match (names.len(), rows.first()) {
+        (0, Some((_, first))) => first.len(),
+        (0, None) => return Err(CustomErrors::EmptyVector(EmptyVectorError)),
+        (n, _) => n,
+    };
+
+    let target = match &options.target {
+        Some(column) => Some(resolve(column, &names, width)?),
+        None => None,
+    };
+    let features = match &options.features {
+        Some(columns) => columns
+            .iter()
+            .map(|c| resolve(c, &names, width))
+            .collect::<Result<Vec<usize>, CustomErrors>>()?,
+        None => (0..width).filter(|j| Some(*j) != target).collect(),
+    };
+    if features.is_empty() || rows.is_empty() {
+        return Err(CustomErrors::EmptyVector(EmptyVectorError));
+    }
+
+    let mut data = vec![];
+    let mut targets = vec![];
+    for (line, fields) in rows {
+        if fields.len() != width {
+            return Err(CustomErrors::Parse(ParseError { line }));
+        }
+
+        let mut row = vec![];
+        for j in &features {
+            let field = fields[*j].trim();
+            if options.na_values.iter().any(|na| na == field) {
+                row.push(f64::NAN);
+                continue;
+            }
+            match field.parse::<f64>() {
+                Ok(value) => row.push(value),
+                Err(_) => return Err(CustomErrors::Parse(ParseError { line })),
+            }
+        }
+        data.push(row);
+
+        if let Some(t) = target {
+            targets.push(fields[t].trim().to_string());
+        }
+    }
+
+    let n = features.len();
+    Ok(CsvData {
+        features: Matrix {
+            m: data.len(),
+            rows: data,
+            n,
+        },
+        feature_names: features
+            .iter()
+            .filter_map(|j| names.get(*j).cloned())
+            .collect(),
+        targets: target.map(|_| targets),
+    })
+}
+
+pub fn read_csv<P: AsRef<Path>>(path: P, options: &CsvOptions) -> Result<CsvData, CustomErrors> {
+    parse_csv(&fs::read_to_string(path)?, options)
+}
+
+/// Parses CSV text into a table. Columns where every field is a number or
+/// missing are numeric, the others text. Without a header the columns are
+/// named by position, `0`, `1`, ..
+pub fn parse_table(text: &str, options: &CsvOptions) -> Result<Table, CustomErrors> {
+    let mut records = records(text, options.delimiter, options.quote)?.into_iter();
+    let names: Vec<String> = if options.header {
+        match records.next() {
+            Some((_, names)) => names.iter().map(|n| n.trim().to_string()).collect(),
+            None => return Err(CustomErrors::EmptyVector(EmptyVectorError)),
+        }
+    } else {
+        vec![]
+    };
+
+    let rows: Vec<(usize, Vec<String>)> = records.collect();
+    let width = match (names.len(), rows.first()) {
+        (0, Some((_, first))) => first.len(),
+        (0, None) => return Err(CustomErrors::EmptyVector(EmptyVectorError)),
+        (n, _) => n,
+    };
+    let mut columns: Vec<Vec<String>> = vec![vec![]; width];
+    for (line, fields) in rows {
+        if fields.len() != width {
+            return Err(CustomErrors::Parse(ParseError { line }));
+        }
+        for (column, field) in columns.iter_mut().zip(fields) {
+            column.push(field.trim().to_string());
+        }
+    }
+
+    let mut table = Table::new();
+    for (j, column) in columns.iter().enumerate() {
+        let numbers: Option<Vec<f64>> = column
+            .iter()
+            .map(|field| {
+                if options.na_values.contains(field) {
+                    Some(f64::NAN)
+                } else {
+                    field.parse().ok()
+                }
+            })
+            .collect();
+        let series = match numbers {
+            Some(numbers) => Series::Numeric(numbers),
+            None => Series::text(column),
+        };
+        match names.get(j) {
+            Some(name) => table.add_column(name, series)?,
+            None => table.add_column(&j.to_string(), series)?,
+        }
+    }
+    Ok(table)
+}
+
+pub fn read_table<P: AsRef<Path>>(path: P, options: &CsvOptions) -> Result<Table, CustomErrors> {
+    parse_table(&fs::read_to_string(path)?, options)
+}
+
+fn quote_field(field: &str, delimiter: char) -> String {
+    if field.contains(delimiter) || field.contains('"') || field.contains('\n') {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// CSV text of `x`, with an optional header row. Missing values are written as `NaN`.
+pub fn to_csv(
+    x: &Matrix<f64>,
+    header: Option<&[&str]>,
+    delimiter: char,
+) -> Result<String, CustomErrors> {
+    let separator = delimiter.to_string();
+    let mut out = String::new();
+    if let Some(names) = header {
+        if names.len() != x.n {
+            return Err(CustomErrors::Mismatch(MismatchError));
+        }
+        let fields: Vec<String> = names.iter().map(|n| quote_field(n, delimiter)).collect();
+        out.push_str(&fields.join(&separator));
+        out.push('\n');
+    }
+    for row in &x.rows {
+        let fields: Vec<String> = row.iter().map(|v| v.to_string()).collect();
+        out.push_str(&fields.join(&separator));
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+pub fn write_csv<P: AsRef<Path>>(
+    path: P,
+    x: &Matrix<f64>,
+    header: Option<&[&str]>,
+    delimiter: char,
+) -> Result<(), CustomErrors> {
+    fs::write(path, to_csv(x, header, delimiter)?)?;
+    Ok(())
+}
+
+/// One prediction per line under a `name` header.
+pub fn write_predictions<P: AsRef<Path>>(
+    path: P,
+    predictions: &[f64],
+    name: &str,
+) -> Result<(), CustomErrors> {
+    let x = Matrix {
+        rows: predictions.iter().map(|p| vec![*p]).collect(),
+        m: predictions.len(),
+        n: 1,
+    };
+    write_csv(path, &x, Some(&[name]), ',')
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        data::table::Series,
+        error::{CustomErrors, ParseError},
+        matrix::Matrix,
+        test_util::temp_path,
+    };
+
+    use super::{
+        parse_csv, parse_table, read_csv, to_csv, write_csv, write_predictions, CsvOptions,
+    };
+
+    #[test]
+    fn test_parse_csv() {
+        let text =
+            "name;a;b;label\n\"x; y\";1.5;NA;\"say \"\"hi\"\"\"\r\n\nz;-2;nan;\"two\nlines\"\n";
+        let options = CsvOptions::new()
+            .with_delimiter(';')
+            .with_features(&["b", "a"])
+            .with_target("label");
+        let data = parse_csv(text, &options).unwrap();
+
+        assert_eq!(data.feature_names, vec!["b", "a"]);
+        assert_eq!((data.features.m, data.features.n), (2, 2));
+        assert!(data.features.rows[0][0].is_nan() && data.features.rows[1][0].is_nan());
+        assert_eq!(data.features.rows[1][1], -2.0);
+        assert_eq!(data.targets.unwrap(), vec!["say \"hi\"", "two\nlines"]);
+
+        let data = parse_csv("1,2\n3,4", &CsvOptions::new().with_header(false)).unwrap();
+        assert_eq!(data.features.rows, vec![vec![1.0, 2.0], vec![3.0, 4.0]]);
+        assert!(data.feature_names.is_empty());
+
+        let table = parse_table("x,kind\n1.5,a\nNA,b\n", &CsvOptions::new()).unwrap();
+        assert_eq!(table.column("kind").unwrap(), &Series::text(&["a", "b"]));
+        match table.column("x").unwrap() {
+            Series::Numeric(x) => assert!(x[0] == 1.5 && x[1].is_nan()),
+            _ => panic!("x is numeric"),
+        }
+        let table = parse_table("1,2\n", &CsvOptions::new().with_header(false)).unwrap();
+        assert_eq!(table.column_names(), &vec!["0", "1"]);
+    }
+
+    #[test]
+    fn test_malformed_rows() {
+        let options = CsvOptions::new();
+        let parse = |text: &str| parse_csv(text, &options).unwrap_err();
+
+        assert_eq!(
+            parse("a,b\n1,2\n\n3\n"),
+            CustomErrors::Parse(ParseError { line: 4 })
+        );
+        assert_eq!(
+            parse("a,b\n1,2\n3,x\n"),
+            CustomErrors::Parse(ParseError { line: 3 })
+        );
+        assert_eq!(
+            parse("a,b\n1,\"2\n"),
+            CustomErrors::Parse(ParseError { line: 2 })
+        );
+        assert!(parse_csv("a,b\n1,2\n", &options.clone().with_target("c")).is_err());
+    }
+
+    #[test]
+    fn test_write_read_roundtrip() {
+        let x = Matrix::new(vec![vec![1.0, f64::NAN], vec![0.25, -3.0]]).unwrap();
+        assert_eq!(
+            to_csv(&x, Some(&["a", "b,c"]), ',').unwrap(),
+            "a,\"b,c\"\n1,NaN\n0.25,-3\n"
+        );
+
+        let path = temp_path("csv_test_roundtrip.csv");
+        write_csv(&path, &x, Some(&["a", "b"]), '\t').unwrap();
+        let data = read_csv(&path, &CsvOptions::new().with_delimiter('\t')).unwrap();
+        assert_eq!(data.features.rows[1], x.rows[1]);
+        assert!(data.features.rows[0][1].is_nan());
+
+        write_predictions(&path, &[1.0, 0.0], "pred").unwrap();
+        let data = read_csv(&path, &CsvOptions::new()).unwrap();
+        assert_eq!(data.feature_names, vec!["pred"]);
+        assert_eq!(data.features.rows, vec![vec![1.0], vec![0.0]]);
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(matches!(
+            read_csv(temp_path("csv_test_missing.csv"), &CsvOptions::new()),
+            Err(CustomErrors::Io(_))
+        ));
+    }
+}
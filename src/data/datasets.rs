@@ -0,0 +1,264 @@
+use std::{fs, path::Path};
+
+use crate::{
+    error::{CustomErrors, ParseError},
+    matrix::Matrix,
+};
+
+use super::{
+    csv::{parse_csv, CsvOptions},
+    encoding::LabelEncoder,
+};
+
+/// A reference dataset. Classification targets are class ids indexing
+/// `target_names`, regression datasets leave `target_names` empty.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Dataset {
+    pub features: Matrix<f64>,
+    pub targets: Vec<f64>,
+    pub feature_names: Vec<String>,
+    pub target_names: Vec<String>,
+}
+
+fn names(names: &[&str]) -> Vec<String> {
+    names.iter().map(|n| n.to_string()).collect()
+}
+
+/// Features of a header-less CSV with string labels in column `target`.
+fn labelled(text: &str, target: usize) -> Result<(Matrix<f64>, Vec<String>), CustomErrors> {
+    let options = CsvOptions::new().with_header(false).with_target(target);
+    let data = parse_csv(text, &options)?;
+    Ok((data.features, data.targets.unwrap_or_default()))
+}
+
+/// Rejects files whose rows don't have the expected `n` columns, checked
+/// before anything is sliced off them.
+fn check_width(features: &Matrix<f64>, n: usize) -> Result<(), CustomErrors> {
+    if features.n != n {
+        return Err(CustomErrors::Parse(ParseError { line: 1 }));
+    }
+    Ok(())
+}
+
+/// Maps raw labels onto class ids `0, 1, ..` in the order of `classes`.
+fn class_ids(labels: &[String], classes: &[&str]) -> Result<Vec<f64>, CustomErrors> {
+    let mut ids = vec![];
+    for (line, label) in labels.iter().enumerate() {
+        match classes.iter().position(|c| c == label) {
+            Some(id) => ids.push(id as f64),
+            None => return Err(CustomErrors::Parse(ParseError { line: line + 1 })),
+        }
+    }
+    Ok(ids)
+}
+
+/// The iris data shipped with the crate, see `get_iris_data`.
+pub fn load_iris() -> Dataset {
+    let options = CsvOptions::new().with_target("species");
+    // the embedded file is known to parse
+    let data = parse_csv(include_str!("iris.csv"), &options).unwrap();
+    let mut encoder = LabelEncoder::new();
+    let targets = encoder.fit_transform(&data.targets.unwrap()).unwrap();
+    Dataset {
+        features: data.features,
+        targets,
+        feature_names: data.feature_names,
+        target_names: encoder.classes().clone(),
+    }
+}
+
+const WINE_FEATURES: [&str; 13] = [
+    "alcohol",
+    "malic_acid",
+    "ash",
+    "alcalinity_of_ash",
+    "magnesium",
+    "total_phenols",
+    "flavanoids",
+    "nonflavanoid_phenols",
+    "proanthocyanins",
+    "color_intensity",
+    "hue",
+    "od280/od315_of_diluted_wines",
+    "proline",
+];
+
+fn parse_wine(text: &str) -> Result<Dataset, CustomErrors> {
+    let (features, labels) = labelled(text, 0)?;
+    check_width(&features, WINE_FEATURES.len())?;
+    Ok(Dataset {
+        features,
+        targets: class_ids(&labels, &["1", "2", "3"])?,
+        feature_names: names(&WINE_FEATURES),
+        target_names: names(&["class_0", "class_1", "class_2"]),
+    })
+}
+
+/// UCI wine recognition data (`wine.data`): the cultivar, 1 to 3, followed
+/// by 13 measurements.
+pub fn load_wine<P: AsRef<Path>>(path: P) -> Result<Dataset, CustomErrors> {
+    parse_wine(&fs::read_to_string(path)?)
+}
+
+fn parse_breast_cancer(text: &str) -> Result<Dataset, CustomErrors> {
+    let (features, labels) = labelled(text, 1)?;
+    check_width(&features, 31)?;
+    // drop the sample id
+    let features = Matrix {
+        rows: features.rows.into_iter().map(|r| r[1..].to_vec()).collect(),
+        m: features.m,
+        n: features.n - 1,
+    };
+
+    let measures = [
+        "radius",
+        "texture",
+        "perimeter",
+        "area",
+        "smoothness",
+        "compactness",
+        "concavity",
+        "concave_points",
+        "symmetry",
+        "fractal_dimension",
+    ];
+    let mut feature_names = vec![];
+    for kind in ["mean", "error", "worst"] {
+        for measure in measures {
+            feature_names.push(format!("{}_{}", kind, measure));
+        }
+    }
+
+    Ok(Dataset {
+        features,
+        targets: class_ids(&labels, &["M", "B"])?,
+        feature_names,
+        target_names: names(&["malignant", "benign"]),
+    })
+}
+
+/// UCI Wisconsin diagnostic breast cancer data (`wdbc.data`): an id, the
+/// diagnosis `M` or `B`, then 30 features.
+pub fn load_breast_cancer<P: AsRef<Path>>(path: P) -> Result<Dataset, CustomErrors> {
+    parse_breast_cancer(&fs::read_to_string(path)?)
+}
+
+fn parse_boston(text: &str) -> Result<Dataset, CustomErrors> {
+    // whitespace separated, keep the lines so errors point at the right one
+    let text: Vec<String> = text
+        .lines()
+        .map(|l| l.split_whitespace().collect::<Vec<&str>>().join(","))
+        .collect();
+    let data = parse_csv(&text.join("\n"), &CsvOptions::new().with_header(false))?;
+    check_width(&data.features, 14)?;
+    let n = 13;
+
+    let targets = data.features.rows.iter().map(|r| r[n]).collect();
+    Ok(Dataset {
+        features: Matrix {
+            rows: data.features.rows.iter().map(|r| r[..n].to_vec()).collect(),
+            m: data.features.m,
+            n,
+        },
+        targets,
+        feature_names: names(&[
+            "CRIM", "ZN", "INDUS", "CHAS", "NOX", "RM", "AGE", "DIS", "RAD", "TAX", "PTRATIO", "B",
+            "LSTAT",
+        ]),
+        target_names: vec![],
+    })
+}
+
+/// Boston-style house price regression data (`housing.data`): 13 whitespace
+/// separated features and the median value `MEDV` as target.
+pub fn load_boston<P: AsRef<Path>>(path: P) -> Result<Dataset, CustomErrors> {
+    parse_boston(&fs::read_to_string(path)?)
+}
+
+fn parse_digits(text: &str) -> Result<Dataset, CustomErrors> {
+    let (features, labels) = labelled(text, 64)?;
+    check_width(&features, 64)?;
+    let digits = ["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"];
+    let mut feature_names = vec![];
+    for row in 0..8 {
+        for col in 0..8 {
+            feature_names.push(format!("pixel_{}_{}", row, col));
+        }
+    }
+    Ok(Dataset {
+        features,
+        targets: class_ids(&labels, &digits)?,
+        feature_names,
+        target_names: names(&digits),
+    })
+}
+
+/// UCI optical handwritten digits (`optdigits.tra`, `optdigits.tes`): 64
+/// pixel counts of an 8x8 grid, 0 to 16, followed by the digit.
+pub fn load_digits<P: AsRef<Path>>(path: P) -> Result<Dataset, CustomErrors> {
+    parse_digits(&fs::read_to_string(path)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::{CustomErrors, ParseError};
+
+    use super::{load_iris, parse_boston, parse_breast_cancer, parse_digits, parse_wine};
+
+    #[test]
+    fn test_load_iris() {
+        let iris = load_iris();
+        assert_eq!((iris.features.m, iris.features.n), (150, 4));
+        assert_eq!(iris.feature_names[2], "petal_length");
+        assert_eq!(
+            iris.target_names,
+            vec!["Iris-setosa", "Iris-versicolor", "Iris-virginica"]
+        );
+        assert_eq!((iris.targets[0], iris.targets[149]), (0.0, 2.0));
+    }
+
+    #[test]
+    fn test_uci_formats() {
+        // first rows of the UCI files
+        let wine = parse_wine(
+            "1,14.23,1.71,2.43,15.6,127,2.8,3.06,.28,2.29,5.64,1.04,3.92,1065\n\
+             3,13.2,1.78,2.14,11.2,100,2.65,2.76,.26,1.28,4.38,1.05,3.4,1050\n",
+        )
+        .unwrap();
+        assert_eq!(wine.features.n, 13);
+        assert_eq!(wine.targets, vec![0.0, 2.0]);
+        assert_eq!(
+            parse_wine("4,1,1,1,1,1,1,1,1,1,1,1,1,1\n").unwrap_err(),
+            CustomErrors::Parse(ParseError { line: 1 })
+        );
+
+        let row = "17.99,10.38,122.8,1001,0.1184,0.2776,0.3001,0.1471,0.2419,0.07871,\
+                   1.095,0.9053,8.589,153.4,0.006399,0.04904,0.05373,0.01587,0.03003,0.006193,\
+                   25.38,17.33,184.6,2019,0.1622,0.6656,0.7119,0.2654,0.4601,0.1189";
+        let cancer = parse_breast_cancer(&format!("842302,M,{}\n842517,B,{}\n", row, row)).unwrap();
+        assert_eq!((cancer.features.n, cancer.feature_names.len()), (30, 30));
+        assert_eq!(cancer.features.rows[0][0], 17.99);
+        assert_eq!(cancer.targets, vec![0.0, 1.0]);
+
+        let boston = parse_boston(
+            " 0.00632  18.00   2.310  0  0.5380  6.5750  65.20  4.0900   1  296.0  15.30 396.90   4.98  24.00\n",
+        )
+        .unwrap();
+        assert_eq!((boston.features.n, boston.targets[0]), (13, 24.0));
+
+        let pixels = vec!["0"; 64].join(",");
+        let digits = parse_digits(&format!("{},7\n", pixels)).unwrap();
+        assert_eq!((digits.features.n, digits.targets[0]), (64, 7.0));
+
+        // a column short, or one too many for the digits
+        let short = CustomErrors::Parse(ParseError { line: 1 });
+        assert_eq!(
+            parse_wine("1,1,1,1,1,1,1,1,1,1,1,1,1\n").unwrap_err(),
+            short
+        );
+        assert_eq!(parse_boston("1 2 3\n").unwrap_err(), short);
+        let pixels = vec!["0"; 65].join(",");
+        assert_eq!(parse_digits(&format!("{},7\n", pixels)).unwrap_err(), short);
+    }
+}
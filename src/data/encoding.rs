@@ -0,0 +1,500 @@
+use crate::{
+    error::{BadTypeError, CustomErrors, EmptyVectorError, MismatchError, UnknownCategoryError},
+    matrix::{sparse::SparseMatrix, Matrix},
+    traits::{Fit, Transform},
+};
+
+/// What to do with a category that was not seen during `fit`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HandleUnknown {
+    /// Fail with an `UnknownCategory` error.
+    Error,
+    /// One-hot: all indicator columns are 0. Ordinal: the value is NaN.
+    Ignore,
+}
+
+/// Sorted distinct values.
+fn sorted_categories<S: AsRef<str>>(values: &[S]) -> Vec<String> {
+    let mut categories: Vec<String> = values.iter().map(|v| v.as_ref().to_string()).collect();
+    categories.sort();
+    categories.dedup();
+    categories
+}
+
+/// Splits rows of categorical values into columns, checking they are uniform.
+fn columns<S: AsRef<str>>(rows: &[Vec<S>]) -> Result<Vec<Vec<&str>>, CustomErrors> {
+    if rows.is_empty() || rows[0].is_empty() {
+        return Err(CustomErrors::EmptyVector(EmptyVectorError));
+    }
+    let n = rows[0].len();
+    if rows.iter().any(|row| row.len() != n) {
+        return Err(CustomErrors::Mismatch(MismatchError));
+    }
+
+    Ok((0..n)
+        .map(|j| rows.iter().map(|row| row[j].as_ref()).collect())
+        .collect())
+}
+
+fn check_fitted<S: AsRef<str>>(
+    categories: &[Vec<String>],
+    rows: &[Vec<S>],
+) -> Result<(), CustomErrors> {
+    if categories.is_empty() {
+        return Err(CustomErrors::EmptyVector(EmptyVectorError));
+    }
+    if rows.iter().any(|row| row.len() != categories.len()) {
+        return Err(CustomErrors::Mismatch(MismatchError));
+    }
+    Ok(())
+}
+
+/// Maps string labels onto class ids `0.0, 1.0, ..` in sorted label order.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LabelEncoder {
+    classes: Vec<String>,
+}
+
+impl LabelEncoder {
+    pub fn new() -> LabelEncoder {
+        LabelEncoder { classes: vec![] }
+    }
+
+    /// Known labels, position `i` is class id `i`.
+    pub fn classes(&self) -> &Vec<String> {
+        &self.classes
+    }
+
+    pub fn fit<S: AsRef<str>>(&mut self, labels: &[S]) -> Result<(), CustomErrors> {
+        if labels.is_empty() {
+            return Err(CustomErrors::EmptyVector(EmptyVectorError));
+        }
+        self.classes = sorted_categories(labels);
+        Ok(())
+    }
+
+    pub fn transform<S: AsRef<str>>(&self, labels: &[S]) -> Result<Vec<f64>, CustomErrors> {
+        if self.classes.is_empty() {
+            return Err(CustomErrors::EmptyVector(EmptyVectorError));
+        }
+
+        let mut ids = vec![];
+        for label in labels {
+            match self
+                .classes
+                .binary_search_by(|c| c.as_str().cmp(label.as_ref()))
+            {
+                Ok(id) => ids.push(id as f64),
+                Err(_) => return Err(CustomErrors::UnknownCategory(UnknownCategoryError)),
+            }
+        }
+        Ok(ids)
+    }
+
+    pub fn fit_transform<S: AsRef<str>>(&mut self, labels: &[S]) -> Result<Vec<f64>, CustomErrors> {
+        self.fit(labels)?;
+        self.transform(labels)
+    }
+
+    /// Labels for class ids, which must be whole numbers below `classes().len()`.
+    pub fn inverse_transform(&self, ids: &[f64]) -> Result<Vec<String>, CustomErrors> {
+        let mut labels = vec![];
+        for id in ids {
+            if id.fract() != 0.0 || *id < 0.0 {
+                return Err(CustomErrors::BadType(BadTypeError));
+            }
+            match self.classes.get(*id as usize) {
+                Some(label) => labels.push(label.clone()),
+                None => return Err(CustomErrors::UnknownCategory(UnknownCategoryError)),
+            }
+        }
+        Ok(labels)
+    }
+}
+
+impl Fit for LabelEncoder {
+    type Input = [String];
+    type Target = ();
+    type Output = ();
+
+    fn fit(&mut self, x: &[String], _y: &()) -> Result<(), CustomErrors> {
+        LabelEncoder::fit(self, x)
+    }
+}
+
+impl Transform for LabelEncoder {
+    type Input = [String];
+    type Output = Vec<f64>;
+
+    fn transform(&self, x: &[String]) -> Result<Vec<f64>, CustomErrors> {
+        LabelEncoder::transform(self, x)
+    }
+}
+
+/// One indicator column per (input column, category) pair, ordered by input
+/// column and then by sorted category.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OneHotEncoder {
+    handle_unknown: HandleUnknown,
+    categories: Vec<Vec<String>>,
+}
+
+impl Default for OneHotEncoder {
+    fn default() -> Self {
+        OneHotEncoder::new()
+    }
+}
+
+impl OneHotEncoder {
+    pub fn new() -> OneHotEncoder {
+        OneHotEncoder {
+            handle_unknown: HandleUnknown::Error,
+            categories: vec![],
+        }
+    }
+
+    pub fn with_handle_unknown(mut self, handle_unknown: HandleUnknown) -> OneHotEncoder {
+        self.handle_unknown = handle_unknown;
+        self
+    }
+
+    /// Learned categories of every input column.
+    pub fn categories(&self) -> &Vec<Vec<String>> {
+        &self.categories
+    }
+
+    /// Output column names as `<input name>=<category>`.
+    pub fn feature_names<S: AsRef<str>>(
+        &self,
+        input_names: &[S],
+    ) -> Result<Vec<String>, CustomErrors> {
+        if input_names.len() != self.categories.len() {
+            return Err(CustomErrors::Mismatch(MismatchError));
+        }
+        Ok(input_names
+            .iter()
+            .zip(self.categories.iter())
+            .flat_map(|(name, cats)| cats.iter().map(move |c| format!("{}={}", name.as_ref(), c)))
+            .collect())
+    }
+
+    pub fn fit<S: AsRef<str>>(&mut self, rows: &[Vec<S>]) -> Result<(), CustomErrors> {
+        self.categories = columns(rows)?
+            .iter()
+            .map(|col| sorted_categories(col))
+            .collect();
+        Ok(())
+    }
+
+    /// Indicator matrix in sparse form, one stored entry per known value.
+    pub fn transform_sparse<S: AsRef<str>>(
+        &self,
+        rows: &[Vec<S>],
+    ) -> Result<SparseMatrix, CustomErrors> {
+        check_fitted(&self.categories, rows)?;
+
+        let mut offsets = vec![0];
+        for cats in &self.categories {
+            offsets.push(offsets[offsets.len() - 1] + cats.len());
+        }
+
+        let mut triplets = vec![];
+        for (i, row) in rows.iter().enumerate() {
+            for (j, value) in row.iter().enumerate() {
+                match self.categories[j].binary_search_by(|c| c.as_str().cmp(value.as_ref())) {
+                    Ok(k) => triplets.push((i, offsets[j] + k, 1.0)),
+                    Err(_) => {
+                        if self.handle_unknown == HandleUnknown::Error {
+                            return Err(CustomErrors::UnknownCategory(UnknownCategoryError));
+                        }
+                    }
+                }
+            }
+        }
+        SparseMatrix::from_triplets(rows.len(), offsets[offsets.len() - 1], &triplets)
+    }
+
+    pub fn transform<S: AsRef<str>>(&self, rows: &[Vec<S>]) -> Result<Matrix<f64>, CustomErrors> {
+        Ok(self.transform_sparse(rows)?.to_dense())
+    }
+
+    /// Category of the set indicator in every block, `None` for all-zero blocks
+    /// (ignored unknown values).
+    pub fn inverse_transform(
+        &self,
+        x: &Matrix<f64>,
+    ) -> Result<Vec<Vec<Option<String>>>, CustomErrors> {
+        let width: usize = self.categories.iter().map(|c| c.len()).sum();
+        if x.n != width || width == 0 {
+            return Err(CustomErrors::Mismatch(MismatchError));
+        }
+
+        let mut rows = vec![];
+        for row in &x.rows {
+            let mut decoded = vec![];
+            let mut start = 0;
+            for cats in &self.categories {
+                let block = &row[start..start + cats.len()];
+                decoded.push(
+                    block
+                        .iter()
+                        .position(|v| *v != 0.0)
+                        .map(|k| cats[k].clone()),
+                );
+                start += cats.len();
+            }
+            rows.push(decoded);
+        }
+        Ok(rows)
+    }
+}
+
+impl Fit for OneHotEncoder {
+    type Input = [Vec<String>];
+    type Target = ();
+    type Output = ();
+
+    fn fit(&mut self, x: &[Vec<String>], _y: &()) -> Result<(), CustomErrors> {
+        OneHotEncoder::fit(self, x)
+    }
+}
+
+impl Transform for OneHotEncoder {
+    type Input = [Vec<String>];
+    type Output = Matrix<f64>;
+
+    fn transform(&self, x: &[Vec<String>]) -> Result<Matrix<f64>, CustomErrors> {
+        OneHotEncoder::transform(self, x)
+    }
+}
+
+/// Replaces every category with its position in the column's category list,
+/// either learned (sorted) or given by `with_categories`.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OrdinalEncoder {
+    handle_unknown: HandleUnknown,
+    user_categories: Option<Vec<Vec<String>>>,
+    categories: Vec<Vec<String>>,
+}
+
+impl Default for OrdinalEncoder {
+    fn default() -> Self {
+        OrdinalEncoder::new()
+    }
+}
+
+impl OrdinalEncoder {
+    pub fn new() -> OrdinalEncoder {
+        OrdinalEncoder {
+            handle_unknown: HandleUnknown::Error,
+            user_categories: None,
+            categories: vec![],
+        }
+    }
+
+    /// Fixed ordering per column, e.g. `["low", "medium", "high"]`.
+    pub fn with_categories<S: AsRef<str>>(mut self, categories: &[Vec<S>]) -> OrdinalEncoder {
+        self.user_categories = Some(
+            categories
+                .iter()
+                .map(|col| col.iter().map(|c| c.as_ref().to_string()).collect())
+                .collect(),
+        );
+        self
+    }
+
+    pub fn with_handle_unknown(mut self, handle_unknown: HandleUnknown) -> OrdinalEncoder {
+        self.handle_unknown = handle_unknown;
+        self
+    }
+
+    pub fn categories(&self) -> &Vec<Vec<String>> {
+        &self.categories
+    }
+
+    pub fn fit<S: AsRef<str>>(&mut self, rows: &[Vec<S>]) -> Result<(), CustomErrors> {
+        let cols = columns(rows)?;
+
+        match &self.user_categories {
+            None => self.categories = cols.iter().map(|col| sorted_categories(col)).collect(),
+            Some(user) => {
+                if user.len() != cols.len() {
+                    return Err(CustomErrors::Mismatch(MismatchError));
+                }
+                // the training data may not contain values outside the given order
+                for (col, cats) in cols.iter().zip(user.iter()) {
+                    if col.iter().any(|v| !cats.iter().any(|c| c == v)) {
+                        return Err(CustomErrors::UnknownCategory(UnknownCategoryError));
+                    }
+                }
+                self.categories = user.clone();
+            }
+        }
+        Ok(())
+    }
+
+    pub fn transform<S: AsRef<str>>(&self, rows: &[Vec<S>]) -> Result<Matrix<f64>, CustomErrors> {
+        check_fitted(&self.categories, rows)?;
+
+        let mut out = vec![];
+        for row in rows {
+            let mut encoded = vec![];
+            for (value, cats) in row.iter().zip(self.categories.iter()) {
+                match cats.iter().position(|c| c == value.as_ref()) {
+                    Some(k) => encoded.push(k as f64),
+                    None => match self.handle_unknown {
+                        HandleUnknown::Error => {
+                            return Err(CustomErrors::UnknownCategory(UnknownCategoryError))
+                        }
+                        HandleUnknown::Ignore => encoded.push(f64::NAN),
+                    },
+                }
+            }
+            out.push(encoded);
+        }
+
+        Ok(Matrix {
+            rows: out,
+            m: rows.len(),
+            n: self.categories.len(),
+        })
+    }
+
+    /// Categories for encoded values, `None` for NaN.
+    pub fn inverse_transform(
+        &self,
+        x: &Matrix<f64>,
+    ) -> Result<Vec<Vec<Option<String>>>, CustomErrors> {
+        if x.n != self.categories.len() || x.n == 0 {
+            return Err(CustomErrors::Mismatch(MismatchError));
+        }
+
+        let mut rows = vec![];
+        for row in &x.rows {
+            let mut decoded = vec![];
+            for (v, cats) in row.iter().zip(self.categories.iter()) {
+                if v.is_nan() {
+                    decoded.push(None);
+                    continue;
+                }
+                if v.fract() != 0.0 || *v < 0.0 {
+                    return Err(CustomErrors::BadType(BadTypeError));
+                }
+                match cats.get(*v as usize) {
+                    Some(c) => decoded.push(Some(c.clone())),
+                    None => return Err(CustomErrors::UnknownCategory(UnknownCategoryError)),
+                }
+            }
+            rows.push(decoded);
+        }
+        Ok(rows)
+    }
+}
+
+impl Fit for OrdinalEncoder {
+    type Input = [Vec<String>];
+    type Target = ();
+    type Output = ();
+
+    fn fit(&mut self, x: &[Vec<String>], _y: &()) -> Result<(), CustomErrors> {
+        OrdinalEncoder::fit(self, x)
+    }
+}
+
+impl Transform for OrdinalEncoder {
+    type Input = [Vec<String>];
+    type Output = Matrix<f64>;
+
+    fn transform(&self, x: &[Vec<String>]) -> Result<Matrix<f64>, CustomErrors> {
+        OrdinalEncoder::transform(self, x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        data::get_iris_data,
+        error::{CustomErrors, UnknownCategoryError},
+    };
+
+    use super::{HandleUnknown, LabelEncoder, OneHotEncoder, OrdinalEncoder};
+
+    #[test]
+    fn test_label_encoder() {
+        let (_, targets) = get_iris_data();
+
+        let mut encoder = LabelEncoder::new();
+        let ids = encoder.fit_transform(&targets).unwrap();
+        assert_eq!(encoder.classes().len(), 3);
+        assert_eq!(ids[0], 0.0);
+        assert_eq!(ids[ids.len() - 1], 2.0);
+
+        assert_eq!(
+            encoder.inverse_transform(&[2.0, 0.0]).unwrap(),
+            vec![encoder.classes()[2].clone(), encoder.classes()[0].clone()]
+        );
+        assert_eq!(
+            encoder.transform(&["Iris-unknown"]).unwrap_err(),
+            CustomErrors::UnknownCategory(UnknownCategoryError)
+        );
+        assert!(encoder.inverse_transform(&[0.5]).is_err());
+    }
+
+    #[test]
+    fn test_one_hot_encoder() {
+        let train = vec![vec!["red", "s"], vec!["green", "m"], vec!["red", "l"]];
+
+        let mut encoder = OneHotEncoder::new();
+        encoder.fit(&train).unwrap();
+        assert_eq!(
+            encoder.feature_names(&["color", "size"]).unwrap(),
+            vec!["color=green", "color=red", "size=l", "size=m", "size=s"]
+        );
+
+        let dense = encoder.transform(&[vec!["green", "s"]]).unwrap();
+        assert_eq!(dense.rows, vec![vec![1.0, 0.0, 0.0, 0.0, 1.0]]);
+
+        let sparse = encoder.transform_sparse(&train).unwrap();
+        assert_eq!(sparse.nnz(), 6);
+        assert_eq!(sparse.to_dense(), encoder.transform(&train).unwrap());
+
+        let unknown = vec![vec!["blue", "m"]];
+        assert!(encoder.transform(&unknown).is_err());
+
+        let encoder = encoder.with_handle_unknown(HandleUnknown::Ignore);
+        let out = encoder.transform(&unknown).unwrap();
+        assert_eq!(out.rows, vec![vec![0.0, 0.0, 0.0, 1.0, 0.0]]);
+        assert_eq!(
+            encoder.inverse_transform(&out).unwrap(),
+            vec![vec![None, Some("m".to_string())]]
+        );
+    }
+
+    #[test]
+    fn test_ordinal_encoder() {
+        let train = vec![vec!["low"], vec!["high"], vec!["medium"]];
+
+        let mut encoder = OrdinalEncoder::new().with_categories(&[vec!["low", "medium", "high"]]);
+        encoder.fit(&train).unwrap();
+        let out = encoder.transform(&train).unwrap();
+        assert_eq!(out.rows, vec![vec![0.0], vec![2.0], vec![1.0]]);
+        assert_eq!(
+            encoder.inverse_transform(&out).unwrap()[1],
+            vec![Some("high".to_string())]
+        );
+
+        // learned categories are sorted
+        let mut learned = OrdinalEncoder::new().with_handle_unknown(HandleUnknown::Ignore);
+        learned.fit(&train).unwrap();
+        assert_eq!(learned.categories()[0], vec!["high", "low", "medium"]);
+        assert!(learned.transform(&[vec!["extreme"]]).unwrap().rows[0][0].is_nan());
+
+        // training values must be part of a user given order
+        let mut strict = OrdinalEncoder::new().with_categories(&[vec!["low", "high"]]);
+        assert!(strict.fit(&train).is_err());
+    }
+}
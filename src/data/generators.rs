@@ -0,0 +1,280 @@
+use std::f64::consts::PI;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::{
+    error::{BadTypeError, CustomErrors, EmptyVectorError, NonUniformError},
+    matrix::Matrix,
+    stats::distributions::standard_normal,
+};
+
+fn matrix(rows: Vec<Vec<f64>>, n: usize) -> Matrix<f64> {
+    Matrix {
+        m: rows.len(),
+        rows,
+        n,
+    }
+}
+
+/// Features, targets and the true coefficients.
+type Regression = (Matrix<f64>, Vec<f64>, Vec<f64>);
+
+fn check_noise(noise: f64) -> Result<(), CustomErrors> {
+    if noise.is_nan() || noise < 0.0 {
+        return Err(CustomErrors::BadType(BadTypeError));
+    }
+    Ok(())
+}
+
+/// Isotropic Gaussian blobs around the given centers, sample `i` is drawn
+/// from center `i % centers.len()`, which is also its label.
+pub fn make_blobs(
+    n_samples: usize,
+    centers: &[Vec<f64>],
+    cluster_std: f64,
+    seed: u64,
+) -> Result<(Matrix<f64>, Vec<f64>), CustomErrors> {
+    if n_samples == 0 || centers.is_empty() || centers[0].is_empty() {
+        return Err(CustomErrors::EmptyVector(EmptyVectorError));
+    }
+    let n = centers[0].len();
+    if centers.iter().any(|c| c.len() != n) {
+        return Err(CustomErrors::NonUniform(NonUniformError));
+    }
+    check_noise(cluster_std)?;
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut rows = vec![];
+    let mut labels = vec![];
+    for i in 0..n_samples {
+        let k = i % centers.len();
+        rows.push(
+            centers[k]
+                .iter()
+                .map(|c| c + cluster_std * standard_normal(&mut rng))
+                .collect(),
+        );
+        labels.push(k as f64);
+    }
+    Ok((matrix(rows, n), labels))
+}
+
+/// Classes drawn as unit Gaussians around distinct vertices of a hypercube
+/// with side 2 in the first `n_informative` features. The other features are
+/// standard normal noise. Sample `i` belongs to class `i % n_classes`.
+pub fn make_classification(
+    n_samples: usize,
+    n_features: usize,
+    n_informative: usize,
+    n_classes: usize,
+    seed: u64,
+) -> Result<(Matrix<f64>, Vec<f64>), CustomErrors> {
+    if n_samples == 0 || n_features == 0 {
+        return Err(CustomErrors::EmptyVector(EmptyVectorError));
+    }
+    // every class needs a vertex of its own
+    if n_informative == 0
+        || n_informative > n_features
+        || n_classes < 2
+        || (n_informative < usize::BITS as usize && n_classes > 1 << n_informative)
+    {
+        return Err(CustomErrors::BadType(BadTypeError));
+    }
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    // random signs so the classes do not line up with the origin the same way every time
+    let signs: Vec<f64> = (0..n_informative)
+        .map(|_| if rng.gen::<bool>() { 1.0 } else { -1.0 })
+        .collect();
+
+    let mut rows = vec![];
+    let mut labels = vec![];
+    for i in 0..n_samples {
+        let k = i % n_classes;
+        let row = (0..n_features)
+            .map(|j| {
+                let noise = standard_normal(&mut rng);
+                if j < n_informative {
+                    let bit = k.checked_shr(j as u32).unwrap_or(0) & 1;
+                    signs[j] * (2.0 * bit as f64 - 1.0) + noise
+                } else {
+                    noise
+                }
+            })
+            .collect();
+        rows.push(row);
+        labels.push(k as f64);
+    }
+    Ok((matrix(rows, n_features), labels))
+}
+
+/// Linear targets `y = X b + noise * e` with standard normal features and
+/// errors. Only the first `n_informative` coefficients are non-zero, drawn
+/// uniformly from `[0, 100)`.
+pub fn make_regression(
+    n_samples: usize,
+    n_features: usize,
+    n_informative: usize,
+    noise: f64,
+    seed: u64,
+) -> Result<Regression, CustomErrors> {
+    if n_samples == 0 || n_features == 0 {
+        return Err(CustomErrors::EmptyVector(EmptyVectorError));
+    }
+    if n_informative > n_features {
+        return Err(CustomErrors::BadType(BadTypeError));
+    }
+    check_noise(noise)?;
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let coef: Vec<f64> = (0..n_features)
+        .map(|j| {
+            if j < n_informative {
+                100.0 * rng.gen::<f64>()
+            } else {
+                0.0
+            }
+        })
+        .collect();
+
+    let mut rows = vec![];
+    let mut targets = vec![];
+    for _ in 0..n_samples {
+        let row: Vec<f64> = (0..n_features).map(|_| standard_normal(&mut rng)).collect();
+        let y: f64 = row.iter().zip(coef.iter()).map(|(x, b)| x * b).sum();
+        targets.push(y + noise * standard_normal(&mut rng));
+        rows.push(row);
+    }
+    Ok((matrix(rows, n_features), targets, coef))
+}
+
+/// Two interleaving half circles, the upper one is class 0.
+pub fn make_moons(
+    n_samples: usize,
+    noise: f64,
+    seed: u64,
+) -> Result<(Matrix<f64>, Vec<f64>), CustomErrors> {
+    if n_samples < 2 {
+        return Err(CustomErrors::EmptyVector(EmptyVectorError));
+    }
+    check_noise(noise)?;
+
+    let n_outer = n_samples / 2;
+    let n_inner = n_samples - n_outer;
+    let angle = |i: usize, n: usize| {
+        if n > 1 {
+            PI * i as f64 / (n - 1) as f64
+        } else {
+            0.0
+        }
+    };
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut rows = vec![];
+    let mut labels = vec![];
+    for i in 0..n_outer {
+        let t = angle(i, n_outer);
+        rows.push(vec![t.cos(), t.sin()]);
+        labels.push(0.0);
+    }
+    for i in 0..n_inner {
+        let t = angle(i, n_inner);
+        rows.push(vec![1.0 - t.cos(), 0.5 - t.sin()]);
+        labels.push(1.0);
+    }
+    for row in rows.iter_mut() {
+        for v in row.iter_mut() {
+            *v += noise * standard_normal(&mut rng);
+        }
+    }
+    Ok((matrix(rows, 2), labels))
+}
+
+/// A unit circle (class 0) around a smaller one of radius `factor` (class 1).
+pub fn make_circles(
+    n_samples: usize,
+    factor: f64,
+    noise: f64,
+    seed: u64,
+) -> Result<(Matrix<f64>, Vec<f64>), CustomErrors> {
+    if n_samples < 2 {
+        return Err(CustomErrors::EmptyVector(EmptyVectorError));
+    }
+    if factor.is_nan() || factor <= 0.0 || factor >= 1.0 {
+        return Err(CustomErrors::BadType(BadTypeError));
+    }
+    check_noise(noise)?;
+
+    let n_outer = n_samples / 2;
+    let n_inner = n_samples - n_outer;
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut rows = vec![];
+    let mut labels = vec![];
+    for (label, radius, n) in [(0.0, 1.0, n_outer), (1.0, factor, n_inner)] {
+        for i in 0..n {
+            let t = 2.0 * PI * i as f64 / n as f64;
+            rows.push(vec![
+                radius * t.cos() + noise * standard_normal(&mut rng),
+                radius * t.sin() + noise * standard_normal(&mut rng),
+            ]);
+            labels.push(label);
+        }
+    }
+    Ok((matrix(rows, 2), labels))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        models::linear::LinearRegression,
+        vector::operations::{mean, stddev},
+    };
+
+    use super::{make_blobs, make_circles, make_classification, make_moons, make_regression};
+
+    #[test]
+    fn test_make_blobs_and_classification() {
+        let centers = vec![vec![0.0, 0.0], vec![10.0, -10.0]];
+        let (x, y) = make_blobs(2000, &centers, 2.0, 3).unwrap();
+        assert_eq!((x.m, x.n, y[1]), (2000, 2, 1.0));
+        assert_eq!(make_blobs(2000, &centers, 2.0, 3).unwrap().0, x);
+
+        let second: Vec<f64> = x.rows.iter().skip(1).step_by(2).map(|r| r[0]).collect();
+        assert!((mean(&second).unwrap() - 10.0).abs() < 0.2);
+        assert!((stddev(&second).unwrap() - 2.0).abs() < 0.2);
+
+        let (x, y) = make_classification(300, 5, 2, 4, 1).unwrap();
+        assert_eq!((x.m, x.n, y[3]), (300, 5, 3.0));
+        assert!(make_classification(300, 5, 2, 5, 1).is_err());
+    }
+
+    #[test]
+    fn test_make_regression() {
+        let (x, y, coef) = make_regression(200, 4, 2, 0.0, 5).unwrap();
+        assert_eq!((coef[2], coef[3]), (0.0, 0.0));
+
+        let mut ols = LinearRegression::new();
+        ols.fit(&x, &y).unwrap();
+        for (a, b) in ols.coefficients().iter().zip(coef.iter()) {
+            assert!((a - b).abs() < 1e-8);
+        }
+    }
+
+    #[test]
+    fn test_make_moons_and_circles() {
+        let (x, y) = make_moons(101, 0.0, 0).unwrap();
+        assert_eq!((x.m, y.iter().sum::<f64>()), (101, 51.0));
+        assert_eq!(x.rows[0], vec![1.0, 0.0]);
+        assert_eq!(x.rows[50], vec![0.0, 0.5]);
+
+        let (x, y) = make_circles(100, 0.5, 0.0, 0).unwrap();
+        for (row, label) in x.rows.iter().zip(y.iter()) {
+            let radius = (row[0] * row[0] + row[1] * row[1]).sqrt();
+            let expected = if *label == 0.0 { 1.0 } else { 0.5 };
+            assert!((radius - expected).abs() < 1e-12);
+        }
+        assert!(make_circles(100, 1.5, 0.0, 0).is_err());
+    }
+}
@@ -0,0 +1,453 @@
+use std::cmp::Ordering;
+
+use crate::{
+    error::{BadTypeError, CustomErrors, EmptyVectorError, MismatchError},
+    matrix::Matrix,
+    vector::operations::{mean, quantile, stddev},
+};
+
+/// The values of one table column.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Series {
+    /// NaN marks a missing value.
+    Numeric(Vec<f64>),
+    /// Codes into `categories`, which also give the sort order.
+    Categorical {
+        codes: Vec<usize>,
+        categories: Vec<String>,
+    },
+    Text(Vec<String>),
+}
+
+/// A single cell, categories are read as their label.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Number(f64),
+    Text(String),
+}
+
+impl Series {
+    /// Categorical series with the sorted distinct labels as categories.
+    pub fn categorical<S: AsRef<str>>(labels: &[S]) -> Series {
+        let mut categories: Vec<String> = labels.iter().map(|l| l.as_ref().to_string()).collect();
+        categories.sort();
+        categories.dedup();
+        let codes = labels
+            .iter()
+            .map(|l| {
+                categories
+                    .binary_search_by(|c| c.as_str().cmp(l.as_ref()))
+                    .unwrap()
+            })
+            .collect();
+        Series::Categorical { codes, categories }
+    }
+
+    pub fn text<S: AsRef<str>>(values: &[S]) -> Series {
+        Series::Text(values.iter().map(|v| v.as_ref().to_string()).collect())
+    }
+
+    pub fn len(&self) -> usize {
+        match self {
+            Series::Numeric(values) => values.len(),
+            Series::Categorical { codes, .. } => codes.len(),
+            Series::Text(values) => values.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Panics when `i` is out of range.
+    pub fn get(&self, i: usize) -> Value {
+        match self {
+            Series::Numeric(values) => Value::Number(values[i]),
+            Series::Categorical { codes, categories } => Value::Text(categories[codes[i]].clone()),
+            Series::Text(values) => Value::Text(values[i].clone()),
+        }
+    }
+
+    fn take(&self, indices: &[usize]) -> Series {
+        match self {
+            Series::Numeric(values) => {
+                Series::Numeric(indices.iter().map(|i| values[*i]).collect())
+            }
+            Series::Categorical { codes, categories } => Series::Categorical {
+                codes: indices.iter().map(|i| codes[*i]).collect(),
+                categories: categories.clone(),
+            },
+            Series::Text(values) => {
+                Series::Text(indices.iter().map(|i| values[*i].clone()).collect())
+            }
+        }
+    }
+
+    /// Order of rows `a` and `b`, NaN after every number.
+    fn compare(&self, a: usize, b: usize) -> Ordering {
+        match self {
+            Series::Numeric(values) => match (values[a].is_nan(), values[b].is_nan()) {
+                (false, false) => values[a].total_cmp(&values[b]),
+                (x, y) => x.cmp(&y),
+            },
+            Series::Categorical { codes, .. } => codes[a].cmp(&codes[b]),
+            Series::Text(values) => values[a].cmp(&values[b]),
+        }
+    }
+
+    fn numeric(&self) -> Result<&Vec<f64>, CustomErrors> {
+        match self {
+            Series::Numeric(values) => Ok(values),
+            _ => Err(CustomErrors::BadType(BadTypeError)),
+        }
+    }
+}
+
+/// Reduction applied to the rows of every group.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Aggregate {
+    Count,
+    Sum,
+    Mean,
+    /// Population standard deviation, like `vector::operations::stddev`.
+    Std,
+    Min,
+    Max,
+}
+
+impl Aggregate {
+    fn name(&self) -> &str {
+        match self {
+            Aggregate::Count => "count",
+            Aggregate::Sum => "sum",
+            Aggregate::Mean => "mean",
+            Aggregate::Std => "std",
+            Aggregate::Min => "min",
+            Aggregate::Max => "max",
+        }
+    }
+
+    /// Missing values are skipped, NaN when nothing is left.
+    fn apply(&self, values: &[f64]) -> f64 {
+        let present: Vec<f64> = values.iter().copied().filter(|v| !v.is_nan()).collect();
+        match self {
+            Aggregate::Count => present.len() as f64,
+            _ if present.is_empty() => f64::NAN,
+            Aggregate::Sum => present.iter().sum(),
+            Aggregate::Mean => mean(&present).unwrap_or(f64::NAN),
+            Aggregate::Std => stddev(&present).unwrap_or(f64::NAN),
+            Aggregate::Min => present.iter().copied().fold(f64::INFINITY, f64::min),
+            Aggregate::Max => present.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+        }
+    }
+}
+
+/// Named columns of equal length, each with its own type.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Table {
+    names: Vec<String>,
+    columns: Vec<Series>,
+}
+
+impl Table {
+    pub fn new() -> Table {
+        Table {
+            names: vec![],
+            columns: vec![],
+        }
+    }
+
+    pub fn from_columns(columns: Vec<(&str, Series)>) -> Result<Table, CustomErrors> {
+        let mut table = Table::new();
+        for (name, series) in columns {
+            table.add_column(name, series)?;
+        }
+        Ok(table)
+    }
+
+    /// Fails on a duplicate name, a length different from the other columns or
+    /// a category code out of range.
+    pub fn add_column(&mut self, name: &str, series: Series) -> Result<(), CustomErrors> {
+        if self.index(name).is_ok() {
+            return Err(CustomErrors::BadType(BadTypeError));
+        }
+        if !self.columns.is_empty() && series.len() != self.n_rows() {
+            return Err(CustomErrors::Mismatch(MismatchError));
+        }
+        if let Series::Categorical { codes, categories } = &series {
+            if codes.iter().any(|c| *c >= categories.len()) {
+                return Err(CustomErrors::BadType(BadTypeError));
+            }
+        }
+        self.names.push(name.to_string());
+        self.columns.push(series);
+        Ok(())
+    }
+
+    pub fn n_rows(&self) -> usize {
+        self.columns.first().map_or(0, |c| c.len())
+    }
+
+    pub fn n_columns(&self) -> usize {
+        self.columns.len()
+    }
+
+    pub fn column_names(&self) -> &Vec<String> {
+        &self.names
+    }
+
+    fn index(&self, name: &str) -> Result<usize, CustomErrors> {
+        match self.names.iter().position(|n| n == name) {
+            Some(index) => Ok(index),
+            None => Err(CustomErrors::BadType(BadTypeError)),
+        }
+    }
+
+    pub fn column(&self, name: &str) -> Result<&Series, CustomErrors> {
+        Ok(&self.columns[self.index(name)?])
+    }
+
+    /// New table with the given columns, in that order.
+    pub fn select(&self, names: &[&str]) -> Result<Table, CustomErrors> {
+        let mut table = Table::new();
+        for name in names {
+            table.add_column(name, self.column(name)?.clone())?;
+        }
+        Ok(table)
+    }
+
+    /// New table with the rows at `indices`. Panics on an index out of range.
+    pub fn take(&self, indices: &[usize]) -> Table {
+        Table {
+            names: self.names.clone(),
+            columns: self.columns.iter().map(|c| c.take(indices)).collect(),
+        }
+    }
+
+    /// Rows whose value in `column` satisfies `predicate`.
+    pub fn filter<F: Fn(&Value) -> bool>(
+        &self,
+        column: &str,
+        predicate: F,
+    ) -> Result<Table, CustomErrors> {
+        let series = self.column(column)?;
+        let indices: Vec<usize> = (0..self.n_rows())
+            .filter(|i| predicate(&series.get(*i)))
+            .collect();
+        Ok(self.take(&indices))
+    }
+
+    /// Stable sort on one column. Missing numbers go last in either direction.
+    pub fn sort_by(&self, column: &str, ascending: bool) -> Result<Table, CustomErrors> {
+        let series = self.column(column)?;
+        let mut order: Vec<usize> = (0..self.n_rows()).collect();
+        order.sort_by(|a, b| {
+            let ordering = series.compare(*a, *b);
+            let missing = |i: usize| matches!(series, Series::Numeric(v) if v[i].is_nan());
+            if ascending || missing(*a) || missing(*b) {
+                ordering
+            } else {
+                ordering.reverse()
+            }
+        });
+        Ok(self.take(&order))
+    }
+
+    /// One row per distinct value of `key`, in sorted order, with a column
+    /// `<column>_<aggregate>` for every requested aggregate. Aggregated columns
+    /// must be numeric, except for `Count`.
+    pub fn group_by(
+        &self,
+        key: &str,
+        aggregates: &[(&str, Aggregate)],
+    ) -> Result<Table, CustomErrors> {
+        let keys = self.column(key)?;
+        let mut order: Vec<usize> = (0..self.n_rows()).collect();
+        order.sort_by(|a, b| keys.compare(*a, *b));
+
+        let mut groups: Vec<Vec<usize>> = vec![];
+        for i in order {
+            match groups.last_mut() {
+                Some(group) if keys.get(group[0]) == keys.get(i) => group.push(i),
+                _ => groups.push(vec![i]),
+            }
+        }
+        // NaN keys never compare equal, keep them together
+        if let Series::Numeric(values) = keys {
+            let (missing, mut present): (Vec<Vec<usize>>, Vec<Vec<usize>>) =
+                groups.into_iter().partition(|g| values[g[0]].is_nan());
+            if !missing.is_empty() {
+                present.push(missing.concat());
+            }
+            groups = present;
+        }
+
+        let firsts: Vec<usize> = groups.iter().map(|g| g[0]).collect();
+        let mut table = Table::new();
+        table.add_column(key, keys.take(&firsts))?;
+        for (column, aggregate) in aggregates {
+            let series = self.column(column)?;
+            let values = match (series, aggregate) {
+                (Series::Numeric(values), _) => values.clone(),
+                // every non numeric value counts once
+                (_, Aggregate::Count) => vec![0.0; series.len()],
+                _ => return Err(CustomErrors::BadType(BadTypeError)),
+            };
+            let out = groups
+                .iter()
+                .map(|g| aggregate.apply(&g.iter().map(|i| values[*i]).collect::<Vec<f64>>()))
+                .collect();
+            table.add_column(
+                &format!("{}_{}", column, aggregate.name()),
+                Series::Numeric(out),
+            )?;
+        }
+        Ok(table)
+    }
+
+    /// Model input from the given columns, categories become their codes.
+    /// Text columns can not be converted.
+    pub fn to_matrix(&self, columns: &[&str]) -> Result<Matrix<f64>, CustomErrors> {
+        if columns.is_empty() || self.n_rows() == 0 {
+            return Err(CustomErrors::EmptyVector(EmptyVectorError));
+        }
+
+        let mut rows = vec![vec![]; self.n_rows()];
+        for name in columns {
+            let values: Vec<f64> = match self.column(name)? {
+                Series::Numeric(values) => values.clone(),
+                Series::Categorical { codes, .. } => codes.iter().map(|c| *c as f64).collect(),
+                Series::Text(_) => return Err(CustomErrors::BadType(BadTypeError)),
+            };
+            for (row, v) in rows.iter_mut().zip(values) {
+                row.push(v);
+            }
+        }
+        Ok(Matrix {
+            m: rows.len(),
+            rows,
+            n: columns.len(),
+        })
+    }
+
+    /// Count, mean, std, min, quartiles and max of every numeric column,
+    /// ignoring missing values.
+    pub fn describe(&self) -> Result<Table, CustomErrors> {
+        let statistics = ["count", "mean", "std", "min", "25%", "50%", "75%", "max"];
+        let mut table = Table::new();
+        table.add_column("statistic", Series::text(&statistics))?;
+
+        for (name, series) in self.names.iter().zip(self.columns.iter()) {
+            let values = match series.numeric() {
+                Ok(values) => values,
+                Err(_) => continue,
+            };
+            let present: Vec<f64> = values.iter().copied().filter(|v| !v.is_nan()).collect();
+            let summary = if present.is_empty() {
+                let mut summary = vec![f64::NAN; statistics.len()];
+                summary[0] = 0.0;
+                summary
+            } else {
+                vec![
+                    present.len() as f64,
+                    mean(&present)?,
+                    stddev(&present)?,
+                    quantile(&present, 0.0)?,
+                    quantile(&present, 0.25)?,
+                    quantile(&present, 0.5)?,
+                    quantile(&present, 0.75)?,
+                    quantile(&present, 1.0)?,
+                ]
+            };
+            table.add_column(name, Series::Numeric(summary))?;
+        }
+
+        if table.n_columns() == 1 {
+            return Err(CustomErrors::EmptyVector(EmptyVectorError));
+        }
+        Ok(table)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Aggregate, Series, Table, Value};
+
+    fn pets() -> Table {
+        Table::from_columns(vec![
+            ("name", Series::text(&["rex", "tom", "kit", "fido", "bob"])),
+            (
+                "kind",
+                Series::categorical(&["dog", "cat", "cat", "dog", "dog"]),
+            ),
+            (
+                "weight",
+                Series::Numeric(vec![30.0, 4.0, f64::NAN, 12.0, 21.0]),
+            ),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn test_select_filter_sort() {
+        let mut table = pets();
+        assert_eq!((table.n_rows(), table.n_columns()), (5, 3));
+        assert!(table.add_column("x", Series::Numeric(vec![1.0])).is_err());
+
+        let dogs = table
+            .filter("kind", |v| *v == Value::Text("dog".to_string()))
+            .unwrap();
+        assert_eq!(
+            dogs.column("name").unwrap(),
+            &Series::text(&["rex", "fido", "bob"])
+        );
+
+        let sorted = table.sort_by("weight", false).unwrap();
+        assert_eq!(
+            sorted.column("name").unwrap(),
+            &Series::text(&["rex", "bob", "fido", "tom", "kit"])
+        );
+
+        let x = table
+            .select(&["weight", "kind"])
+            .unwrap()
+            .to_matrix(&["kind", "weight"])
+            .unwrap();
+        assert_eq!(x.rows[1], vec![0.0, 4.0]);
+        assert!(table.to_matrix(&["name"]).is_err());
+    }
+
+    #[test]
+    fn test_group_by_and_describe() {
+        let table = pets();
+        let grouped = table
+            .group_by(
+                "kind",
+                &[("weight", Aggregate::Mean), ("name", Aggregate::Count)],
+            )
+            .unwrap();
+        assert_eq!(
+            grouped.column("kind").unwrap().get(0),
+            Value::Text("cat".to_string())
+        );
+        assert_eq!(
+            grouped.column("weight_mean").unwrap(),
+            &Series::Numeric(vec![4.0, 21.0])
+        );
+        assert_eq!(
+            grouped.column("name_count").unwrap(),
+            &Series::Numeric(vec![2.0, 3.0])
+        );
+        assert!(table.group_by("kind", &[("name", Aggregate::Sum)]).is_err());
+
+        let summary = table.describe().unwrap();
+        assert_eq!(summary.column_names(), &vec!["statistic", "weight"]);
+        let weight = summary.column("weight").unwrap();
+        assert_eq!(weight.get(0), Value::Number(4.0));
+        assert_eq!(weight.get(1), Value::Number(16.75));
+        assert_eq!(weight.get(5), Value::Number(16.5));
+        assert_eq!(weight.get(7), Value::Number(30.0));
+    }
+}
@@ -0,0 +1,320 @@
+use crate::{
+    error::{BadTypeError, CustomErrors, EmptyVectorError, MismatchError},
+    matrix::{decomposition::cholesky_inverse, Matrix},
+    vector::operations::cosine_similarity,
+};
+
+/// A dissimilarity between two points of equal dimension.
+pub trait Distance {
+    fn distance(&self, a: &[f64], b: &[f64]) -> Result<f64, CustomErrors>;
+
+    /// Lower bound on the distance between two points whose coordinates differ by
+    /// `diff` along a single axis. Space partitioning indexes (the KD-tree) need it to
+    /// prune, metrics that can't provide one return `None`.
+    fn coordinate_bound(&self, _diff: f64) -> Option<f64> {
+        None
+    }
+
+    /// Whether the triangle inequality holds, which the ball tree needs to prune.
+    fn is_metric(&self) -> bool {
+        false
+    }
+}
+
+fn check_dims(a: &[f64], b: &[f64]) -> Result<(), CustomErrors> {
+    if a.len() != b.len() {
+        return Err(CustomErrors::Mismatch(MismatchError));
+    }
+    if a.is_empty() {
+        return Err(CustomErrors::EmptyVector(EmptyVectorError));
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Euclidean;
+
+impl Distance for Euclidean {
+    fn distance(&self, a: &[f64], b: &[f64]) -> Result<f64, CustomErrors> {
+        Ok(SquaredEuclidean.distance(a, b)?.sqrt())
+    }
+
+    fn coordinate_bound(&self, diff: f64) -> Option<f64> {
+        Some(diff.abs())
+    }
+
+    fn is_metric(&self) -> bool {
+        true
+    }
+}
+
+/// Not a metric (no triangle inequality), but cheaper and order preserving.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SquaredEuclidean;
+
+impl Distance for SquaredEuclidean {
+    fn distance(&self, a: &[f64], b: &[f64]) -> Result<f64, CustomErrors> {
+        check_dims(a, b)?;
+        Ok(a.iter().zip(b.iter()).map(|(x, y)| (x - y).powi(2)).sum())
+    }
+
+    fn coordinate_bound(&self, diff: f64) -> Option<f64> {
+        Some(diff * diff)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Manhattan;
+
+impl Distance for Manhattan {
+    fn distance(&self, a: &[f64], b: &[f64]) -> Result<f64, CustomErrors> {
+        check_dims(a, b)?;
+        Ok(a.iter().zip(b.iter()).map(|(x, y)| (x - y).abs()).sum())
+    }
+
+    fn coordinate_bound(&self, diff: f64) -> Option<f64> {
+        Some(diff.abs())
+    }
+
+    fn is_metric(&self) -> bool {
+        true
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Chebyshev;
+
+impl Distance for Chebyshev {
+    fn distance(&self, a: &[f64], b: &[f64]) -> Result<f64, CustomErrors> {
+        check_dims(a, b)?;
+        Ok(a.iter()
+            .zip(b.iter())
+            .map(|(x, y)| (x - y).abs())
+            .fold(0.0, f64::max))
+    }
+
+    fn coordinate_bound(&self, diff: f64) -> Option<f64> {
+        Some(diff.abs())
+    }
+
+    fn is_metric(&self) -> bool {
+        true
+    }
+}
+
+/// `p = 1` is Manhattan, `p = 2` Euclidean and `p -> inf` approaches Chebyshev.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Minkowski {
+    pub p: f64,
+}
+
+impl Minkowski {
+    pub fn new(p: f64) -> Result<Minkowski, CustomErrors> {
+        // below 1 the triangle inequality no longer holds
+        if p.is_nan() || p < 1.0 {
+            return Err(CustomErrors::BadType(BadTypeError));
+        }
+        Ok(Minkowski { p })
+    }
+}
+
+impl Distance for Minkowski {
+    fn distance(&self, a: &[f64], b: &[f64]) -> Result<f64, CustomErrors> {
+        check_dims(a, b)?;
+        let s: f64 = a
+            .iter()
+            .zip(b.iter())
+            .map(|(x, y)| (x - y).abs().powf(self.p))
+            .sum();
+        Ok(s.powf(1.0 / self.p))
+    }
+
+    fn coordinate_bound(&self, diff: f64) -> Option<f64> {
+        Some(diff.abs())
+    }
+
+    fn is_metric(&self) -> bool {
+        true
+    }
+}
+
+/// `1 - cosine_similarity`. Zero vectors have no direction and are rejected.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Cosine;
+
+impl Distance for Cosine {
+    fn distance(&self, a: &[f64], b: &[f64]) -> Result<f64, CustomErrors> {
+        check_dims(a, b)?;
+        if a.iter().all(|v| *v == 0.0) || b.iter().all(|v| *v == 0.0) {
+            return Err(CustomErrors::EmptyVector(EmptyVectorError));
+        }
+        Ok(1.0 - cosine_similarity(&a.to_vec(), &b.to_vec())?)
+    }
+}
+
+/// Fraction of coordinates that differ.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Hamming;
+
+impl Distance for Hamming {
+    fn distance(&self, a: &[f64], b: &[f64]) -> Result<f64, CustomErrors> {
+        check_dims(a, b)?;
+        let differing = a.iter().zip(b.iter()).filter(|(x, y)| x != y).count();
+        Ok(differing as f64 / a.len() as f64)
+    }
+
+    fn is_metric(&self) -> bool {
+        true
+    }
+}
+
+/// `sqrt((a - b)^T S^-1 (a - b))` for a covariance matrix `S`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Mahalanobis {
+    inv_cov: Matrix<f64>,
+}
+
+impl Mahalanobis {
+    /// Takes the already inverted covariance matrix.
+    pub fn new(inv_cov: Matrix<f64>) -> Result<Mahalanobis, CustomErrors> {
+        if inv_cov.m != inv_cov.n {
+            return Err(CustomErrors::Mismatch(MismatchError));
+        }
+        Ok(Mahalanobis { inv_cov })
+    }
+
+    pub fn from_covariance(cov: &Matrix<f64>) -> Result<Mahalanobis, CustomErrors> {
+        Mahalanobis::new(cholesky_inverse(cov)?)
+    }
+}
+
+impl Distance for Mahalanobis {
+    fn distance(&self, a: &[f64], b: &[f64]) -> Result<f64, CustomErrors> {
+        check_dims(a, b)?;
+        if a.len() != self.inv_cov.n {
+            return Err(CustomErrors::Mismatch(MismatchError));
+        }
+
+        let diff: Vec<f64> = a.iter().zip(b.iter()).map(|(x, y)| x - y).collect();
+        let mut q = 0.0;
+        for (row, d_i) in self.inv_cov.rows.iter().zip(diff.iter()) {
+            let row_dot: f64 = row.iter().zip(diff.iter()).map(|(s, d)| s * d).sum();
+            q += d_i * row_dot;
+        }
+        Ok(q.max(0.0).sqrt())
+    }
+
+    fn is_metric(&self) -> bool {
+        true
+    }
+}
+
+/// Distance between every row of `a` (rows of the result) and every row of `b`
+/// (columns of the result).
+pub fn pairwise_distances<D: Distance + ?Sized>(
+    a: &Matrix<f64>,
+    b: &Matrix<f64>,
+    metric: &D,
+) -> Result<Matrix<f64>, CustomErrors> {
+    if a.n != b.n {
+        return Err(CustomErrors::Mismatch(MismatchError));
+    }
+
+    let mut rows = vec![];
+    for pa in &a.rows {
+        let mut row = vec![];
+        for pb in &b.rows {
+            row.push(metric.distance(pa, pb)?);
+        }
+        rows.push(row)
+    }
+
+    Matrix::new(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        error::{CustomErrors, MismatchError},
+        matrix::Matrix,
+        vector::operations::cosine_similarity,
+    };
+
+    use super::{
+        pairwise_distances, Chebyshev, Cosine, Distance, Euclidean, Hamming, Mahalanobis,
+        Manhattan, Minkowski, SquaredEuclidean,
+    };
+
+    #[test]
+    fn test_basic_metrics() {
+        let a = [0.0, 0.0];
+        let b = [3.0, -4.0];
+
+        assert_eq!(Euclidean.distance(&a, &b).unwrap(), 5.0);
+        assert_eq!(SquaredEuclidean.distance(&a, &b).unwrap(), 25.0);
+        assert_eq!(Manhattan.distance(&a, &b).unwrap(), 7.0);
+        assert_eq!(Chebyshev.distance(&a, &b).unwrap(), 4.0);
+        assert_eq!(Minkowski::new(1.0).unwrap().distance(&a, &b).unwrap(), 7.0);
+        assert!((Minkowski::new(2.0).unwrap().distance(&a, &b).unwrap() - 5.0).abs() < 1e-12);
+        assert_eq!(
+            Hamming
+                .distance(&[1.0, 0.0, 1.0, 1.0], &[1.0, 1.0, 0.0, 1.0])
+                .unwrap(),
+            0.5
+        );
+
+        assert_eq!(
+            Euclidean.distance(&[1.0], &b).unwrap_err(),
+            CustomErrors::Mismatch(MismatchError)
+        );
+        assert!(Minkowski::new(0.5).is_err());
+    }
+
+    #[test]
+    fn test_cosine() {
+        assert!(Cosine.distance(&[1.0, 0.0], &[2.0, 0.0]).unwrap().abs() < 1e-12);
+        assert!((Cosine.distance(&[1.0, 0.0], &[0.0, 3.0]).unwrap() - 1.0).abs() < 1e-12);
+        assert!((Cosine.distance(&[1.0, 1.0], &[-1.0, -1.0]).unwrap() - 2.0).abs() < 1e-12);
+        assert!(Cosine.distance(&[0.0, 0.0], &[1.0, 1.0]).is_err());
+
+        // |[1, 1]| = sqrt(2), not the squared norm 2
+        let similarity = cosine_similarity(&vec![1.0, 0.0], &vec![1.0, 1.0]).unwrap();
+        assert!((similarity - 1.0 / 2f64.sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_mahalanobis() {
+        // identity covariance reduces to euclidean
+        let identity = Matrix::new(vec![vec![1.0, 0.0], vec![0.0, 1.0]]).unwrap();
+        let metric = Mahalanobis::from_covariance(&identity).unwrap();
+        assert!((metric.distance(&[0.0, 0.0], &[3.0, 4.0]).unwrap() - 5.0).abs() < 1e-12);
+
+        // variance 4 along the first axis halves distances in that direction
+        let cov = Matrix::new(vec![vec![4.0, 0.0], vec![0.0, 1.0]]).unwrap();
+        let metric = Mahalanobis::from_covariance(&cov).unwrap();
+        assert!((metric.distance(&[0.0, 0.0], &[2.0, 0.0]).unwrap() - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_pairwise_distances() {
+        let a = Matrix::new(vec![vec![0.0, 0.0], vec![1.0, 1.0]]).unwrap();
+        let b = Matrix::new(vec![vec![0.0, 0.0], vec![0.0, 1.0], vec![3.0, 1.0]]).unwrap();
+
+        let d = pairwise_distances(&a, &b, &Manhattan).unwrap();
+        let target = Matrix::new(vec![vec![0.0, 1.0, 4.0], vec![2.0, 1.0, 2.0]]).unwrap();
+        assert_eq!(d, target);
+
+        // works through a trait object too
+        let metric: Box<dyn Distance> = Box::new(Euclidean);
+        let d = pairwise_distances(&a, &a, metric.as_ref()).unwrap();
+        assert_eq!(d.rows[0][1], 2.0_f64.sqrt());
+    }
+}
@@ -18,6 +18,37 @@ pub struct NotImplementedError;
 #[derive(Debug, Clone, PartialEq)]
 pub struct SingularMatrixError;
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnknownCategoryError;
+
+/// Malformed input, `line` is 1-based.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub line: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct IoError {
+    pub kind: std::io::ErrorKind,
+}
+
+/// Saved with another format version than this build reads.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VersionError {
+    pub found: u32,
+    pub expected: u32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodeError;
+
+/// An array element type other than the one asked for, as a NumPy `descr`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DtypeError {
+    pub found: String,
+    pub expected: String,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum CustomErrors {
     EmptyVector(EmptyVectorError),
@@ -26,10 +57,43 @@ pub enum CustomErrors {
     BadType(BadTypeError),
     NotImplemented(NotImplementedError),
     SingularMatrix(SingularMatrixError),
+    UnknownCategory(UnknownCategoryError),
+    Parse(ParseError),
+    Io(IoError),
+    Version(VersionError),
+    Decode(DecodeError),
+    Dtype(DtypeError),
+}
+
+impl From<std::io::Error> for CustomErrors {
+    fn from(err: std::io::Error) -> Self {
+        CustomErrors::Io(IoError { kind: err.kind() })
+    }
 }
 
 impl fmt::Display for CustomErrors {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Matrix dimensions are mismatched.")
+        match self {
+            CustomErrors::Parse(err) => write!(f, "Malformed input on line {}.", err.line),
+            CustomErrors::Io(err) => write!(f, "I/O error: {}.", err.kind),
+            CustomErrors::Version(err) => write!(
+                f,
+                "Saved with format version {}, expected version {}.",
+                err.found, err.expected
+            ),
+            CustomErrors::Decode(_) => write!(f, "Malformed saved data."),
+            CustomErrors::Dtype(err) => write!(
+                f,
+                "Unsupported dtype '{}', expected '{}'.",
+                err.found, err.expected
+            ),
+            CustomErrors::EmptyVector(_) => write!(f, "Input is empty."),
+            CustomErrors::NonUniform(_) => write!(f, "Rows have different lengths."),
+            CustomErrors::BadType(_) => write!(f, "Invalid argument."),
+            CustomErrors::NotImplemented(_) => write!(f, "Not implemented."),
+            CustomErrors::SingularMatrix(_) => write!(f, "Matrix is singular."),
+            CustomErrors::UnknownCategory(_) => write!(f, "Unknown category."),
+            CustomErrors::Mismatch(_) => write!(f, "Matrix dimensions are mismatched."),
+        }
     }
 }
@@ -0,0 +1,346 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    error::{CustomErrors, MismatchError},
+    matrix::{
+        decomposition::{determinant, inverse},
+        logic::is_square,
+        operations::{
+            add_matrices, multiply_matrices, scalar_divide, scalar_multiply, sub_matrices,
+        },
+        Matrix,
+    },
+    polynomial::Polynomial,
+};
+
+use super::{
+    parser::{Expr, Node},
+    ExprError, Span, Value,
+};
+
+/// Largest matrix power, squaring needs about 60 products for it.
+const MAX_POWER: f64 = 1e18;
+/// Largest `eye(n)`, bigger identities are more likely typos than intent.
+const MAX_EYE: f64 = 1000.0;
+
+pub fn eval(node: &Node, variables: &BTreeMap<String, Value>) -> Result<Value, ExprError> {
+    let span = node.span;
+    match &node.expr {
+        Expr::Number(value) => Ok(Value::Scalar(*value)),
+        Expr::Var(name) => match variables.get(name) {
+            Some(value) => Ok(value.clone()),
+            None => Err(ExprError::new(
+                format!("undefined variable '{}'", name),
+                span,
+            )),
+        },
+        Expr::Str(_) => Err(ExprError::new("strings can only be passed to roots", span)),
+        Expr::Matrix(rows) => concatenate(rows, variables, span),
+        Expr::Neg(operand) => match eval(operand, variables)? {
+            Value::Scalar(x) => Ok(Value::Scalar(-x)),
+            Value::Matrix(x) => Ok(Value::Matrix(map(&x, |v| -v))),
+        },
+        Expr::Transpose(operand) => match eval(operand, variables)? {
+            Value::Scalar(x) => Ok(Value::Scalar(x)),
+            Value::Matrix(x) => Ok(Value::Matrix(x.transpose())),
+        },
+        Expr::Binary(op, left, right) => {
+            let left = eval(left, variables)?;
+            let right = eval(right, variables)?;
+            binary(*op, left, right, span)
+        }
+        Expr::Call(name, args) => call(name, args, variables, span),
+    }
+}
+
+fn map(x: &Matrix<f64>, f: impl Fn(f64) -> f64) -> Matrix<f64> {
+    Matrix {
+        rows: x
+            .rows
+            .iter()
+            .map(|r| r.iter().map(|v| f(*v)).collect())
+            .collect(),
+        m: x.m,
+        n: x.n,
+    }
+}
+
+fn identity(n: usize) -> Matrix<f64> {
+    Matrix {
+        rows: (0..n)
+            .map(|i| (0..n).map(|j| if i == j { 1.0 } else { 0.0 }).collect())
+            .collect(),
+        m: n,
+        n,
+    }
+}
+
+fn binary(op: char, left: Value, right: Value, span: Span) -> Result<Value, ExprError> {
+    let lib = |err: CustomErrors| ExprError::from_custom(err, span);
+    let unsupported = |what: &str| ExprError::new(format!("{} is not supported", what), span);
+
+    match (left, right) {
+        (Value::Scalar(a), Value::Scalar(b)) => Ok(Value::Scalar(match op {
+            '+' => a + b,
+            '-' => a - b,
+            '*' => a * b,
+            '/' => a / b,
+            _ => a.powf(b),
+        })),
+        (Value::Matrix(a), Value::Matrix(b)) => match op {
+            '+' => Ok(Value::Matrix(add_matrices(a, b).map_err(lib)?)),
+            '-' => Ok(Value::Matrix(sub_matrices(a, b).map_err(lib)?)),
+            '*' => Ok(Value::Matrix(multiply_matrices(&a, &b).map_err(lib)?)),
+            '/' => Err(unsupported("dividing by a matrix, use inv")),
+            _ => Err(unsupported("a matrix exponent")),
+        },
+        (Value::Matrix(a), Value::Scalar(b)) => match op {
+            '+' => Ok(Value::Matrix(map(&a, |v| v + b))),
+            '-' => Ok(Value::Matrix(map(&a, |v| v - b))),
+            '*' => Ok(Value::Matrix(scalar_multiply(&a, b).map_err(lib)?)),
+            '/' => Ok(Value::Matrix(scalar_divide(&a, b).map_err(lib)?)),
+            _ => {
+                if b < 0.0 || b.fract() != 0.0 {
+                    return Err(unsupported("a matrix power other than 0, 1, 2, .."));
+                }
+                if b > MAX_POWER {
+                    return Err(unsupported("a matrix power above 1e18"));
+                }
+                if !is_square(&a) {
+                    return Err(lib(CustomErrors::Mismatch(MismatchError)));
+                }
+                // exponentiation by squaring
+                let mut power = identity(a.n);
+                let mut square = a;
+                let mut k = b as u64;
+                while k > 0 {
+                    if k & 1 == 1 {
+                        power = multiply_matrices(&power, &square).map_err(lib)?;
+                    }
+                    k >>= 1;
+                    if k > 0 {
+                        square = multiply_matrices(&square, &square).map_err(lib)?;
+                    }
+                }
+                Ok(Value::Matrix(power))
+            }
+        },
+        (Value::Scalar(a), Value::Matrix(b)) => match op {
+            '+' => Ok(Value::Matrix(map(&b, |v| a + v))),
+            '-' => Ok(Value::Matrix(map(&b, |v| a - v))),
+            '*' => Ok(Value::Matrix(scalar_multiply(&b, a).map_err(lib)?)),
+            '/' => Err(unsupported("dividing by a matrix, use inv")),
+            _ => Err(unsupported("a matrix exponent")),
+        },
+    }
+}
+
+/// Joins the elements of each row side by side, then stacks the rows.
+fn concatenate(
+    rows: &[Vec<Node>],
+    variables: &BTreeMap<String, Value>,
+    span: Span,
+) -> Result<Value, ExprError> {
+    let mut stacked: Vec<Vec<f64>> = vec![];
+    for row in rows {
+        let mut joined: Vec<Vec<f64>> = vec![];
+        for element in row {
+            let block = match eval(element, variables)? {
+                Value::Scalar(x) => vec![vec![x]],
+                Value::Matrix(x) => x.rows,
+            };
+            if joined.is_empty() {
+                joined = block;
+            } else if joined.len() == block.len() {
+                for (left, right) in joined.iter_mut().zip(block) {
+                    left.extend(right);
+                }
+            } else {
+                return Err(ExprError::new(
+                    "rows of the elements do not agree",
+                    element.span,
+                ));
+            }
+        }
+        if let (Some(first), Some(last)) = (stacked.first(), joined.first()) {
+            if first.len() != last.len() {
+                let start = row.first().map_or(span.start, |e| e.span.start);
+                let end = row.last().map_or(span.end, |e| e.span.end);
+                return Err(ExprError::new(
+                    "columns of the rows do not agree",
+                    Span::new(start, end),
+                ));
+            }
+        }
+        stacked.extend(joined);
+    }
+    match Matrix::new(stacked) {
+        Ok(matrix) => Ok(Value::Matrix(matrix)),
+        Err(err) => Err(ExprError::from_custom(err, span)),
+    }
+}
+
+fn matrix_arg(value: Value) -> Matrix<f64> {
+    match value {
+        Value::Scalar(x) => Matrix {
+            rows: vec![vec![x]],
+            m: 1,
+            n: 1,
+        },
+        Value::Matrix(x) => x,
+    }
+}
+
+fn call(
+    name: &str,
+    args: &[Node],
+    variables: &BTreeMap<String, Value>,
+    span: Span,
+) -> Result<Value, ExprError> {
+    let lib = |err: CustomErrors| ExprError::from_custom(err, span);
+    if !["det", "inv", "trace", "eye", "roots"].contains(&name) {
+        return Err(ExprError::new(format!("unknown function '{}'", name), span));
+    }
+    if args.len() != 1 {
+        return Err(ExprError::new(format!("{} takes one argument", name), span));
+    }
+
+    if name == "roots" {
+        let equation = match &args[0].expr {
+            Expr::Str(equation) => equation,
+            _ => {
+                return Err(ExprError::new(
+                    "roots takes a polynomial in quotes, roots(\"x^2 - 4\")",
+                    args[0].span,
+                ))
+            }
+        };
+        let polynomial = match Polynomial::parse(equation) {
+            Ok(polynomial) => polynomial,
+            Err(_) => return Err(ExprError::new("invalid polynomial", args[0].span)),
+        };
+        if !(1..=2).contains(&polynomial.degree()) {
+            return Err(ExprError::new(
+                "roots supports polynomials of order 1 and 2",
+                args[0].span,
+            ));
+        }
+        let c = |power| polynomial.coefficient(power) as f64;
+        if polynomial.degree() == 2 && c(1) * c(1) - 4.0 * c(2) * c(0) < 0.0 {
+            return Err(ExprError::new("no real roots", args[0].span));
+        }
+        let roots = polynomial.get_roots();
+        if roots.iter().any(|r| !r.is_finite()) {
+            return Err(ExprError::new("roots are not finite", args[0].span));
+        }
+        return Ok(Value::Matrix(Matrix {
+            m: roots.len(),
+            n: 1,
+            rows: roots.into_iter().map(|r| vec![r]).collect(),
+        }));
+    }
+
+    let arg = eval(&args[0], variables)?;
+    match name {
+        "eye" => match arg {
+            Value::Scalar(n) if (1.0..=MAX_EYE).contains(&n) && n.fract() == 0.0 => {
+                Ok(Value::Matrix(identity(n as usize)))
+            }
+            _ => Err(ExprError::new(
+                "eye takes an integer from 1 to 1000",
+                args[0].span,
+            )),
+        },
+        "det" => Ok(Value::Scalar(determinant(&matrix_arg(arg)).map_err(lib)?)),
+        "inv" => Ok(Value::Matrix(inverse(&matrix_arg(arg)).map_err(lib)?)),
+        _ => {
+            let x = matrix_arg(arg);
+            if !is_square(&x) {
+                return Err(lib(CustomErrors::Mismatch(MismatchError)));
+            }
+            Ok(Value::Scalar(x.trace()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::eval;
+    use crate::{
+        expr::{parser::parse, parser::Statement, Span, Value},
+        matrix::Matrix,
+    };
+
+    fn run(source: &str) -> Result<Value, crate::expr::ExprError> {
+        let mut variables = BTreeMap::new();
+        variables.insert(
+            "A".to_string(),
+            Value::Matrix(Matrix::new(vec![vec![2.0, 0.0], vec![0.0, 3.0]]).unwrap()),
+        );
+        match parse(source)? {
+            Statement::Expr(node) => eval(&node, &variables),
+            Statement::Assign(..) => panic!("assignment"),
+        }
+    }
+
+    #[test]
+    fn test_eval() {
+        assert_eq!(run("-2^2 + 10 / 4").unwrap(), Value::Scalar(-1.5));
+        assert_eq!(run("trace(A^2 - 1)").unwrap(), Value::Scalar(11.0));
+        assert_eq!(
+            run("trace(A^5) + trace(A^0)").unwrap(),
+            Value::Scalar(277.0)
+        );
+        // squaring keeps huge powers quick
+        assert_eq!(
+            run("trace([0.5 0; 0 1]^1000000000)").unwrap(),
+            Value::Scalar(1.0)
+        );
+        assert_eq!(
+            run("[A [1; 1]; 0 0 eye(1)]").unwrap(),
+            Value::Matrix(
+                Matrix::new(vec![
+                    vec![2.0, 0.0, 1.0],
+                    vec![0.0, 3.0, 1.0],
+                    vec![0.0, 0.0, 1.0]
+                ])
+                .unwrap()
+            )
+        );
+        assert_eq!(
+            run("roots(\"x^2 - 4\")'").unwrap(),
+            Value::Matrix(Matrix::new(vec![vec![2.0, -2.0]]).unwrap())
+        );
+        assert_eq!(
+            run("roots(\"0x^2 + 2x - 1\")").unwrap(),
+            Value::Matrix(Matrix::new(vec![vec![0.5]]).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_eval_errors() {
+        let err = run("A * [1 2 3]'").unwrap_err();
+        assert_eq!(err.message, "Matrix dimensions are mismatched.");
+        assert_eq!(err.span, Span::new(0, 12));
+        assert_eq!(run("[A; 1 2 3]").unwrap_err().span, Span::new(4, 9));
+        assert_eq!(run("roots(\"x^3\")").unwrap_err().span, Span::new(6, 11));
+        assert_eq!(
+            run("roots(\"0x^2 + 3\")").unwrap_err().message,
+            "roots supports polynomials of order 1 and 2"
+        );
+        assert_eq!(run("f(A)").unwrap_err().message, "unknown function 'f'");
+
+        assert_eq!(run("eye(1e9)").unwrap_err().span, Span::new(4, 7));
+        assert_eq!(
+            run("A^1e19").unwrap_err().message,
+            "a matrix power above 1e18 is not supported"
+        );
+        let err = run("roots(\"x^2 + 1\")").unwrap_err();
+        assert_eq!(
+            (err.message.as_str(), err.span),
+            ("no real roots", Span::new(6, 15))
+        );
+    }
+}
@@ -0,0 +1,131 @@
+use super::{ExprError, Span};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TokenKind {
+    Number(f64),
+    Ident(String),
+    Str(String),
+    /// One of `+ - * / ^ ' ( ) [ ] , ; =`.
+    Symbol(char),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub span: Span,
+    /// Whether whitespace comes right before the token, `[1 -2]` has two
+    /// elements but `[1 - 2]` has one.
+    pub spaced: bool,
+}
+
+pub fn tokenize(source: &str) -> Result<Vec<Token>, ExprError> {
+    let chars: Vec<(usize, char)> = source.char_indices().collect();
+    let mut tokens = vec![];
+    let mut i = 0;
+    let mut spaced = false;
+
+    while i < chars.len() {
+        let (start, c) = chars[i];
+        if c.is_whitespace() {
+            spaced = true;
+            i += 1;
+            continue;
+        }
+
+        // end of the token, as an index into `chars`
+        let mut j = i + 1;
+        let kind = if c.is_ascii_digit() || c == '.' {
+            while j < chars.len() && (chars[j].1.is_ascii_digit() || chars[j].1 == '.') {
+                j += 1;
+            }
+            // exponent, only when digits follow so that `2e` stays an error
+            if j < chars.len() && (chars[j].1 == 'e' || chars[j].1 == 'E') {
+                let mut k = j + 1;
+                if k < chars.len() && (chars[k].1 == '+' || chars[k].1 == '-') {
+                    k += 1;
+                }
+                if k < chars.len() && chars[k].1.is_ascii_digit() {
+                    j = k;
+                    while j < chars.len() && chars[j].1.is_ascii_digit() {
+                        j += 1;
+                    }
+                }
+            }
+            let end = offset(source, &chars, j);
+            match source[start..end].parse() {
+                Ok(value) => TokenKind::Number(value),
+                Err(_) => return Err(ExprError::new("invalid number", Span::new(start, end))),
+            }
+        } else if c.is_alphabetic() || c == '_' {
+            while j < chars.len() && (chars[j].1.is_alphanumeric() || chars[j].1 == '_') {
+                j += 1;
+            }
+            TokenKind::Ident(source[start..offset(source, &chars, j)].to_string())
+        } else if c == '"' {
+            while j < chars.len() && chars[j].1 != '"' {
+                j += 1;
+            }
+            if j == chars.len() {
+                return Err(ExprError::new(
+                    "unterminated string",
+                    Span::new(start, source.len()),
+                ));
+            }
+            let text = source[start + 1..chars[j].0].to_string();
+            j += 1;
+            TokenKind::Str(text)
+        } else if "+-*/^'()[],;=".contains(c) {
+            TokenKind::Symbol(c)
+        } else {
+            return Err(ExprError::new(
+                format!("unexpected character '{}'", c),
+                Span::new(start, start + c.len_utf8()),
+            ));
+        };
+
+        tokens.push(Token {
+            kind,
+            span: Span::new(start, offset(source, &chars, j)),
+            spaced,
+        });
+        spaced = false;
+        i = j;
+    }
+    Ok(tokens)
+}
+
+/// Byte offset of the `j`-th character.
+fn offset(source: &str, chars: &[(usize, char)], j: usize) -> usize {
+    chars.get(j).map(|(o, _)| *o).unwrap_or(source.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{tokenize, TokenKind};
+    use crate::expr::Span;
+
+    #[test]
+    fn test_tokenize() {
+        let tokens = tokenize("A' * 2.5e-1 + roots(\"x^2\")").unwrap();
+        let kinds: Vec<TokenKind> = tokens.iter().map(|t| t.kind.clone()).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                TokenKind::Ident("A".to_string()),
+                TokenKind::Symbol('\''),
+                TokenKind::Symbol('*'),
+                TokenKind::Number(0.25),
+                TokenKind::Symbol('+'),
+                TokenKind::Ident("roots".to_string()),
+                TokenKind::Symbol('('),
+                TokenKind::Str("x^2".to_string()),
+                TokenKind::Symbol(')'),
+            ]
+        );
+        assert!(tokens[2].spaced && !tokens[1].spaced);
+
+        let err = tokenize("1 + 1.2.3").unwrap_err();
+        assert_eq!(err.span, Span::new(4, 9));
+        assert!(tokenize("a # b").is_err());
+    }
+}
@@ -0,0 +1,163 @@
+//! A small expression language over matrices, `A = [1 2; 3 4]`,
+//! `inv(A) * b`, `det(A)`, `A'` or `roots("x^2 - 4")`.
+
+mod eval;
+mod lexer;
+mod parser;
+
+use std::{collections::BTreeMap, fmt};
+
+use crate::{error::CustomErrors, matrix::Matrix};
+
+use parser::{parse, Statement};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Scalar(f64),
+    Matrix(Matrix<f64>),
+}
+
+/// Byte range of the source an error points at.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize) -> Span {
+        Span { start, end }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExprError {
+    pub message: String,
+    pub span: Span,
+}
+
+impl ExprError {
+    pub fn new(message: impl Into<String>, span: Span) -> ExprError {
+        ExprError {
+            message: message.into(),
+            span,
+        }
+    }
+
+    fn from_custom(err: CustomErrors, span: Span) -> ExprError {
+        ExprError::new(err.to_string(), span)
+    }
+
+    /// The source followed by a line marking the span with `^`.
+    pub fn render(&self, source: &str) -> String {
+        let start = source
+            .get(..self.span.start)
+            .map_or(0, |s| s.chars().count());
+        let width = source
+            .get(self.span.start..self.span.end)
+            .map_or(0, |s| s.chars().count());
+        format!(
+            "{}\n{}{}",
+            source,
+            " ".repeat(start),
+            "^".repeat(width.max(1))
+        )
+    }
+}
+
+impl fmt::Display for ExprError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+/// Variables and the lines entered so far. The last expression that was not
+/// an assignment is kept in `ans`.
+#[derive(Debug, Clone, Default)]
+pub struct Session {
+    variables: BTreeMap<String, Value>,
+    history: Vec<String>,
+}
+
+impl Session {
+    pub fn new() -> Session {
+        Session::default()
+    }
+
+    /// Evaluates one statement and returns its value.
+    pub fn eval(&mut self, line: &str) -> Result<Value, ExprError> {
+        self.history.push(line.to_string());
+        match parse(line)? {
+            Statement::Assign(name, node) => {
+                let value = eval::eval(&node, &self.variables)?;
+                self.variables.insert(name, value.clone());
+                Ok(value)
+            }
+            Statement::Expr(node) => {
+                let value = eval::eval(&node, &self.variables)?;
+                self.variables.insert("ans".to_string(), value.clone());
+                Ok(value)
+            }
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.variables.get(name)
+    }
+
+    pub fn set(&mut self, name: &str, value: Value) {
+        self.variables.insert(name.to_string(), value);
+    }
+
+    /// Variable names in alphabetical order.
+    pub fn names(&self) -> Vec<&String> {
+        self.variables.keys().collect()
+    }
+
+    pub fn history(&self) -> &[String] {
+        &self.history
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Session, Span, Value};
+    use crate::matrix::Matrix;
+
+    #[test]
+    fn test_session() {
+        let mut session = Session::new();
+        session.eval("A = [1 2; 3 4]").unwrap();
+        session.eval("b = [5; 6]").unwrap();
+        let x = session.eval("inv(A) * b").unwrap();
+        match x {
+            Value::Matrix(x) => {
+                assert!((x.rows[0][0] + 4.0).abs() < 1e-12);
+                assert!((x.rows[1][0] - 4.5).abs() < 1e-12);
+            }
+            other => panic!("not a matrix: {:?}", other),
+        }
+        assert_eq!(session.eval("det(A) + 1").unwrap(), Value::Scalar(-1.0));
+        assert_eq!(session.get("ans"), Some(&Value::Scalar(-1.0)));
+        assert_eq!(
+            session.eval("A'").unwrap(),
+            Value::Matrix(Matrix::new(vec![vec![1.0, 3.0], vec![2.0, 4.0]]).unwrap())
+        );
+        assert_eq!(session.names(), vec!["A", "ans", "b"]);
+        assert_eq!(session.history().len(), 5);
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut session = Session::new();
+        session.eval("A = [1 2; 2 4]").unwrap();
+        let err = session.eval("2 * inv(A)").unwrap_err();
+        assert_eq!(err.message, "Matrix is singular.");
+        assert_eq!(err.span, Span::new(4, 10));
+        assert_eq!(err.render("2 * inv(A)"), "2 * inv(A)\n    ^^^^^^");
+
+        let err = session.eval("A + B").unwrap_err();
+        assert_eq!(err.message, "undefined variable 'B'");
+        assert_eq!(err.render("A + B"), "A + B\n    ^");
+    }
+}
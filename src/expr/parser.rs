@@ -0,0 +1,281 @@
+use super::{
+    lexer::{tokenize, Token, TokenKind},
+    ExprError, Span,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Number(f64),
+    Var(String),
+    Str(String),
+    /// Rows of elements, `[1 2; 3 4]`.
+    Matrix(Vec<Vec<Node>>),
+    Neg(Box<Node>),
+    /// Operator, one of `+ - * / ^`, and its operands.
+    Binary(char, Box<Node>, Box<Node>),
+    Transpose(Box<Node>),
+    Call(String, Vec<Node>),
+}
+
+/// An expression and the part of the source it was parsed from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Node {
+    pub expr: Expr,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Statement {
+    Assign(String, Node),
+    Expr(Node),
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    /// Length of the source, where errors at the end of input point.
+    end: usize,
+    /// Whether spaces separate elements, true directly inside `[ ]`.
+    in_matrix: bool,
+}
+
+pub fn parse(source: &str) -> Result<Statement, ExprError> {
+    let mut parser = Parser {
+        tokens: tokenize(source)?,
+        pos: 0,
+        end: source.len(),
+        in_matrix: false,
+    };
+    let statement = parser.statement()?;
+    match parser.peek() {
+        None => Ok(statement),
+        Some(token) => Err(ExprError::new("unexpected input", token.span)),
+    }
+}
+
+fn node(expr: Expr, start: Span, end: Span) -> Node {
+    Node {
+        expr,
+        span: Span::new(start.start, end.end),
+    }
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn peek_symbol(&self) -> Option<char> {
+        match self.peek() {
+            Some(Token {
+                kind: TokenKind::Symbol(c),
+                ..
+            }) => Some(*c),
+            _ => None,
+        }
+    }
+
+    fn next(&mut self) -> Result<Token, ExprError> {
+        match self.tokens.get(self.pos) {
+            Some(token) => {
+                self.pos += 1;
+                Ok(token.clone())
+            }
+            None => Err(ExprError::new(
+                "unexpected end of input",
+                Span::new(self.end, self.end),
+            )),
+        }
+    }
+
+    fn expect(&mut self, symbol: char) -> Result<Token, ExprError> {
+        let span = match self.peek() {
+            Some(token) if token.kind == TokenKind::Symbol(symbol) => return self.next(),
+            Some(token) => token.span,
+            None => Span::new(self.end, self.end),
+        };
+        Err(ExprError::new(format!("expected '{}'", symbol), span))
+    }
+
+    fn statement(&mut self) -> Result<Statement, ExprError> {
+        if let (Some(TokenKind::Ident(name)), Some(TokenKind::Symbol('='))) = (
+            self.tokens.first().map(|t| &t.kind),
+            self.tokens.get(1).map(|t| &t.kind),
+        ) {
+            let name = name.clone();
+            self.pos = 2;
+            return Ok(Statement::Assign(name, self.expr()?));
+        }
+        Ok(Statement::Expr(self.expr()?))
+    }
+
+    fn expr(&mut self) -> Result<Node, ExprError> {
+        let mut left = self.term()?;
+        while let Some(op @ ('+' | '-')) = self.peek_symbol() {
+            // `[1 -2]`, a sign after a space starts the next element
+            if self.in_matrix {
+                let sign = &self.tokens[self.pos];
+                let operand_spaced = self.tokens.get(self.pos + 1).map(|t| t.spaced);
+                if sign.spaced && operand_spaced == Some(false) {
+                    break;
+                }
+            }
+            self.next()?;
+            let right = self.term()?;
+            let span = Span::new(left.span.start, right.span.end);
+            left = Node {
+                expr: Expr::Binary(op, Box::new(left), Box::new(right)),
+                span,
+            };
+        }
+        Ok(left)
+    }
+
+    fn term(&mut self) -> Result<Node, ExprError> {
+        let mut left = self.unary()?;
+        while let Some(op @ ('*' | '/')) = self.peek_symbol() {
+            self.next()?;
+            let right = self.unary()?;
+            let span = Span::new(left.span.start, right.span.end);
+            left = Node {
+                expr: Expr::Binary(op, Box::new(left), Box::new(right)),
+                span,
+            };
+        }
+        Ok(left)
+    }
+
+    /// Negation binds looser than powers, `-2^2` is `-4`.
+    fn unary(&mut self) -> Result<Node, ExprError> {
+        if self.peek_symbol() == Some('-') {
+            let sign = self.next()?;
+            let operand = self.unary()?;
+            let span = operand.span;
+            return Ok(node(Expr::Neg(Box::new(operand)), sign.span, span));
+        }
+        self.power()
+    }
+
+    fn power(&mut self) -> Result<Node, ExprError> {
+        let base = self.postfix()?;
+        if self.peek_symbol() != Some('^') {
+            return Ok(base);
+        }
+        self.next()?;
+        let exponent = self.unary()?;
+        let span = Span::new(base.span.start, exponent.span.end);
+        Ok(Node {
+            expr: Expr::Binary('^', Box::new(base), Box::new(exponent)),
+            span,
+        })
+    }
+
+    fn postfix(&mut self) -> Result<Node, ExprError> {
+        let mut operand = self.primary()?;
+        while self.peek_symbol() == Some('\'') {
+            let quote = self.next()?;
+            let span = operand.span;
+            operand = node(Expr::Transpose(Box::new(operand)), span, quote.span);
+        }
+        Ok(operand)
+    }
+
+    fn primary(&mut self) -> Result<Node, ExprError> {
+        let token = self.next()?;
+        match token.kind {
+            TokenKind::Number(value) => Ok(node(Expr::Number(value), token.span, token.span)),
+            TokenKind::Str(text) => Ok(node(Expr::Str(text), token.span, token.span)),
+            TokenKind::Ident(name) => {
+                if self.peek_symbol() != Some('(')
+                    || self.peek().is_some_and(|t| t.spaced && self.in_matrix)
+                {
+                    return Ok(node(Expr::Var(name), token.span, token.span));
+                }
+                self.next()?;
+                let in_matrix = std::mem::replace(&mut self.in_matrix, false);
+                let mut args = vec![];
+                if self.peek_symbol() != Some(')') {
+                    args.push(self.expr()?);
+                    while self.peek_symbol() == Some(',') {
+                        self.next()?;
+                        args.push(self.expr()?);
+                    }
+                }
+                let close = self.expect(')')?;
+                self.in_matrix = in_matrix;
+                Ok(node(Expr::Call(name, args), token.span, close.span))
+            }
+            TokenKind::Symbol('(') => {
+                let in_matrix = std::mem::replace(&mut self.in_matrix, false);
+                let inner = self.expr()?;
+                let close = self.expect(')')?;
+                self.in_matrix = in_matrix;
+                Ok(node(inner.expr, token.span, close.span))
+            }
+            TokenKind::Symbol('[') => {
+                let in_matrix = std::mem::replace(&mut self.in_matrix, true);
+                let mut rows = vec![vec![]];
+                loop {
+                    match self.peek_symbol() {
+                        Some(']') => break,
+                        Some(';') => rows.push(vec![]),
+                        Some(',') => {}
+                        _ => {
+                            let element = self.expr()?;
+                            rows.last_mut().unwrap().push(element);
+                            continue;
+                        }
+                    }
+                    self.next()?;
+                }
+                let close = self.expect(']')?;
+                self.in_matrix = in_matrix;
+                // a trailing `;` does not start a row
+                if rows.len() > 1 && rows.last().is_some_and(|r| r.is_empty()) {
+                    rows.pop();
+                }
+                Ok(node(Expr::Matrix(rows), token.span, close.span))
+            }
+            TokenKind::Symbol(c) => Err(ExprError::new(format!("unexpected '{}'", c), token.span)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse, Expr, Statement};
+    use crate::expr::Span;
+
+    fn elements(source: &str) -> Vec<usize> {
+        match parse(source).unwrap() {
+            Statement::Expr(node) => match node.expr {
+                Expr::Matrix(rows) => rows.iter().map(|r| r.len()).collect(),
+                other => panic!("not a matrix: {:?}", other),
+            },
+            _ => panic!("not an expression"),
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(elements("[1 2; 3 4]"), vec![2, 2]);
+        assert_eq!(elements("[1 -2, 3 - 4 (5)]"), vec![4]);
+        assert_eq!(elements("[a' f(1, 2);]"), vec![2]);
+
+        match parse("x = -2^2 * b'").unwrap() {
+            Statement::Assign(name, node) => {
+                assert_eq!(name, "x");
+                assert_eq!(node.span, Span::new(4, 13));
+                assert!(matches!(node.expr, Expr::Binary('*', _, _)));
+            }
+            _ => panic!("not an assignment"),
+        }
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(parse("inv(A").unwrap_err().span, Span::new(5, 5));
+        assert_eq!(parse("1 + * 2").unwrap_err().span, Span::new(4, 5));
+        assert_eq!(parse("(1) 2").unwrap_err().span, Span::new(4, 5));
+    }
+}
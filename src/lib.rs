@@ -1,5 +1,20 @@
 pub mod algebra;
+#[cfg(feature = "cli")]
+pub mod cli;
+pub mod data;
+pub mod distance;
 pub mod error;
+pub mod expr;
 pub mod matrix;
+pub mod metrics;
+pub mod model_selection;
 pub mod models;
+#[cfg(feature = "serde")]
+pub mod persist;
+pub mod polynomial;
+pub mod special;
+pub mod stats;
+#[cfg(test)]
+mod test_util;
+pub mod traits;
 pub mod vector;
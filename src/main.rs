@@ -1,12 +1,14 @@
-pub mod algebra;
-pub mod data;
-pub mod error;
-pub mod matrix;
-pub mod models;
-pub mod polynomial;
-pub mod vector;
+use std::{env, io, process};
+
+use pikus::cli::{run, CliError};
 
 fn main() {
-    let x: f64 = 0.0;
-    println!("{:?}", x.ln())
+    let args: Vec<String> = env::args().skip(1).collect();
+    if let Err(err) = run(&args, &mut io::stdin().lock(), &mut io::stdout().lock()) {
+        eprintln!("pikus: {}", err);
+        process::exit(match err {
+            CliError::Usage(_) => 2,
+            CliError::Failed(_) => 1,
+        });
+    }
 }
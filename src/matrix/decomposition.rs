@@ -0,0 +1,602 @@
+use crate::error::{CustomErrors, EmptyVectorError, MismatchError, SingularMatrixError};
+
+use super::{logic::is_square, Matrix};
+
+/// Cholesky factor `L` of a symmetric positive definite matrix, `A = L * L^T`.
+/// Only the lower triangle of `matrix` is read.
+pub fn cholesky(matrix: &Matrix<f64>) -> Result<Matrix<f64>, CustomErrors> {
+    if !is_square(matrix) {
+        return Err(CustomErrors::Mismatch(MismatchError));
+    }
+
+    let n = matrix.n;
+    let a = &matrix.rows;
+    let mut l: Vec<Vec<f64>> = vec![vec![0.0; n]; n];
+
+    for i in 0..n {
+        for j in 0..=i {
+            let mut sum = a[i][j];
+            for (lik, ljk) in l[i][..j].iter().zip(l[j][..j].iter()) {
+                sum -= lik * ljk;
+            }
+
+            if i == j {
+                // not positive definite (or numerically singular)
+                if sum <= 0.0 || !sum.is_finite() {
+                    return Err(CustomErrors::SingularMatrix(SingularMatrixError));
+                }
+                l[i][j] = sum.sqrt();
+            } else {
+                l[i][j] = sum / l[j][j];
+            }
+        }
+    }
+
+    Ok(Matrix { rows: l, m: n, n })
+}
+
+/// Solves `L * x = b` for lower triangular `L` by forward substitution.
+pub fn solve_lower_triangular(l: &Matrix<f64>, b: &[f64]) -> Result<Vec<f64>, CustomErrors> {
+    if !is_square(l) || l.n != b.len() {
+        return Err(CustomErrors::Mismatch(MismatchError));
+    }
+
+    let n = l.n;
+    let mut x = vec![0.0; n];
+    for i in 0..n {
+        let mut sum = b[i];
+        for (lik, xk) in l.rows[i][..i].iter().zip(x.iter()) {
+            sum -= lik * xk;
+        }
+        if l.rows[i][i] == 0.0 {
+            return Err(CustomErrors::SingularMatrix(SingularMatrixError));
+        }
+        x[i] = sum / l.rows[i][i];
+    }
+
+    Ok(x)
+}
+
+/// Solves `U * x = b` for upper triangular `U` by back substitution.
+pub fn solve_upper_triangular(u: &Matrix<f64>, b: &[f64]) -> Result<Vec<f64>, CustomErrors> {
+    if !is_square(u) || u.n != b.len() {
+        return Err(CustomErrors::Mismatch(MismatchError));
+    }
+
+    let n = u.n;
+    let mut x = vec![0.0; n];
+    for i in (0..n).rev() {
+        let mut sum = b[i];
+        for (uik, xk) in u.rows[i][(i + 1)..].iter().zip(x[(i + 1)..].iter()) {
+            sum -= uik * xk;
+        }
+        if u.rows[i][i] == 0.0 {
+            return Err(CustomErrors::SingularMatrix(SingularMatrixError));
+        }
+        x[i] = sum / u.rows[i][i];
+    }
+
+    Ok(x)
+}
+
+/// Inverse of a symmetric positive definite matrix through its Cholesky factor.
+pub fn cholesky_inverse(matrix: &Matrix<f64>) -> Result<Matrix<f64>, CustomErrors> {
+    if matrix.n == 0 {
+        return Err(CustomErrors::EmptyVector(EmptyVectorError));
+    }
+
+    let l = cholesky(matrix)?;
+    let lt = l.transpose();
+    let n = matrix.n;
+
+    let mut columns: Vec<Vec<f64>> = vec![];
+    for j in 0..n {
+        let mut e = vec![0.0; n];
+        e[j] = 1.0;
+        let y = solve_lower_triangular(&l, &e)?;
+        columns.push(solve_upper_triangular(&lt, &y)?);
+    }
+
+    // columns of the inverse were collected as rows, the inverse is symmetric anyway
+    Ok(Matrix {
+        rows: columns,
+        m: n,
+        n,
+    }
+    .transpose())
+}
+
+/// Thin QR decomposition by Householder reflections. For an `m x n` matrix with
+/// `m >= n` returns `Q` (`m x n`, orthonormal columns) and upper triangular `R` (`n x n`).
+pub fn qr(matrix: &Matrix<f64>) -> Result<(Matrix<f64>, Matrix<f64>), CustomErrors> {
+    let m = matrix.m;
+    let n = matrix.n;
+    if m == 0 || n == 0 {
+        return Err(CustomErrors::EmptyVector(EmptyVectorError));
+    }
+    if m < n {
+        return Err(CustomErrors::Mismatch(MismatchError));
+    }
+
+    let mut r = matrix.rows.clone();
+    let mut reflectors: Vec<Vec<f64>> = vec![];
+
+    for k in 0..n {
+        let norm: f64 = r[k..].iter().map(|row| row[k] * row[k]).sum::<f64>().sqrt();
+        let mut v: Vec<f64> = r[k..].iter().map(|row| row[k]).collect();
+
+        if norm == 0.0 {
+            reflectors.push(vec![0.0; m - k]);
+            continue;
+        }
+
+        let alpha = if v[0] > 0.0 { -norm } else { norm };
+        v[0] -= alpha;
+        let v_norm: f64 = v.iter().map(|x| x * x).sum::<f64>().sqrt();
+        for x in v.iter_mut() {
+            *x /= v_norm;
+        }
+
+        // R <- (I - 2 v v^T) R on the trailing block
+        for j in k..n {
+            let dot: f64 = v
+                .iter()
+                .zip(r[k..].iter())
+                .map(|(vi, row)| vi * row[j])
+                .sum();
+            for (vi, row) in v.iter().zip(r[k..].iter_mut()) {
+                row[j] -= 2.0 * vi * dot;
+            }
+        }
+        reflectors.push(v);
+    }
+
+    // Q = H_0 H_1 ... H_{n-1} applied to the first n columns of the identity
+    let mut q: Vec<Vec<f64>> = vec![vec![0.0; n]; m];
+    for (j, row) in q.iter_mut().enumerate().take(n) {
+        row[j] = 1.0;
+    }
+    for (k, v) in reflectors.iter().enumerate().rev() {
+        for j in 0..n {
+            let dot: f64 = v
+                .iter()
+                .zip(q[k..].iter())
+                .map(|(vi, row)| vi * row[j])
+                .sum();
+            for (vi, row) in v.iter().zip(q[k..].iter_mut()) {
+                row[j] -= 2.0 * vi * dot;
+            }
+        }
+    }
+
+    let mut r_rows: Vec<Vec<f64>> = vec![];
+    for (i, row) in r.iter().enumerate().take(n) {
+        let mut r_row = vec![0.0; n];
+        r_row[i..].copy_from_slice(&row[i..]);
+        r_rows.push(r_row)
+    }
+
+    Ok((
+        Matrix { rows: q, m, n },
+        Matrix {
+            rows: r_rows,
+            m: n,
+            n,
+        },
+    ))
+}
+
+/// Minimizes `||A x - b||` through a QR decomposition of `A`. A rank deficient `A`
+/// yields a `SingularMatrix` error.
+pub fn solve_least_squares(a: &Matrix<f64>, b: &[f64]) -> Result<Vec<f64>, CustomErrors> {
+    if a.m != b.len() {
+        return Err(CustomErrors::Mismatch(MismatchError));
+    }
+
+    let (q, r) = qr(a)?;
+    check_full_rank(&r)?;
+
+    let mut qtb = vec![0.0; a.n];
+    for (row, bi) in q.rows.iter().zip(b.iter()) {
+        for (acc, qij) in qtb.iter_mut().zip(row.iter()) {
+            *acc += qij * bi;
+        }
+    }
+
+    solve_upper_triangular(&r, &qtb)
+}
+
+/// Rejects an `R` factor whose diagonal has (numerically) vanished.
+pub fn check_full_rank(r: &Matrix<f64>) -> Result<(), CustomErrors> {
+    let largest = (0..r.n).map(|i| r.rows[i][i].abs()).fold(0.0, f64::max);
+    let tol = largest * r.n as f64 * f64::EPSILON * 10.0;
+    for i in 0..r.n {
+        if r.rows[i][i].abs() <= tol {
+            return Err(CustomErrors::SingularMatrix(SingularMatrixError));
+        }
+    }
+    Ok(())
+}
+
+/// Inverse of an upper triangular matrix, column by column.
+pub fn upper_triangular_inverse(u: &Matrix<f64>) -> Result<Matrix<f64>, CustomErrors> {
+    let n = u.n;
+    let mut columns = vec![];
+    for j in 0..n {
+        let mut e = vec![0.0; n];
+        e[j] = 1.0;
+        columns.push(solve_upper_triangular(u, &e)?);
+    }
+    Ok(Matrix {
+        rows: columns,
+        m: n,
+        n,
+    }
+    .transpose())
+}
+
+/// `L`, `U` and the row permutation of an LU decomposition.
+pub type Lu = (Matrix<f64>, Matrix<f64>, Vec<usize>);
+
+/// LU decomposition with partial pivoting, `P A = L U`. Returns the unit lower
+/// triangular `L`, `U`, and the row order: row `i` of `P A` is row `perm[i]` of `A`.
+/// A singular matrix still factors, with a zero on the diagonal of `U`.
+pub fn lu(matrix: &Matrix<f64>) -> Result<Lu, CustomErrors> {
+    if !is_square(matrix) {
+        return Err(CustomErrors::Mismatch(MismatchError));
+    }
+    let n = matrix.n;
+    if n == 0 {
+        return Err(CustomErrors::EmptyVector(EmptyVectorError));
+    }
+
+    let mut u = matrix.rows.clone();
+    let mut l = vec![vec![0.0; n]; n];
+    let mut perm: Vec<usize> = (0..n).collect();
+
+    for k in 0..n {
+        let pivot = (k..n)
+            .max_by(|a, b| u[*a][k].abs().total_cmp(&u[*b][k].abs()))
+            .unwrap_or(k);
+        if pivot != k {
+            u.swap(k, pivot);
+            l.swap(k, pivot);
+            perm.swap(k, pivot);
+        }
+        if u[k][k] == 0.0 {
+            continue;
+        }
+        for i in k + 1..n {
+            let factor = u[i][k] / u[k][k];
+            l[i][k] = factor;
+            let (upper, lower) = u.split_at_mut(i);
+            for (x, p) in lower[0][k..].iter_mut().zip(upper[k][k..].iter()) {
+                *x -= factor * p;
+            }
+        }
+    }
+    for (i, row) in l.iter_mut().enumerate() {
+        row[i] = 1.0;
+    }
+
+    Ok((
+        Matrix { rows: l, m: n, n },
+        Matrix { rows: u, m: n, n },
+        perm,
+    ))
+}
+
+/// Determinant through an LU decomposition, `O(n^3)` unlike `get_determinant`.
+pub fn determinant(matrix: &Matrix<f64>) -> Result<f64, CustomErrors> {
+    let (_, u, perm) = lu(matrix)?;
+
+    // every cycle of length c takes c - 1 swaps
+    let mut sign = 1.0;
+    let mut seen = vec![false; perm.len()];
+    for start in 0..perm.len() {
+        let mut i = start;
+        let mut length = 0;
+        while !seen[i] {
+            seen[i] = true;
+            i = perm[i];
+            length += 1;
+        }
+        if length > 1 && length % 2 == 0 {
+            sign = -sign;
+        }
+    }
+    Ok((0..u.n).map(|i| u.rows[i][i]).product::<f64>() * sign)
+}
+
+fn lu_solve(
+    l: &Matrix<f64>,
+    u: &Matrix<f64>,
+    perm: &[usize],
+    b: &[f64],
+) -> Result<Vec<f64>, CustomErrors> {
+    let pb: Vec<f64> = perm.iter().map(|i| b[*i]).collect();
+    let y = solve_lower_triangular(l, &pb)?;
+    solve_upper_triangular(u, &y)
+}
+
+/// Solves the square system `A x = b` by LU decomposition.
+pub fn solve(a: &Matrix<f64>, b: &[f64]) -> Result<Vec<f64>, CustomErrors> {
+    if a.m != b.len() {
+        return Err(CustomErrors::Mismatch(MismatchError));
+    }
+    let (l, u, perm) = lu(a)?;
+    check_full_rank(&u)?;
+    lu_solve(&l, &u, &perm, b)
+}
+
+/// Inverse of a square matrix by LU decomposition.
+pub fn inverse(matrix: &Matrix<f64>) -> Result<Matrix<f64>, CustomErrors> {
+    let (l, u, perm) = lu(matrix)?;
+    check_full_rank(&u)?;
+
+    let n = matrix.n;
+    let mut columns = vec![];
+    for j in 0..n {
+        let mut e = vec![0.0; n];
+        e[j] = 1.0;
+        columns.push(lu_solve(&l, &u, &perm, &e)?);
+    }
+    Ok(Matrix {
+        rows: columns,
+        m: n,
+        n,
+    }
+    .transpose())
+}
+
+/// `U`, the singular values and `V` of a singular value decomposition.
+pub type Svd = (Matrix<f64>, Vec<f64>, Matrix<f64>);
+
+/// Thin singular value decomposition `A = U diag(s) V^T` by one-sided Jacobi
+/// rotations. For `k = min(m, n)`, `U` is `m x k`, `V` is `n x k` and the
+/// singular values are descending. Columns of `U` for zero singular values are zero.
+pub fn svd(matrix: &Matrix<f64>) -> Result<Svd, CustomErrors> {
+    if matrix.m == 0 || matrix.n == 0 {
+        return Err(CustomErrors::EmptyVector(EmptyVectorError));
+    }
+    if matrix.m < matrix.n {
+        let (u, s, v) = svd(&matrix.transpose())?;
+        return Ok((v, s, u));
+    }
+
+    let (m, n) = (matrix.m, matrix.n);
+    // columns of A and V are kept as rows
+    let mut a = matrix.transpose().rows;
+    let mut v: Vec<Vec<f64>> = (0..n)
+        .map(|i| (0..n).map(|j| if i == j { 1.0 } else { 0.0 }).collect())
+        .collect();
+
+    let dot = |x: &[f64], y: &[f64]| -> f64 { x.iter().zip(y.iter()).map(|(a, b)| a * b).sum() };
+    let rotate = |rows: &mut Vec<Vec<f64>>, p: usize, q: usize, c: f64, s: f64| {
+        for k in 0..rows[p].len() {
+            let (x, y) = (rows[p][k], rows[q][k]);
+            rows[p][k] = c * x - s * y;
+            rows[q][k] = s * x + c * y;
+        }
+    };
+
+    for _ in 0..100 {
+        let mut rotated = false;
+        for p in 0..n {
+            for q in p + 1..n {
+                let alpha = dot(&a[p], &a[p]);
+                let beta = dot(&a[q], &a[q]);
+                let gamma = dot(&a[p], &a[q]);
+                if gamma == 0.0 || gamma.abs() <= f64::EPSILON * (alpha * beta).sqrt() {
+                    continue;
+                }
+                rotated = true;
+
+                let zeta = (beta - alpha) / (2.0 * gamma);
+                let t = zeta.signum() / (zeta.abs() + (1.0 + zeta * zeta).sqrt());
+                let c = 1.0 / (1.0 + t * t).sqrt();
+                rotate(&mut a, p, q, c, c * t);
+                rotate(&mut v, p, q, c, c * t);
+            }
+        }
+        if !rotated {
+            break;
+        }
+    }
+
+    let mut order: Vec<(f64, usize)> = a
+        .iter()
+        .enumerate()
+        .map(|(j, col)| (dot(col, col).sqrt(), j))
+        .collect();
+    order.sort_by(|x, y| y.0.total_cmp(&x.0));
+
+    let values: Vec<f64> = order.iter().map(|(s, _)| *s).collect();
+    let u_cols: Vec<Vec<f64>> = order
+        .iter()
+        .map(|(s, j)| {
+            if *s > 0.0 {
+                a[*j].iter().map(|x| x / s).collect()
+            } else {
+                vec![0.0; m]
+            }
+        })
+        .collect();
+    let v_cols: Vec<Vec<f64>> = order.iter().map(|(_, j)| v[*j].clone()).collect();
+
+    Ok((
+        Matrix {
+            rows: u_cols,
+            m: n,
+            n: m,
+        }
+        .transpose(),
+        values,
+        Matrix {
+            rows: v_cols,
+            m: n,
+            n,
+        }
+        .transpose(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        error::{CustomErrors, SingularMatrixError},
+        matrix::{operations::multiply_matrices, Matrix},
+    };
+
+    use super::{
+        cholesky, cholesky_inverse, determinant, inverse, lu, qr, solve, solve_least_squares,
+        solve_lower_triangular, svd,
+    };
+
+    #[test]
+    fn test_cholesky() {
+        let a = Matrix::new(vec![
+            vec![4.0, 12.0, -16.0],
+            vec![12.0, 37.0, -43.0],
+            vec![-16.0, -43.0, 98.0],
+        ])
+        .unwrap();
+        let target = Matrix::new(vec![
+            vec![2.0, 0.0, 0.0],
+            vec![6.0, 1.0, 0.0],
+            vec![-8.0, 5.0, 3.0],
+        ])
+        .unwrap();
+
+        assert_eq!(cholesky(&a).unwrap(), target);
+
+        let not_pd = Matrix::new(vec![vec![1.0, 2.0], vec![2.0, 1.0]]).unwrap();
+        assert_eq!(
+            cholesky(&not_pd).unwrap_err(),
+            CustomErrors::SingularMatrix(SingularMatrixError)
+        );
+    }
+
+    #[test]
+    fn test_solve_lower_triangular() {
+        let l = Matrix::new(vec![vec![2.0, 0.0], vec![1.0, 1.0]]).unwrap();
+        let x = solve_lower_triangular(&l, &[4.0, 5.0]).unwrap();
+        assert_eq!(x, vec![2.0, 3.0]);
+    }
+
+    #[test]
+    fn test_cholesky_inverse() {
+        let a = Matrix::new(vec![vec![4.0, 2.0], vec![2.0, 3.0]]).unwrap();
+        let inv = cholesky_inverse(&a).unwrap();
+        let prod = multiply_matrices(&a, &inv).unwrap();
+
+        for i in 0..2 {
+            for j in 0..2 {
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert!((prod.rows[i][j] - expected).abs() < 1e-12)
+            }
+        }
+    }
+
+    #[test]
+    fn test_qr() {
+        let a = Matrix::new(vec![
+            vec![12.0, -51.0, 4.0],
+            vec![6.0, 167.0, -68.0],
+            vec![-4.0, 24.0, -41.0],
+            vec![1.0, 2.0, 3.0],
+        ])
+        .unwrap();
+        let (q, r) = qr(&a).unwrap();
+
+        assert_eq!((q.m, q.n, r.m, r.n), (4, 3, 3, 3));
+        assert_eq!(r.rows[1][0], 0.0);
+        assert_eq!(r.rows[2][1], 0.0);
+
+        let qr_prod = multiply_matrices(&q, &r).unwrap();
+        let qtq = multiply_matrices(&q.transpose(), &q).unwrap();
+        for i in 0..4 {
+            for j in 0..3 {
+                assert!((qr_prod.rows[i][j] - a.rows[i][j]).abs() < 1e-10);
+            }
+        }
+        for i in 0..3 {
+            for j in 0..3 {
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert!((qtq.rows[i][j] - expected).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_solve_least_squares() {
+        // y = 1 + 2x fitted exactly, then a rank deficient system
+        let a = Matrix::new(vec![vec![1.0, 0.0], vec![1.0, 1.0], vec![1.0, 2.0]]).unwrap();
+        let x = solve_least_squares(&a, &[1.0, 3.0, 5.0]).unwrap();
+        assert!((x[0] - 1.0).abs() < 1e-12);
+        assert!((x[1] - 2.0).abs() < 1e-12);
+
+        let a = Matrix::new(vec![vec![1.0, 2.0], vec![2.0, 4.0], vec![3.0, 6.0]]).unwrap();
+        assert_eq!(
+            solve_least_squares(&a, &[1.0, 2.0, 3.0]).unwrap_err(),
+            CustomErrors::SingularMatrix(SingularMatrixError)
+        );
+    }
+
+    #[test]
+    fn test_lu_solve_inverse() {
+        // the zero pivot forces a row swap
+        let a = Matrix::new(vec![
+            vec![0.0, 2.0, 1.0],
+            vec![3.0, 4.0, 0.0],
+            vec![1.0, 1.0, 5.0],
+        ])
+        .unwrap();
+        let (l, u, perm) = lu(&a).unwrap();
+        let lu_prod = multiply_matrices(&l, &u).unwrap();
+        for (row, p) in lu_prod.rows.iter().zip(perm.iter()) {
+            for (x, y) in row.iter().zip(a.rows[*p].iter()) {
+                assert!((x - y).abs() < 1e-12);
+            }
+        }
+
+        // 0 * (20 - 0) - 2 * (15 - 0) + 1 * (3 - 4)
+        assert!((determinant(&a).unwrap() + 31.0).abs() < 1e-12);
+        let x = solve(&a, &[5.0, 10.0, 18.0]).unwrap();
+        for (xi, expected) in x.iter().zip([2.0, 1.0, 3.0]) {
+            assert!((xi - expected).abs() < 1e-12);
+        }
+
+        let prod = multiply_matrices(&a, &inverse(&a).unwrap()).unwrap();
+        for i in 0..3 {
+            for j in 0..3 {
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert!((prod.rows[i][j] - expected).abs() < 1e-12);
+            }
+        }
+
+        let singular = Matrix::new(vec![vec![1.0, 2.0], vec![2.0, 4.0]]).unwrap();
+        assert_eq!(determinant(&singular).unwrap(), 0.0);
+        assert_eq!(
+            inverse(&singular).unwrap_err(),
+            CustomErrors::SingularMatrix(SingularMatrixError)
+        );
+    }
+
+    #[test]
+    fn test_svd() {
+        let a = Matrix::new(vec![vec![3.0, 2.0, 2.0], vec![2.0, 3.0, -2.0]]).unwrap();
+        let (u, s, v) = svd(&a).unwrap();
+        assert_eq!((u.m, u.n, v.m, v.n), (2, 2, 3, 2));
+        assert!((s[0] - 5.0).abs() < 1e-12 && (s[1] - 3.0).abs() < 1e-12);
+
+        for i in 0..2 {
+            for j in 0..3 {
+                let x: f64 = (0..2).map(|k| u.rows[i][k] * s[k] * v.rows[j][k]).sum();
+                assert!((x - a.rows[i][j]).abs() < 1e-12);
+            }
+        }
+        let vtv = multiply_matrices(&v.transpose(), &v).unwrap();
+        assert!((vtv.rows[0][1]).abs() < 1e-12 && (vtv.rows[1][1] - 1.0).abs() < 1e-12);
+    }
+}
@@ -1,3 +1,5 @@
+use crate::error::{BadTypeError, CustomErrors, MismatchError};
+
 use super::{logic::is_square, Matrix};
 
 pub struct EigenMatrix {
@@ -26,3 +28,119 @@ pub fn get_expanded_eigen_polynomial<
 
     terms
 }
+
+/// Eigenvalues (descending) and eigenvectors (as columns, in the same order) of a
+/// symmetric matrix, computed with cyclic Jacobi rotations.
+pub fn symmetric_eigen(matrix: &Matrix<f64>) -> Result<(Vec<f64>, Matrix<f64>), CustomErrors> {
+    if !is_square(matrix) {
+        return Err(CustomErrors::Mismatch(MismatchError));
+    }
+    let n = matrix.n;
+    let scale = matrix
+        .rows
+        .iter()
+        .flatten()
+        .fold(0.0, |acc: f64, v| acc.max(v.abs()));
+    for i in 0..n {
+        for j in 0..i {
+            if (matrix.rows[i][j] - matrix.rows[j][i]).abs() > 1e-10 * scale.max(1.0) {
+                return Err(CustomErrors::BadType(BadTypeError));
+            }
+        }
+    }
+
+    let mut a = matrix.rows.clone();
+    let mut v: Vec<Vec<f64>> = (0..n)
+        .map(|i| (0..n).map(|j| if i == j { 1.0 } else { 0.0 }).collect())
+        .collect();
+
+    for _ in 0..100 {
+        let off: f64 = (0..n)
+            .flat_map(|i| (0..n).filter(move |j| *j != i).map(move |j| (i, j)))
+            .map(|(i, j)| a[i][j] * a[i][j])
+            .sum();
+        if off.sqrt() <= f64::EPSILON * scale {
+            break;
+        }
+
+        for p in 0..n {
+            for q in (p + 1)..n {
+                if a[p][q] == 0.0 {
+                    continue;
+                }
+
+                // rotation angle that zeroes a[p][q]
+                let theta = (a[q][q] - a[p][p]) / (2.0 * a[p][q]);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                let c = 1.0 / (t * t + 1.0).sqrt();
+                let s = t * c;
+
+                for row in a.iter_mut() {
+                    let (kp, kq) = (row[p], row[q]);
+                    row[p] = c * kp - s * kq;
+                    row[q] = s * kp + c * kq;
+                }
+                let (top, bottom) = a.split_at_mut(q);
+                for (pk, qk) in top[p].iter_mut().zip(bottom[0].iter_mut()) {
+                    let (vp, vq) = (*pk, *qk);
+                    *pk = c * vp - s * vq;
+                    *qk = s * vp + c * vq;
+                }
+                for row in v.iter_mut() {
+                    let (kp, kq) = (row[p], row[q]);
+                    row[p] = c * kp - s * kq;
+                    row[q] = s * kp + c * kq;
+                }
+            }
+        }
+    }
+
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|x, y| a[*y][*y].total_cmp(&a[*x][*x]));
+
+    let values = order.iter().map(|k| a[*k][*k]).collect();
+    let vectors = v
+        .iter()
+        .map(|row| order.iter().map(|k| row[*k]).collect())
+        .collect();
+    Ok((
+        values,
+        Matrix {
+            rows: vectors,
+            m: n,
+            n,
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::matrix::Matrix;
+
+    use super::symmetric_eigen;
+
+    #[test]
+    fn test_symmetric_eigen() {
+        let a = Matrix::new(vec![
+            vec![4.0, 1.0, 2.0],
+            vec![1.0, 3.0, 0.0],
+            vec![2.0, 0.0, 5.0],
+        ])
+        .unwrap();
+        let (values, vectors) = symmetric_eigen(&a).unwrap();
+
+        assert!(values[0] >= values[1] && values[1] >= values[2]);
+        assert!((values.iter().sum::<f64>() - 12.0).abs() < 1e-10);
+
+        // A v = lambda v for every column
+        for (k, lambda) in values.iter().enumerate() {
+            for i in 0..3 {
+                let av: f64 = (0..3).map(|j| a.rows[i][j] * vectors.rows[j][k]).sum();
+                assert!((av - lambda * vectors.rows[i][k]).abs() < 1e-10);
+            }
+        }
+
+        let b = Matrix::new(vec![vec![1.0, 2.0], vec![0.0, 1.0]]).unwrap();
+        assert!(symmetric_eigen(&b).is_err());
+    }
+}
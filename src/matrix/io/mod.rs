@@ -0,0 +1,3 @@
+pub mod mtx;
+pub mod npy;
+pub mod npz;
@@ -0,0 +1,497 @@
+use std::{fs, path::Path};
+
+use crate::{
+    error::{BadTypeError, CustomErrors, ParseError},
+    matrix::{sparse::SparseMatrix, Matrix},
+};
+
+/// Type of the stored values. `Pattern` files hold positions only, read as ones.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MtxField {
+    Real,
+    Integer,
+    Pattern,
+}
+
+/// Symmetric files store the lower triangle, skew-symmetric ones the strict
+/// lower triangle with `a[j][i] = -a[i][j]`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MtxSymmetry {
+    General,
+    Symmetric,
+    SkewSymmetric,
+}
+
+/// Most entries `parse_mtx_dense` allocates, 1 GiB of `f64`.
+const MAX_DENSE: usize = 1 << 27;
+
+struct Parsed {
+    m: usize,
+    n: usize,
+    /// Line of the size header.
+    size_line: usize,
+    coordinate: bool,
+    entries: Vec<(usize, usize, f64)>,
+}
+
+fn parse_error(line: usize) -> CustomErrors {
+    CustomErrors::Parse(ParseError { line })
+}
+
+fn parse_banner(banner: &str) -> Result<(bool, MtxField, MtxSymmetry), CustomErrors> {
+    let words: Vec<String> = banner
+        .split_whitespace()
+        .map(|w| w.to_lowercase())
+        .collect();
+    let words: Vec<&str> = words.iter().map(|w| w.as_str()).collect();
+    match words[..] {
+        ["%%matrixmarket", "matrix", format, field, symmetry] => {
+            let coordinate = match format {
+                "coordinate" => true,
+                "array" => false,
+                _ => return Err(parse_error(1)),
+            };
+            // complex values and hermitian matrices are not supported
+            let field = match field {
+                "real" | "double" => MtxField::Real,
+                "integer" => MtxField::Integer,
+                "pattern" if coordinate => MtxField::Pattern,
+                _ => return Err(parse_error(1)),
+            };
+            let symmetry = match symmetry {
+                "general" => MtxSymmetry::General,
+                "symmetric" => MtxSymmetry::Symmetric,
+                "skew-symmetric" => MtxSymmetry::SkewSymmetric,
+                _ => return Err(parse_error(1)),
+            };
+            Ok((coordinate, field, symmetry))
+        }
+        _ => Err(parse_error(1)),
+    }
+}
+
+fn parse_value(token: &str, field: MtxField, line: usize) -> Result<f64, CustomErrors> {
+    match field {
+        MtxField::Real | MtxField::Pattern => token.parse().map_err(|_| parse_error(line)),
+        MtxField::Integer => match token.parse::<i64>() {
+            Ok(v) => Ok(v as f64),
+            Err(_) => Err(parse_error(line)),
+        },
+    }
+}
+
+fn parse(text: &str) -> Result<Parsed, CustomErrors> {
+    let mut lines = text.lines().enumerate().map(|(k, l)| (k + 1, l));
+    let (coordinate, field, symmetry) = match lines.next() {
+        Some((_, banner)) => parse_banner(banner)?,
+        None => return Err(parse_error(1)),
+    };
+    let mut lines = lines.filter(|(_, l)| {
+        let l = l.trim();
+        !l.is_empty() && !l.starts_with('%')
+    });
+
+    let (line, size) = match lines.next() {
+        Some((line, size)) => (line, size),
+        None => return Err(parse_error(text.lines().count() + 1)),
+    };
+    let size = size
+        .split_whitespace()
+        .map(|t| t.parse::<usize>())
+        .collect::<Result<Vec<usize>, _>>()
+        .map_err(|_| parse_error(line))?;
+    let (m, n, count) = match (coordinate, &size[..]) {
+        (true, [m, n, nnz]) => (*m, *n, Some(*nnz)),
+        (false, [m, n]) => match symmetry {
+            MtxSymmetry::General => (*m, *n, m.checked_mul(*n)),
+            _ if m != n => return Err(parse_error(line)),
+            MtxSymmetry::Symmetric => (
+                *m,
+                *n,
+                n.checked_add(1)
+                    .and_then(|k| k.checked_mul(*n))
+                    .map(|k| k / 2),
+            ),
+            MtxSymmetry::SkewSymmetric => {
+                (*m, *n, n.checked_mul(n.saturating_sub(1)).map(|k| k / 2))
+            }
+        },
+        _ => return Err(parse_error(line)),
+    };
+    // sizes come straight from the file
+    let count = count.ok_or_else(|| parse_error(line))?;
+    let size_line = line;
+    if symmetry != MtxSymmetry::General && m != n {
+        return Err(parse_error(line));
+    }
+
+    // array files list the stored triangle column by column
+    let mut positions = (0..n).flat_map(|j| {
+        let first = match symmetry {
+            MtxSymmetry::General => 0,
+            MtxSymmetry::Symmetric => j,
+            MtxSymmetry::SkewSymmetric => j + 1,
+        };
+        (first..m).map(move |i| (i, j))
+    });
+
+    let mut entries = vec![];
+    let mut read = 0;
+    for (line, entry) in lines {
+        if read == count {
+            return Err(parse_error(line));
+        }
+        let tokens: Vec<&str> = entry.split_whitespace().collect();
+        let (i, j, v) = if coordinate {
+            let width = if field == MtxField::Pattern { 2 } else { 3 };
+            if tokens.len() != width {
+                return Err(parse_error(line));
+            }
+            let index = |t: &str, size: usize| match t.parse::<usize>() {
+                Ok(k) if k >= 1 && k <= size => Ok(k - 1),
+                _ => Err(parse_error(line)),
+            };
+            let v = match field {
+                MtxField::Pattern => 1.0,
+                _ => parse_value(tokens[2], field, line)?,
+            };
+            (index(tokens[0], m)?, index(tokens[1], n)?, v)
+        } else {
+            if tokens.len() != 1 {
+                return Err(parse_error(line));
+            }
+            let (i, j) = positions.next().unwrap_or_default();
+            (i, j, parse_value(tokens[0], field, line)?)
+        };
+
+        match symmetry {
+            MtxSymmetry::General => entries.push((i, j, v)),
+            MtxSymmetry::Symmetric | MtxSymmetry::SkewSymmetric if i < j => {
+                return Err(parse_error(line))
+            }
+            MtxSymmetry::SkewSymmetric if i == j => return Err(parse_error(line)),
+            MtxSymmetry::Symmetric => {
+                entries.push((i, j, v));
+                if i != j {
+                    entries.push((j, i, v));
+                }
+            }
+            MtxSymmetry::SkewSymmetric => {
+                entries.push((i, j, v));
+                entries.push((j, i, -v));
+            }
+        }
+        read += 1;
+    }
+    if read < count {
+        return Err(parse_error(text.lines().count() + 1));
+    }
+
+    Ok(Parsed {
+        m,
+        n,
+        size_line,
+        coordinate,
+        entries,
+    })
+}
+
+/// Parses a MatrixMarket file of either format. Symmetric storage is
+/// expanded, explicit entries of coordinate files are kept even when zero.
+pub fn parse_mtx(text: &str) -> Result<SparseMatrix, CustomErrors> {
+    let mut parsed = parse(text)?;
+    if !parsed.coordinate {
+        parsed.entries.retain(|(_, _, v)| *v != 0.0);
+    }
+    SparseMatrix::from_triplets(parsed.m, parsed.n, &parsed.entries)
+}
+
+/// Dense version of `parse_mtx`, duplicate coordinate entries are summed.
+/// Sizes above `2^27` entries are rejected rather than allocated.
+pub fn parse_mtx_dense(text: &str) -> Result<Matrix<f64>, CustomErrors> {
+    let parsed = parse(text)?;
+    match parsed.m.checked_mul(parsed.n) {
+        Some(size) if size <= MAX_DENSE => {}
+        _ => return Err(parse_error(parsed.size_line)),
+    }
+    let mut rows = vec![vec![0.0; parsed.n]; parsed.m];
+    for (i, j, v) in parsed.entries {
+        rows[i][j] += v;
+    }
+    Ok(Matrix {
+        rows,
+        m: parsed.m,
+        n: parsed.n,
+    })
+}
+
+pub fn read_mtx<P: AsRef<Path>>(path: P) -> Result<SparseMatrix, CustomErrors> {
+    parse_mtx(&fs::read_to_string(path)?)
+}
+
+pub fn read_mtx_dense<P: AsRef<Path>>(path: P) -> Result<Matrix<f64>, CustomErrors> {
+    parse_mtx_dense(&fs::read_to_string(path)?)
+}
+
+fn banner(format: &str, field: MtxField, symmetry: MtxSymmetry) -> String {
+    let field = match field {
+        MtxField::Real => "real",
+        MtxField::Integer => "integer",
+        MtxField::Pattern => "pattern",
+    };
+    let symmetry = match symmetry {
+        MtxSymmetry::General => "general",
+        MtxSymmetry::Symmetric => "symmetric",
+        MtxSymmetry::SkewSymmetric => "skew-symmetric",
+    };
+    format!("%%MatrixMarket matrix {} {} {}\n", format, field, symmetry)
+}
+
+fn format_value(v: f64, field: MtxField) -> Result<String, CustomErrors> {
+    match field {
+        MtxField::Integer if !v.is_finite() || v.fract() != 0.0 => {
+            Err(CustomErrors::BadType(BadTypeError))
+        }
+        MtxField::Integer => Ok(format!("{}", v as i64)),
+        _ => Ok(format!("{:e}", v)),
+    }
+}
+
+/// Whether `(i, j)` is stored for `symmetry`, checking that the mirrored
+/// value `mirror` fits it.
+fn stored(
+    i: usize,
+    j: usize,
+    v: f64,
+    mirror: f64,
+    symmetry: MtxSymmetry,
+) -> Result<bool, CustomErrors> {
+    let fits = match symmetry {
+        MtxSymmetry::General => true,
+        MtxSymmetry::Symmetric => mirror == v,
+        MtxSymmetry::SkewSymmetric => mirror == -v,
+    };
+    if !fits {
+        return Err(CustomErrors::BadType(BadTypeError));
+    }
+    Ok(match symmetry {
+        MtxSymmetry::General => true,
+        MtxSymmetry::Symmetric => i >= j,
+        MtxSymmetry::SkewSymmetric => i > j,
+    })
+}
+
+/// Coordinate format text of `x`. Matrices that do not have the requested
+/// symmetry, or non-integral values in an integer file, are rejected.
+pub fn to_mtx(
+    x: &SparseMatrix,
+    field: MtxField,
+    symmetry: MtxSymmetry,
+) -> Result<String, CustomErrors> {
+    if symmetry != MtxSymmetry::General && x.m != x.n {
+        return Err(CustomErrors::BadType(BadTypeError));
+    }
+
+    let mut entries = vec![];
+    for i in 0..x.m {
+        let (cols, vals) = x.row(i);
+        for (j, v) in cols.iter().zip(vals.iter()) {
+            let mirror = x.get(*j, i).unwrap_or(0.0);
+            if !stored(i, *j, *v, mirror, symmetry)? {
+                continue;
+            }
+            match field {
+                MtxField::Pattern => entries.push(format!("{} {}\n", i + 1, j + 1)),
+                _ => entries.push(format!(
+                    "{} {} {}\n",
+                    i + 1,
+                    j + 1,
+                    format_value(*v, field)?
+                )),
+            }
+        }
+    }
+
+    let mut out = banner("coordinate", field, symmetry);
+    out.push_str(&format!("{} {} {}\n", x.m, x.n, entries.len()));
+    out.push_str(&entries.concat());
+    Ok(out)
+}
+
+/// Array format text of `x`, column by column.
+pub fn to_mtx_dense(
+    x: &Matrix<f64>,
+    field: MtxField,
+    symmetry: MtxSymmetry,
+) -> Result<String, CustomErrors> {
+    if field == MtxField::Pattern || (symmetry != MtxSymmetry::General && x.m != x.n) {
+        return Err(CustomErrors::BadType(BadTypeError));
+    }
+
+    let mut out = banner("array", field, symmetry);
+    out.push_str(&format!("{} {}\n", x.m, x.n));
+    for j in 0..x.n {
+        for i in 0..x.m {
+            let v = x.rows[i][j];
+            let mirror = if symmetry == MtxSymmetry::General {
+                v
+            } else {
+                x.rows[j][i]
+            };
+            if stored(i, j, v, mirror, symmetry)? {
+                out.push_str(&format_value(v, field)?);
+                out.push('\n');
+            }
+        }
+    }
+    Ok(out)
+}
+
+pub fn write_mtx<P: AsRef<Path>>(
+    path: P,
+    x: &SparseMatrix,
+    field: MtxField,
+    symmetry: MtxSymmetry,
+) -> Result<(), CustomErrors> {
+    fs::write(path, to_mtx(x, field, symmetry)?)?;
+    Ok(())
+}
+
+pub fn write_mtx_dense<P: AsRef<Path>>(
+    path: P,
+    x: &Matrix<f64>,
+    field: MtxField,
+    symmetry: MtxSymmetry,
+) -> Result<(), CustomErrors> {
+    fs::write(path, to_mtx_dense(x, field, symmetry)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        error::{CustomErrors, ParseError},
+        matrix::{sparse::SparseMatrix, Matrix},
+        test_util::temp_path,
+    };
+
+    use super::{
+        parse_mtx, parse_mtx_dense, read_mtx, to_mtx, to_mtx_dense, write_mtx, MtxField,
+        MtxSymmetry,
+    };
+
+    #[test]
+    fn test_parse_coordinate() {
+        let text = "%%MatrixMarket matrix coordinate real symmetric\n\
+                    % a comment\n\
+                    3 3 3\n\
+                    1 1 2.5\n\
+                    3 1 -1\n\n\
+                    3 3 4e0\n";
+        let x = parse_mtx_dense(text).unwrap();
+        assert_eq!(
+            x.rows,
+            vec![
+                vec![2.5, 0.0, -1.0],
+                vec![0.0, 0.0, 0.0],
+                vec![-1.0, 0.0, 4.0]
+            ]
+        );
+        assert_eq!(parse_mtx(text).unwrap().nnz(), 4);
+
+        let x = parse_mtx_dense(
+            "%%MatrixMarket matrix coordinate pattern skew-symmetric\n2 2 1\n2 1\n",
+        )
+        .unwrap();
+        assert_eq!(x.rows, vec![vec![0.0, -1.0], vec![1.0, 0.0]]);
+
+        let error = |text: &str| parse_mtx(text).unwrap_err();
+        let header = "%%MatrixMarket matrix coordinate integer general\n2 2 2\n";
+        assert_eq!(
+            error(&format!("{}1 1 1\n3 1 1\n", header)),
+            CustomErrors::Parse(ParseError { line: 4 })
+        );
+        assert_eq!(
+            error(&format!("{}1 1 1.5\n", header)),
+            CustomErrors::Parse(ParseError { line: 3 })
+        );
+        assert_eq!(
+            error(&format!("{}1 1 1\n", header)),
+            CustomErrors::Parse(ParseError { line: 4 })
+        );
+        assert_eq!(
+            error("%%MatrixMarket matrix coordinate complex general\n1 1 1\n1 1 1 0\n"),
+            CustomErrors::Parse(ParseError { line: 1 })
+        );
+    }
+
+    #[test]
+    fn test_parse_array() {
+        // column major
+        let x =
+            parse_mtx_dense("%%MatrixMarket matrix array real general\n2 3\n1\n4\n2\n5\n3\n6\n")
+                .unwrap();
+        assert_eq!(x.rows, vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]);
+
+        let x = parse_mtx("%%MatrixMarket matrix array integer symmetric\n2 2\n1\n0\n3\n").unwrap();
+        assert_eq!((x.nnz(), x.get(1, 1)), (2, Some(3.0)));
+
+        let x = parse_mtx_dense("%%MatrixMarket matrix array real skew-symmetric\n3 3\n1\n2\n3\n")
+            .unwrap();
+        assert_eq!(x.rows[0], vec![0.0, -1.0, -2.0]);
+        assert_eq!(x.rows[2], vec![2.0, 3.0, 0.0]);
+
+        // sizes whose product overflows
+        let header = "%%MatrixMarket matrix array real";
+        for (symmetry, size) in [
+            ("general", "4294967296 4294967296"),
+            ("symmetric", "4294967296 4294967296"),
+            ("symmetric", "18446744073709551615 18446744073709551615"),
+            (
+                "skew-symmetric",
+                "18446744073709551615 18446744073709551615",
+            ),
+        ] {
+            assert_eq!(
+                parse_mtx(&format!("{} {}\n{}\n1\n", header, symmetry, size)).unwrap_err(),
+                CustomErrors::Parse(ParseError { line: 2 })
+            );
+        }
+        // an empty coordinate file too large to hold densely
+        let text = "%%MatrixMarket matrix coordinate real general\n% sizes\n100000 100000 0\n";
+        assert_eq!(parse_mtx(text).unwrap().nnz(), 0);
+        assert_eq!(
+            parse_mtx_dense(text).unwrap_err(),
+            CustomErrors::Parse(ParseError { line: 3 })
+        );
+    }
+
+    #[test]
+    fn test_write_read_roundtrip() {
+        let x = Matrix::new(vec![
+            vec![4.0, 1.0, 0.0],
+            vec![1.0, 3.0, -0.5],
+            vec![0.0, -0.5, 1e-300],
+        ])
+        .unwrap();
+        let sparse = SparseMatrix::from_dense(&x);
+
+        let text = to_mtx(&sparse, MtxField::Real, MtxSymmetry::Symmetric).unwrap();
+        assert!(text.starts_with("%%MatrixMarket matrix coordinate real symmetric\n3 3 5\n"));
+        assert_eq!(parse_mtx(&text).unwrap(), sparse);
+
+        let path = temp_path("mtx_test_roundtrip.mtx");
+        write_mtx(&path, &sparse, MtxField::Pattern, MtxSymmetry::General).unwrap();
+        assert_eq!(read_mtx(&path).unwrap().indices, sparse.indices);
+        std::fs::remove_file(&path).unwrap();
+
+        let text = to_mtx_dense(&x, MtxField::Real, MtxSymmetry::General).unwrap();
+        assert_eq!(parse_mtx_dense(&text).unwrap(), x);
+        let text = to_mtx_dense(&x, MtxField::Real, MtxSymmetry::Symmetric).unwrap();
+        assert_eq!(text.lines().count(), 2 + 6);
+        assert_eq!(parse_mtx_dense(&text).unwrap(), x);
+
+        assert!(to_mtx_dense(&x, MtxField::Real, MtxSymmetry::SkewSymmetric).is_err());
+        assert!(to_mtx(&sparse, MtxField::Integer, MtxSymmetry::General).is_err());
+    }
+}
@@ -0,0 +1,312 @@
+use std::{fs, path::Path};
+
+use crate::{
+    error::{BadTypeError, CustomErrors, DecodeError, DtypeError},
+    matrix::Matrix,
+};
+
+const MAGIC: &[u8; 6] = b"\x93NUMPY";
+
+/// Element types of `.npy` arrays.
+pub trait NpyType: Copy {
+    /// NumPy type code without the byte order, e.g. `f8`.
+    const DTYPE: &'static str;
+
+    fn from_le(bytes: &[u8]) -> Self;
+    fn from_be(bytes: &[u8]) -> Self;
+    fn write_le(self, out: &mut Vec<u8>);
+}
+
+macro_rules! npy_type {
+    ($($t:ty => $dtype:expr),*) => {$(
+        impl NpyType for $t {
+            const DTYPE: &'static str = $dtype;
+
+            fn from_le(bytes: &[u8]) -> Self {
+                <$t>::from_le_bytes(bytes.try_into().unwrap())
+            }
+
+            fn from_be(bytes: &[u8]) -> Self {
+                <$t>::from_be_bytes(bytes.try_into().unwrap())
+            }
+
+            fn write_le(self, out: &mut Vec<u8>) {
+                out.extend_from_slice(&self.to_le_bytes());
+            }
+        }
+    )*};
+}
+
+npy_type!(
+    f64 => "f8", f32 => "f4",
+    i64 => "i8", i32 => "i4", i16 => "i2", i8 => "i1",
+    u64 => "u8", u32 => "u4", u16 => "u2", u8 => "u1"
+);
+
+/// The `descr` written for `T`, single bytes have no byte order.
+fn descr<T: NpyType>() -> String {
+    let order = if std::mem::size_of::<T>() == 1 {
+        '|'
+    } else {
+        '<'
+    };
+    format!("{}{}", order, T::DTYPE)
+}
+
+struct Header {
+    descr: String,
+    fortran_order: bool,
+    shape: Vec<usize>,
+}
+
+/// Text following `'key':` in the header dict.
+fn value<'a>(dict: &'a str, key: &str) -> Result<&'a str, CustomErrors> {
+    let start = match dict.find(&format!("'{}'", key)) {
+        Some(start) => start + key.len() + 2,
+        None => return Err(CustomErrors::Decode(DecodeError)),
+    };
+    match dict[start..].trim_start().strip_prefix(':') {
+        Some(rest) => Ok(rest.trim_start()),
+        None => Err(CustomErrors::Decode(DecodeError)),
+    }
+}
+
+/// Reads the Python dict literal NumPy writes, e.g.
+/// `{'descr': '<f8', 'fortran_order': False, 'shape': (3, 4), }`.
+fn parse_header(dict: &str) -> Result<Header, CustomErrors> {
+    let descr = value(dict, "descr")?;
+    let descr = match descr.strip_prefix('\'').and_then(|d| d.split_once('\'')) {
+        Some((descr, _)) => descr.to_string(),
+        None => return Err(CustomErrors::Decode(DecodeError)),
+    };
+
+    let fortran = value(dict, "fortran_order")?;
+    let fortran_order = if fortran.starts_with("True") {
+        true
+    } else if fortran.starts_with("False") {
+        false
+    } else {
+        return Err(CustomErrors::Decode(DecodeError));
+    };
+
+    let shape = value(dict, "shape")?;
+    let shape = match shape.strip_prefix('(').and_then(|s| s.split_once(')')) {
+        Some((dims, _)) => dims
+            .split(',')
+            .map(|d| d.trim())
+            .filter(|d| !d.is_empty())
+            .map(|d| d.parse::<usize>())
+            .collect::<Result<Vec<usize>, _>>()
+            .map_err(|_| CustomErrors::Decode(DecodeError))?,
+        None => return Err(CustomErrors::Decode(DecodeError)),
+    };
+
+    Ok(Header {
+        descr,
+        fortran_order,
+        shape,
+    })
+}
+
+/// Parses a `.npy` file of any format version. Both byte orders and both C
+/// and Fortran layouts are read, but the element type has to be `T`. A 1-d
+/// array becomes a single column and a scalar a `1 x 1` matrix.
+pub fn from_npy<T: NpyType>(bytes: &[u8]) -> Result<Matrix<T>, CustomErrors> {
+    if bytes.len() < 10 || &bytes[..6] != MAGIC {
+        return Err(CustomErrors::Decode(DecodeError));
+    }
+    let (len, start) = match bytes[6] {
+        1 => (u16::from_le_bytes([bytes[8], bytes[9]]) as usize, 10),
+        2 | 3 if bytes.len() >= 12 => {
+            let len = u32::from_le_bytes([bytes[8], bytes[9], bytes[10], bytes[11]]);
+            (len as usize, 12)
+        }
+        _ => return Err(CustomErrors::Decode(DecodeError)),
+    };
+    let dict = match bytes.get(start..start + len).map(std::str::from_utf8) {
+        Some(Ok(dict)) => dict,
+        _ => return Err(CustomErrors::Decode(DecodeError)),
+    };
+    let header = parse_header(dict)?;
+
+    let (order, dtype) = match header.descr.chars().next() {
+        Some(c @ ('<' | '>' | '|' | '=')) => (c, &header.descr[1..]),
+        _ => ('|', header.descr.as_str()),
+    };
+    if dtype != T::DTYPE {
+        return Err(CustomErrors::Dtype(DtypeError {
+            found: header.descr.clone(),
+            expected: descr::<T>(),
+        }));
+    }
+    let big_endian = order == '>' || (order == '=' && cfg!(target_endian = "big"));
+
+    let (m, n) = match header.shape[..] {
+        [] => (1, 1),
+        [m] => (m, 1),
+        [m, n] => (m, n),
+        _ => return Err(CustomErrors::BadType(BadTypeError)),
+    };
+    let size = std::mem::size_of::<T>();
+    let data = &bytes[start + len..];
+    // the shape comes from the file, a crafted one must not overflow
+    match m.checked_mul(n).and_then(|k| k.checked_mul(size)) {
+        Some(needed) if needed <= data.len() => {}
+        _ => return Err(CustomErrors::Decode(DecodeError)),
+    }
+
+    let read = |k: usize| {
+        let bytes = &data[k * size..(k + 1) * size];
+        if big_endian {
+            T::from_be(bytes)
+        } else {
+            T::from_le(bytes)
+        }
+    };
+    let rows = (0..m)
+        .map(|i| {
+            (0..n)
+                .map(|j| {
+                    read(if header.fortran_order {
+                        j * m + i
+                    } else {
+                        i * n + j
+                    })
+                })
+                .collect()
+        })
+        .collect();
+    Ok(Matrix { rows, m, n })
+}
+
+/// Version 1.0 `.npy` bytes of `x`, little endian in C order.
+pub fn to_npy<T: NpyType>(x: &Matrix<T>) -> Vec<u8> {
+    let mut dict = format!(
+        "{{'descr': '{}', 'fortran_order': False, 'shape': ({}, {}), }}",
+        descr::<T>(),
+        x.m,
+        x.n
+    );
+    // the data starts on a 64 byte boundary, the header ends with a newline
+    while !(MAGIC.len() + 4 + dict.len() + 1).is_multiple_of(64) {
+        dict.push(' ');
+    }
+    dict.push('\n');
+
+    let mut bytes = MAGIC.to_vec();
+    bytes.extend_from_slice(&[1, 0]);
+    bytes.extend_from_slice(&(dict.len() as u16).to_le_bytes());
+    bytes.extend_from_slice(dict.as_bytes());
+    for row in &x.rows {
+        for value in row {
+            value.write_le(&mut bytes);
+        }
+    }
+    bytes
+}
+
+pub fn read_npy<T: NpyType, P: AsRef<Path>>(path: P) -> Result<Matrix<T>, CustomErrors> {
+    from_npy(&fs::read(path)?)
+}
+
+pub fn write_npy<T: NpyType, P: AsRef<Path>>(path: P, x: &Matrix<T>) -> Result<(), CustomErrors> {
+    fs::write(path, to_npy(x))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        error::{CustomErrors, DecodeError, DtypeError},
+        matrix::Matrix,
+        test_util::temp_path,
+    };
+
+    use super::{from_npy, read_npy, to_npy, write_npy};
+
+    fn npy(dict: &str, data: &[u8]) -> Vec<u8> {
+        let mut bytes = b"\x93NUMPY\x01\x00".to_vec();
+        bytes.extend_from_slice(&(dict.len() as u16).to_le_bytes());
+        bytes.extend_from_slice(dict.as_bytes());
+        bytes.extend_from_slice(data);
+        bytes
+    }
+
+    #[test]
+    fn test_npy_roundtrip() {
+        let x = Matrix::new(vec![vec![1.5, -2.0, 3.25], vec![0.0, f64::MAX, 1e-300]]).unwrap();
+        let bytes = to_npy(&x);
+        assert_eq!(bytes.len(), 128 + 6 * 8);
+        assert_eq!(
+            &bytes[10..59],
+            b"{'descr': '<f8', 'fortran_order': False, 'shape':"
+        );
+        assert_eq!(from_npy::<f64>(&bytes).unwrap(), x);
+
+        let x = Matrix {
+            rows: vec![vec![-1i32, 7], vec![i32::MAX, 0]],
+            m: 2,
+            n: 2,
+        };
+        let path = temp_path("npy_test_roundtrip.npy");
+        write_npy(&path, &x).unwrap();
+        assert_eq!(read_npy::<i32, _>(&path).unwrap(), x);
+        std::fs::remove_file(&path).unwrap();
+
+        let x = Matrix::new(vec![vec![255u8, 1]]).unwrap();
+        assert_eq!(from_npy::<u8>(&to_npy(&x)).unwrap(), x);
+    }
+
+    #[test]
+    fn test_npy_layouts() {
+        // [[1, 2, 3], [4, 5, 6]] as big endian float32 in Fortran order
+        let data: Vec<u8> = [1.0f32, 4.0, 2.0, 5.0, 3.0, 6.0]
+            .iter()
+            .flat_map(|v| v.to_be_bytes())
+            .collect();
+        let bytes = npy(
+            "{'descr': '>f4', 'fortran_order': True, 'shape': (2, 3), }\n",
+            &data,
+        );
+        let x = from_npy::<f32>(&bytes).unwrap();
+        assert_eq!(x.rows, vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]);
+
+        let data: Vec<u8> = [3i64, -4].iter().flat_map(|v| v.to_le_bytes()).collect();
+        let bytes = npy(
+            "{'descr': '<i8', 'fortran_order': False, 'shape': (2,), }\n",
+            &data,
+        );
+        assert_eq!(
+            from_npy::<i64>(&bytes).unwrap().rows,
+            vec![vec![3], vec![-4]]
+        );
+
+        assert_eq!(
+            from_npy::<f64>(&bytes).unwrap_err(),
+            CustomErrors::Dtype(DtypeError {
+                found: "<i8".to_string(),
+                expected: "<f8".to_string()
+            })
+        );
+        let bytes = npy(
+            "{'descr': '<c16', 'fortran_order': False, 'shape': (1,), }\n",
+            &[0; 16],
+        );
+        assert!(matches!(
+            from_npy::<f64>(&bytes),
+            Err(CustomErrors::Dtype(_))
+        ));
+
+        // a shape whose byte count overflows, and one larger than the data
+        for shape in ["(4294967296, 4294967296)", "(3,)"] {
+            let header = format!(
+                "{{'descr': '<i8', 'fortran_order': False, 'shape': {}, }}\n",
+                shape
+            );
+            assert_eq!(
+                from_npy::<i64>(&npy(&header, &data)).unwrap_err(),
+                CustomErrors::Decode(DecodeError)
+            );
+        }
+    }
+}
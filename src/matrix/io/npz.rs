@@ -0,0 +1,259 @@
+use std::{fs, path::Path};
+
+use miniz_oxide::{deflate::compress_to_vec, inflate::decompress_to_vec};
+
+use crate::{
+    error::{BadTypeError, CustomErrors, DecodeError},
+    matrix::Matrix,
+};
+
+use super::npy::{from_npy, to_npy, NpyType};
+
+const LOCAL_HEADER: u32 = 0x04034b50;
+const CENTRAL_HEADER: u32 = 0x02014b50;
+const END_OF_DIRECTORY: u32 = 0x06054b50;
+
+const STORED: u16 = 0;
+const DEFLATED: u16 = 8;
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in bytes {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb88320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+fn u16_at(bytes: &[u8], pos: usize) -> Result<u16, CustomErrors> {
+    match bytes.get(pos..pos + 2) {
+        Some(b) => Ok(u16::from_le_bytes([b[0], b[1]])),
+        None => Err(CustomErrors::Decode(DecodeError)),
+    }
+}
+
+fn u32_at(bytes: &[u8], pos: usize) -> Result<u32, CustomErrors> {
+    match bytes.get(pos..pos + 4) {
+        Some(b) => Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]])),
+        None => Err(CustomErrors::Decode(DecodeError)),
+    }
+}
+
+/// Named `.npy` arrays of an archive written by `numpy.savez` or
+/// `numpy.savez_compressed`.
+#[derive(Debug, Clone)]
+pub struct Npz {
+    arrays: Vec<(String, Vec<u8>)>,
+}
+
+impl Npz {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Npz, CustomErrors> {
+        // the end of directory record is followed by a comment of up to 64k
+        let end = (0..bytes.len().saturating_sub(21))
+            .rev()
+            .take(1 << 16)
+            .find(|p| u32_at(bytes, *p).ok() == Some(END_OF_DIRECTORY));
+        let end = match end {
+            Some(end) => end,
+            None => return Err(CustomErrors::Decode(DecodeError)),
+        };
+        let count = u16_at(bytes, end + 10)?;
+        let mut pos = u32_at(bytes, end + 16)? as usize;
+
+        let mut arrays = vec![];
+        for _ in 0..count {
+            if u32_at(bytes, pos)? != CENTRAL_HEADER {
+                return Err(CustomErrors::Decode(DecodeError));
+            }
+            let method = u16_at(bytes, pos + 10)?;
+            let crc = u32_at(bytes, pos + 16)?;
+            let size = u32_at(bytes, pos + 20)? as usize;
+            let name_len = u16_at(bytes, pos + 28)? as usize;
+            let extra_len = u16_at(bytes, pos + 30)? as usize;
+            let comment_len = u16_at(bytes, pos + 32)? as usize;
+            let offset = u32_at(bytes, pos + 42)? as usize;
+            let name = match bytes.get(pos + 46..pos + 46 + name_len) {
+                Some(name) => String::from_utf8_lossy(name).to_string(),
+                None => return Err(CustomErrors::Decode(DecodeError)),
+            };
+            pos += 46 + name_len + extra_len + comment_len;
+
+            if u32_at(bytes, offset)? != LOCAL_HEADER {
+                return Err(CustomErrors::Decode(DecodeError));
+            }
+            let start = offset + 30 + u16_at(bytes, offset + 26)? as usize;
+            let start = start + u16_at(bytes, offset + 28)? as usize;
+            let raw = match bytes.get(start..start + size) {
+                Some(raw) => raw,
+                None => return Err(CustomErrors::Decode(DecodeError)),
+            };
+            let data = match method {
+                STORED => raw.to_vec(),
+                DEFLATED => {
+                    decompress_to_vec(raw).map_err(|_| CustomErrors::Decode(DecodeError))?
+                }
+                _ => return Err(CustomErrors::Decode(DecodeError)),
+            };
+            if crc32(&data) != crc {
+                return Err(CustomErrors::Decode(DecodeError));
+            }
+
+            let name = name.strip_suffix(".npy").unwrap_or(&name).to_string();
+            arrays.push((name, data));
+        }
+        Ok(Npz { arrays })
+    }
+
+    /// Array names in archive order, without the `.npy` suffix.
+    pub fn names(&self) -> Vec<&str> {
+        self.arrays.iter().map(|(name, _)| name.as_str()).collect()
+    }
+
+    pub fn get<T: NpyType>(&self, name: &str) -> Result<Matrix<T>, CustomErrors> {
+        match self.arrays.iter().find(|(n, _)| n == name) {
+            Some((_, data)) => from_npy(data),
+            None => Err(CustomErrors::BadType(BadTypeError)),
+        }
+    }
+}
+
+pub fn read_npz<P: AsRef<Path>>(path: P) -> Result<Npz, CustomErrors> {
+    Npz::from_bytes(&fs::read(path)?)
+}
+
+/// Builds a `.npz` archive, stored like `numpy.savez` unless compressed.
+#[derive(Debug, Clone, Default)]
+pub struct NpzWriter {
+    arrays: Vec<(String, Vec<u8>)>,
+    compress: bool,
+}
+
+impl NpzWriter {
+    pub fn new() -> NpzWriter {
+        NpzWriter {
+            arrays: vec![],
+            compress: false,
+        }
+    }
+
+    /// Deflates every array, like `numpy.savez_compressed`.
+    pub fn with_compression(mut self, compress: bool) -> NpzWriter {
+        self.compress = compress;
+        self
+    }
+
+    pub fn add<T: NpyType>(mut self, name: &str, x: &Matrix<T>) -> NpzWriter {
+        self.arrays.push((format!("{}.npy", name), to_npy(x)));
+        self
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let method = if self.compress { DEFLATED } else { STORED };
+        let mut bytes = vec![];
+        let mut directory = vec![];
+
+        for (name, data) in &self.arrays {
+            let crc = crc32(data);
+            let packed = if self.compress {
+                compress_to_vec(data, 6)
+            } else {
+                data.clone()
+            };
+            let offset = bytes.len() as u32;
+
+            // version, flags, method, time, date (1980-01-01), crc and sizes
+            let mut fields = vec![];
+            for value in [20, 0, method, 0, 0x21] {
+                fields.extend_from_slice(&value.to_le_bytes());
+            }
+            for value in [crc, packed.len() as u32, data.len() as u32] {
+                fields.extend_from_slice(&value.to_le_bytes());
+            }
+            fields.extend_from_slice(&(name.len() as u16).to_le_bytes());
+            fields.extend_from_slice(&0u16.to_le_bytes());
+
+            bytes.extend_from_slice(&LOCAL_HEADER.to_le_bytes());
+            bytes.extend_from_slice(&fields);
+            bytes.extend_from_slice(name.as_bytes());
+            bytes.extend_from_slice(&packed);
+
+            directory.extend_from_slice(&CENTRAL_HEADER.to_le_bytes());
+            directory.extend_from_slice(&20u16.to_le_bytes());
+            directory.extend_from_slice(&fields);
+            // comment length, disk, internal and external attributes
+            directory.extend_from_slice(&[0; 10]);
+            directory.extend_from_slice(&offset.to_le_bytes());
+            directory.extend_from_slice(name.as_bytes());
+        }
+
+        let start = bytes.len() as u32;
+        let count = self.arrays.len() as u16;
+        bytes.extend_from_slice(&directory);
+        bytes.extend_from_slice(&END_OF_DIRECTORY.to_le_bytes());
+        for value in [0, 0, count, count] {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        bytes.extend_from_slice(&(directory.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&start.to_le_bytes());
+        bytes.extend_from_slice(&0u16.to_le_bytes());
+        bytes
+    }
+
+    pub fn write<P: AsRef<Path>>(&self, path: P) -> Result<(), CustomErrors> {
+        fs::write(path, self.to_bytes())?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        error::{CustomErrors, DtypeError},
+        matrix::Matrix,
+        test_util::temp_path,
+    };
+
+    use super::{crc32, read_npz, Npz, NpzWriter};
+
+    #[test]
+    fn test_npz_roundtrip() {
+        assert_eq!(crc32(b"123456789"), 0xcbf43926);
+
+        let x = Matrix::new(vec![vec![0.5, 1.0], vec![-2.0, 3.0], vec![4.0, 0.0]]).unwrap();
+        let y = Matrix::new(vec![vec![0u8], vec![1], vec![1]]).unwrap();
+
+        for compress in [false, true] {
+            let writer = NpzWriter::new()
+                .with_compression(compress)
+                .add("x", &x)
+                .add("y", &y);
+            let npz = Npz::from_bytes(&writer.to_bytes()).unwrap();
+            assert_eq!(npz.names(), vec!["x", "y"]);
+            assert_eq!(npz.get::<f64>("x").unwrap(), x);
+            assert_eq!(npz.get::<u8>("y").unwrap(), y);
+        }
+
+        let path = temp_path("npz_test_roundtrip.npz");
+        NpzWriter::new().add("x", &x).write(&path).unwrap();
+        let npz = read_npz(&path).unwrap();
+        assert!(npz.get::<f64>("y").is_err());
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            npz.get::<f32>("x").unwrap_err(),
+            CustomErrors::Dtype(DtypeError {
+                found: "<f8".to_string(),
+                expected: "<f4".to_string()
+            })
+        );
+
+        let mut bytes = NpzWriter::new().add("x", &x).to_bytes();
+        bytes[200] ^= 1;
+        assert!(Npz::from_bytes(&bytes).is_err());
+    }
+}
@@ -1,14 +1,18 @@
 pub mod adjugate;
 pub mod cofactor;
+pub mod decomposition;
 pub mod eigen;
 pub mod inverse;
+pub mod io;
 pub mod logic;
 pub mod operations;
+pub mod sparse;
 pub mod transform;
 use crate::error::{CustomErrors, EmptyVectorError, NonUniformError};
-use crate::vector::operations::{mean, stddev};
+pub use transform::scaling::standardize;
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Matrix<T: Copy> {
     pub rows: Vec<Vec<T>>,
     pub m: usize,
@@ -88,52 +92,35 @@ impl<T: Copy + From<u8> + Into<f64> + std::ops::AddAssign> Matrix<T> {
     }
 }
 
-pub fn standardize<
-    T: Into<f64>
-        + std::marker::Copy
-        + std::convert::From<u8>
-        + std::ops::Add<Output = T>
-        + std::ops::Sub<f64>
-        + std::fmt::Debug
-        + std::ops::AddAssign,
->(
-    matrix: &Matrix<T>,
-) -> Result<Matrix<f64>, CustomErrors> {
-    let m = matrix.m;
-    let n = matrix.n;
-    let rows = &matrix.rows;
-    let mut new_rows = vec![];
-    for j in 0..n {
-        let mut new_row = vec![];
-        let mut features: Vec<f64> = vec![];
-        for i in 0..m {
-            let v: f64 = rows[i][j].into();
-            features.push(v)
+impl<T: Copy> Matrix<T> {
+    /// New matrix made of the rows at `indices`, in that order. Panics on an
+    /// index out of range.
+    pub fn select_rows(&self, indices: &[usize]) -> Matrix<T> {
+        Matrix {
+            rows: indices.iter().map(|i| self.rows[*i].clone()).collect(),
+            m: indices.len(),
+            n: self.n,
         }
+    }
+}
 
-        let feature_mn: f64 = match mean(&features) {
-            Ok(mn) => mn,
-            Err(_) => return Err(CustomErrors::EmptyVector(EmptyVectorError)),
-        };
-
-        let stdd = match stddev(&features) {
-            Ok(stdd) => stdd,
-            Err(_) => return Err(CustomErrors::EmptyVector(EmptyVectorError)),
-        };
-
-        for k in 0..m {
-            let old_val: f64 = features[k].into();
-            println!("OLD {:?}  MEAN {:?} ", old_val, feature_mn);
-            let mut new_val = old_val - feature_mn;
-            new_val /= stdd;
-            new_row.push(new_val)
+impl Matrix<f64> {
+    /// `true` where the element is NaN.
+    pub fn missing_mask(&self) -> Matrix<bool> {
+        Matrix {
+            rows: self
+                .rows
+                .iter()
+                .map(|row| row.iter().map(|v| v.is_nan()).collect())
+                .collect(),
+            m: self.m,
+            n: self.n,
         }
+    }
 
-        new_rows.push(new_row)
+    pub fn has_missing(&self) -> bool {
+        self.rows.iter().any(|row| row.iter().any(|v| v.is_nan()))
     }
-    let x: Matrix<f64> = Matrix::new(new_rows).unwrap();
-    let xt = x.transpose();
-    Ok(xt)
 }
 
 #[cfg(test)]
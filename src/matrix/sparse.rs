@@ -0,0 +1,180 @@
+use crate::error::{CustomErrors, MismatchError};
+
+use super::Matrix;
+
+/// Compressed sparse row matrix. Row `i` holds the entries
+/// `indices[indptr[i]..indptr[i + 1]]` (column ids, sorted) with their `values`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SparseMatrix {
+    pub m: usize,
+    pub n: usize,
+    pub indptr: Vec<usize>,
+    pub indices: Vec<usize>,
+    pub values: Vec<f64>,
+}
+
+impl SparseMatrix {
+    /// All-zero `m x n` matrix.
+    pub fn zeros(m: usize, n: usize) -> SparseMatrix {
+        SparseMatrix {
+            m,
+            n,
+            indptr: vec![0; m + 1],
+            indices: vec![],
+            values: vec![],
+        }
+    }
+
+    /// Builds the matrix from `(row, column, value)` entries in any order.
+    /// Duplicate positions are summed.
+    pub fn from_triplets(
+        m: usize,
+        n: usize,
+        triplets: &[(usize, usize, f64)],
+    ) -> Result<SparseMatrix, CustomErrors> {
+        if triplets.iter().any(|(i, j, _)| *i >= m || *j >= n) {
+            return Err(CustomErrors::Mismatch(MismatchError));
+        }
+
+        let mut sorted = triplets.to_vec();
+        sorted.sort_by_key(|t| (t.0, t.1));
+
+        let mut indptr = vec![0; m + 1];
+        let mut indices: Vec<usize> = vec![];
+        let mut values: Vec<f64> = vec![];
+        let mut last = None;
+        for (i, j, v) in sorted {
+            if last == Some((i, j)) {
+                if let Some(prev) = values.last_mut() {
+                    *prev += v;
+                }
+                continue;
+            }
+            indptr[i + 1] += 1;
+            indices.push(j);
+            values.push(v);
+            last = Some((i, j));
+        }
+        for i in 0..m {
+            indptr[i + 1] += indptr[i];
+        }
+
+        Ok(SparseMatrix {
+            m,
+            n,
+            indptr,
+            indices,
+            values,
+        })
+    }
+
+    pub fn from_dense(matrix: &Matrix<f64>) -> SparseMatrix {
+        let mut indptr = vec![0];
+        let mut indices = vec![];
+        let mut values = vec![];
+        for row in &matrix.rows {
+            for (j, v) in row.iter().enumerate() {
+                if *v != 0.0 {
+                    indices.push(j);
+                    values.push(*v);
+                }
+            }
+            indptr.push(indices.len());
+        }
+
+        SparseMatrix {
+            m: matrix.m,
+            n: matrix.n,
+            indptr,
+            indices,
+            values,
+        }
+    }
+
+    /// Number of stored entries.
+    pub fn nnz(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn get(&self, i: usize, j: usize) -> Option<f64> {
+        if i >= self.m || j >= self.n {
+            return None;
+        }
+        let (cols, vals) = self.row(i);
+        match cols.binary_search(&j) {
+            Ok(k) => Some(vals[k]),
+            Err(_) => Some(0.0),
+        }
+    }
+
+    /// Column ids and values stored in row `i`.
+    pub fn row(&self, i: usize) -> (&[usize], &[f64]) {
+        let range = self.indptr[i]..self.indptr[i + 1];
+        (&self.indices[range.clone()], &self.values[range])
+    }
+
+    pub fn to_dense(&self) -> Matrix<f64> {
+        let mut rows = vec![vec![0.0; self.n]; self.m];
+        for (i, row) in rows.iter_mut().enumerate() {
+            let (cols, vals) = self.row(i);
+            for (j, v) in cols.iter().zip(vals.iter()) {
+                row[*j] = *v;
+            }
+        }
+        Matrix {
+            rows,
+            m: self.m,
+            n: self.n,
+        }
+    }
+
+    pub fn multiply_vector(&self, vec: &[f64]) -> Result<Vec<f64>, CustomErrors> {
+        if vec.len() != self.n {
+            return Err(CustomErrors::Mismatch(MismatchError));
+        }
+        Ok((0..self.m)
+            .map(|i| {
+                let (cols, vals) = self.row(i);
+                cols.iter().zip(vals.iter()).map(|(j, v)| v * vec[*j]).sum()
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::matrix::Matrix;
+
+    use super::SparseMatrix;
+
+    #[test]
+    fn test_sparse_roundtrip() {
+        let sparse = SparseMatrix::from_triplets(
+            3,
+            3,
+            &[(2, 0, 4.0), (0, 1, 1.0), (0, 1, 2.0), (1, 2, 5.0)],
+        )
+        .unwrap();
+
+        assert_eq!(sparse.nnz(), 3);
+        assert_eq!(sparse.get(0, 1), Some(3.0));
+        assert_eq!(sparse.get(1, 1), Some(0.0));
+        assert_eq!(sparse.get(3, 0), None);
+
+        let dense = Matrix::new(vec![
+            vec![0.0, 3.0, 0.0],
+            vec![0.0, 0.0, 5.0],
+            vec![4.0, 0.0, 0.0],
+        ])
+        .unwrap();
+        assert_eq!(sparse.to_dense(), dense);
+        assert_eq!(SparseMatrix::from_dense(&dense), sparse);
+        assert_eq!(
+            sparse.multiply_vector(&[1.0, 1.0, 2.0]).unwrap(),
+            vec![3.0, 10.0, 4.0]
+        );
+
+        assert!(SparseMatrix::from_triplets(2, 2, &[(2, 0, 1.0)]).is_err());
+    }
+}
@@ -0,0 +1,339 @@
+use crate::{
+    error::{BadTypeError, CustomErrors, EmptyVectorError, MismatchError},
+    matrix::Matrix,
+    models::neighbors::{KBest, Neighbor},
+    traits::{Fit, Transform},
+    vector::operations::{nanmean, quantile},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Strategy {
+    Mean,
+    Median,
+    /// Most common value, ties go to the smallest one.
+    MostFrequent,
+    Constant(f64),
+}
+
+fn present(column: &[f64]) -> Vec<f64> {
+    column.iter().copied().filter(|v| !v.is_nan()).collect()
+}
+
+fn most_frequent(values: &[f64]) -> Result<f64, CustomErrors> {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+
+    let mut best: Option<(f64, usize)> = None;
+    let mut k = 0;
+    while k < sorted.len() {
+        let run = sorted[k..].iter().take_while(|v| **v == sorted[k]).count();
+        if best.is_none_or(|(_, count)| run > count) {
+            best = Some((sorted[k], run));
+        }
+        k += run;
+    }
+
+    match best {
+        Some((value, _)) => Ok(value),
+        None => Err(CustomErrors::EmptyVector(EmptyVectorError)),
+    }
+}
+
+/// Replaces NaNs with a per-column statistic of the training data. Fitting
+/// fails for a column without any observed value, unless the strategy is
+/// `Constant`.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SimpleImputer {
+    strategy: Strategy,
+    statistics: Vec<f64>,
+}
+
+impl SimpleImputer {
+    pub fn new(strategy: Strategy) -> SimpleImputer {
+        SimpleImputer {
+            strategy,
+            statistics: vec![],
+        }
+    }
+
+    /// Fill value of every column.
+    pub fn statistics(&self) -> &Vec<f64> {
+        &self.statistics
+    }
+
+    pub fn fit(&mut self, x: &Matrix<f64>) -> Result<(), CustomErrors> {
+        if x.m == 0 {
+            return Err(CustomErrors::EmptyVector(EmptyVectorError));
+        }
+
+        let mut statistics = vec![];
+        for column in x.transpose().rows {
+            let value = match self.strategy {
+                Strategy::Mean => nanmean(&column)?,
+                Strategy::Median => quantile(&present(&column), 0.5)?,
+                Strategy::MostFrequent => most_frequent(&present(&column))?,
+                Strategy::Constant(value) => value,
+            };
+            statistics.push(value);
+        }
+        self.statistics = statistics;
+        Ok(())
+    }
+
+    pub fn transform(&self, x: &Matrix<f64>) -> Result<Matrix<f64>, CustomErrors> {
+        if self.statistics.is_empty() {
+            return Err(CustomErrors::EmptyVector(EmptyVectorError));
+        }
+        if x.n != self.statistics.len() {
+            return Err(CustomErrors::Mismatch(MismatchError));
+        }
+
+        let rows = x
+            .rows
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .zip(self.statistics.iter())
+                    .map(|(v, fill)| if v.is_nan() { *fill } else { *v })
+                    .collect()
+            })
+            .collect();
+        Ok(Matrix {
+            rows,
+            m: x.m,
+            n: x.n,
+        })
+    }
+}
+
+impl Fit for SimpleImputer {
+    type Input = Matrix<f64>;
+    type Target = ();
+    type Output = ();
+
+    fn fit(&mut self, x: &Matrix<f64>, _y: &()) -> Result<(), CustomErrors> {
+        SimpleImputer::fit(self, x)
+    }
+}
+
+impl Transform for SimpleImputer {
+    type Input = Matrix<f64>;
+    type Output = Matrix<f64>;
+
+    fn transform(&self, x: &Matrix<f64>) -> Result<Matrix<f64>, CustomErrors> {
+        SimpleImputer::transform(self, x)
+    }
+}
+
+/// Euclidean distance over the coordinates present in both rows, scaled up by
+/// `n / n_present`. `None` when the rows share no coordinate.
+pub fn nan_euclidean(a: &[f64], b: &[f64]) -> Option<f64> {
+    let mut sum = 0.0;
+    let mut count = 0;
+    for (x, y) in a.iter().zip(b.iter()) {
+        if !x.is_nan() && !y.is_nan() {
+            sum += (x - y).powi(2);
+            count += 1;
+        }
+    }
+    if count == 0 {
+        return None;
+    }
+    Some((sum * a.len() as f64 / count as f64).sqrt())
+}
+
+/// Fills every missing value with the mean of that feature over the `k`
+/// nearest training rows (by `nan_euclidean`) that have it. Falls back to the
+/// training column mean when no such row exists.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KNNImputer {
+    n_neighbors: usize,
+    data: Vec<Vec<f64>>,
+    column_means: Vec<f64>,
+}
+
+impl KNNImputer {
+    pub fn new(n_neighbors: usize) -> KNNImputer {
+        KNNImputer {
+            n_neighbors,
+            data: vec![],
+            column_means: vec![],
+        }
+    }
+
+    pub fn fit(&mut self, x: &Matrix<f64>) -> Result<(), CustomErrors> {
+        if self.n_neighbors == 0 {
+            return Err(CustomErrors::BadType(BadTypeError));
+        }
+        if x.m == 0 {
+            return Err(CustomErrors::EmptyVector(EmptyVectorError));
+        }
+
+        let mut column_means = vec![];
+        for column in x.transpose().rows {
+            column_means.push(nanmean(&column)?);
+        }
+        self.column_means = column_means;
+        self.data = x.rows.clone();
+        Ok(())
+    }
+
+    pub fn transform(&self, x: &Matrix<f64>) -> Result<Matrix<f64>, CustomErrors> {
+        if self.data.is_empty() {
+            return Err(CustomErrors::EmptyVector(EmptyVectorError));
+        }
+        if x.n != self.column_means.len() {
+            return Err(CustomErrors::Mismatch(MismatchError));
+        }
+
+        let mut rows = vec![];
+        for row in &x.rows {
+            if !row.iter().any(|v| v.is_nan()) {
+                rows.push(row.clone());
+                continue;
+            }
+
+            let distances: Vec<Option<f64>> =
+                self.data.iter().map(|d| nan_euclidean(row, d)).collect();
+
+            let mut filled = row.clone();
+            for (j, value) in filled.iter_mut().enumerate() {
+                if !value.is_nan() {
+                    continue;
+                }
+
+                let mut best = KBest::new(self.n_neighbors);
+                for (index, (d, train)) in distances.iter().zip(self.data.iter()).enumerate() {
+                    if let Some(distance) = d {
+                        if !train[j].is_nan() {
+                            best.push(Neighbor {
+                                index,
+                                distance: *distance,
+                            });
+                        }
+                    }
+                }
+
+                let donors = best.into_sorted_vec();
+                *value = if donors.is_empty() {
+                    self.column_means[j]
+                } else {
+                    donors.iter().map(|n| self.data[n.index][j]).sum::<f64>() / donors.len() as f64
+                };
+            }
+            rows.push(filled);
+        }
+
+        Ok(Matrix {
+            rows,
+            m: x.m,
+            n: x.n,
+        })
+    }
+}
+
+impl Fit for KNNImputer {
+    type Input = Matrix<f64>;
+    type Target = ();
+    type Output = ();
+
+    fn fit(&mut self, x: &Matrix<f64>, _y: &()) -> Result<(), CustomErrors> {
+        KNNImputer::fit(self, x)
+    }
+}
+
+impl Transform for KNNImputer {
+    type Input = Matrix<f64>;
+    type Output = Matrix<f64>;
+
+    fn transform(&self, x: &Matrix<f64>) -> Result<Matrix<f64>, CustomErrors> {
+        KNNImputer::transform(self, x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        matrix::Matrix,
+        vector::operations::{nanmean, nanstd, nansum},
+    };
+
+    use super::{nan_euclidean, KNNImputer, SimpleImputer, Strategy};
+
+    const NAN: f64 = f64::NAN;
+
+    #[test]
+    fn test_nan_reductions() {
+        let v = [1.0, NAN, 3.0];
+        assert_eq!(nansum(&v), 4.0);
+        assert_eq!(nanmean(&v).unwrap(), 2.0);
+        assert_eq!(nanstd(&v).unwrap(), 1.0);
+        assert!(nanmean(&[NAN]).is_err());
+
+        let x = Matrix::new(vec![vec![1.0, NAN], vec![NAN, 2.0]]).unwrap();
+        assert_eq!(
+            x.missing_mask().rows,
+            vec![vec![false, true], vec![true, false]]
+        );
+        assert!(x.has_missing());
+    }
+
+    #[test]
+    fn test_simple_imputer() {
+        let x = Matrix::new(vec![
+            vec![1.0, 5.0],
+            vec![NAN, 5.0],
+            vec![2.0, NAN],
+            vec![6.0, 7.0],
+        ])
+        .unwrap();
+
+        let mut mean = SimpleImputer::new(Strategy::Mean);
+        mean.fit(&x).unwrap();
+        assert_eq!(mean.statistics(), &vec![3.0, 17.0 / 3.0]);
+
+        let mut median = SimpleImputer::new(Strategy::Median);
+        median.fit(&x).unwrap();
+        assert_eq!(median.statistics(), &vec![2.0, 5.0]);
+
+        let mut frequent = SimpleImputer::new(Strategy::MostFrequent);
+        frequent.fit(&x).unwrap();
+        let out = frequent.transform(&x).unwrap();
+        assert_eq!(out.rows[1], vec![1.0, 5.0]);
+        assert_eq!(out.rows[2], vec![2.0, 5.0]);
+
+        let mut constant = SimpleImputer::new(Strategy::Constant(-1.0));
+        constant.fit(&x).unwrap();
+        assert!(!constant.transform(&x).unwrap().has_missing());
+
+        let empty = Matrix::new(vec![vec![NAN], vec![NAN]]).unwrap();
+        assert!(SimpleImputer::new(Strategy::Mean).fit(&empty).is_err());
+    }
+
+    #[test]
+    fn test_knn_imputer() {
+        assert_eq!(
+            nan_euclidean(&[1.0, NAN], &[4.0, 2.0]),
+            Some(18.0_f64.sqrt())
+        );
+        assert_eq!(nan_euclidean(&[NAN, 1.0], &[1.0, NAN]), None);
+
+        let x = Matrix::new(vec![
+            vec![0.0, 0.0, 1.0],
+            vec![0.1, 0.0, 3.0],
+            vec![10.0, 10.0, 100.0],
+            vec![0.0, 0.1, NAN],
+        ])
+        .unwrap();
+
+        let mut imputer = KNNImputer::new(2);
+        imputer.fit(&x).unwrap();
+        let out = imputer.transform(&x).unwrap();
+        // the two close rows are the donors, not the far one
+        assert_eq!(out.rows[3], vec![0.0, 0.1, 2.0]);
+        assert_eq!(out.rows[0], x.rows[0]);
+    }
+}
@@ -1,3 +1,5 @@
 pub mod householder;
+pub mod impute;
+pub mod pca;
 pub mod scaling;
 pub mod tridiagonalize;
@@ -0,0 +1,316 @@
+use crate::{
+    error::{BadTypeError, CustomErrors, EmptyVectorError, MismatchError},
+    matrix::{eigen::symmetric_eigen, Matrix},
+    traits::{Fit, Transform},
+};
+
+/// How many components `PCA` keeps.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NComponents {
+    All,
+    Fixed(usize),
+    /// Smallest number of components explaining at least this share of the variance.
+    Variance(f64),
+}
+
+/// Principal component analysis through an eigen-decomposition of the sample
+/// covariance matrix. Component signs are fixed so the largest loading of every
+/// component is positive.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PCA {
+    n_components: NComponents,
+    whiten: bool,
+    mean: Vec<f64>,
+    components: Matrix<f64>,
+    explained_variance: Vec<f64>,
+    explained_variance_ratio: Vec<f64>,
+}
+
+impl Default for PCA {
+    fn default() -> Self {
+        PCA::new()
+    }
+}
+
+impl PCA {
+    pub fn new() -> PCA {
+        PCA {
+            n_components: NComponents::All,
+            whiten: false,
+            mean: vec![],
+            components: Matrix {
+                rows: vec![],
+                m: 0,
+                n: 0,
+            },
+            explained_variance: vec![],
+            explained_variance_ratio: vec![],
+        }
+    }
+
+    pub fn with_n_components(mut self, n_components: NComponents) -> PCA {
+        self.n_components = n_components;
+        self
+    }
+
+    /// Scale the projections to unit variance.
+    pub fn with_whiten(mut self, whiten: bool) -> PCA {
+        self.whiten = whiten;
+        self
+    }
+
+    /// Kept components as rows, `n_components x n_features`.
+    pub fn components(&self) -> &Matrix<f64> {
+        &self.components
+    }
+
+    pub fn n_components(&self) -> usize {
+        self.components.m
+    }
+
+    /// Variance along every kept component.
+    pub fn explained_variance(&self) -> &Vec<f64> {
+        &self.explained_variance
+    }
+
+    pub fn explained_variance_ratio(&self) -> &Vec<f64> {
+        &self.explained_variance_ratio
+    }
+
+    pub fn mean(&self) -> &Vec<f64> {
+        &self.mean
+    }
+
+    pub fn fit(&mut self, x: &Matrix<f64>) -> Result<(), CustomErrors> {
+        if x.m < 2 || x.n == 0 {
+            return Err(CustomErrors::EmptyVector(EmptyVectorError));
+        }
+
+        let m = x.m as f64;
+        let mut mean = vec![0.0; x.n];
+        for row in &x.rows {
+            for (mn, v) in mean.iter_mut().zip(row.iter()) {
+                *mn += v / m;
+            }
+        }
+
+        let mut cov = vec![vec![0.0; x.n]; x.n];
+        for row in &x.rows {
+            let centered: Vec<f64> = row.iter().zip(mean.iter()).map(|(v, mn)| v - mn).collect();
+            for (cov_row, ci) in cov.iter_mut().zip(centered.iter()) {
+                for (c, cj) in cov_row.iter_mut().zip(centered.iter()) {
+                    *c += ci * cj / (m - 1.0);
+                }
+            }
+        }
+
+        let (values, vectors) = symmetric_eigen(&Matrix {
+            rows: cov,
+            m: x.n,
+            n: x.n,
+        })?;
+        // round-off can leave tiny negative eigenvalues
+        let values: Vec<f64> = values.into_iter().map(|v| v.max(0.0)).collect();
+        let total: f64 = values.iter().sum();
+        let ratio: Vec<f64> = values
+            .iter()
+            .map(|v| if total > 0.0 { v / total } else { 0.0 })
+            .collect();
+
+        let k = match self.n_components {
+            NComponents::All => x.n,
+            NComponents::Fixed(k) => {
+                if k == 0 || k > x.n {
+                    return Err(CustomErrors::BadType(BadTypeError));
+                }
+                k
+            }
+            NComponents::Variance(threshold) => {
+                if threshold.is_nan() || threshold <= 0.0 || threshold > 1.0 {
+                    return Err(CustomErrors::BadType(BadTypeError));
+                }
+                let mut cumulative = 0.0;
+                let mut k = x.n;
+                for (i, r) in ratio.iter().enumerate() {
+                    cumulative += r;
+                    // tolerance so a threshold of 1.0 is reachable
+                    if cumulative >= threshold - 1e-12 {
+                        k = i + 1;
+                        break;
+                    }
+                }
+                k
+            }
+        };
+
+        let vectors = vectors.transpose();
+        let mut components = vec![];
+        for component in vectors.rows.into_iter().take(k) {
+            let largest = component.iter().fold(
+                0.0,
+                |acc: f64, v| if v.abs() > acc.abs() { *v } else { acc },
+            );
+            let sign = if largest < 0.0 { -1.0 } else { 1.0 };
+            components.push(component.iter().map(|v| v * sign).collect());
+        }
+
+        self.mean = mean;
+        self.components = Matrix {
+            rows: components,
+            m: k,
+            n: x.n,
+        };
+        self.explained_variance = values[..k].to_vec();
+        self.explained_variance_ratio = ratio[..k].to_vec();
+        Ok(())
+    }
+
+    /// Projects `x` onto the components.
+    pub fn transform(&self, x: &Matrix<f64>) -> Result<Matrix<f64>, CustomErrors> {
+        if self.mean.is_empty() {
+            return Err(CustomErrors::EmptyVector(EmptyVectorError));
+        }
+        if x.n != self.mean.len() {
+            return Err(CustomErrors::Mismatch(MismatchError));
+        }
+
+        let mut rows = vec![];
+        for row in &x.rows {
+            let centered: Vec<f64> = row
+                .iter()
+                .zip(self.mean.iter())
+                .map(|(v, mn)| v - mn)
+                .collect();
+            let projected = self
+                .components
+                .rows
+                .iter()
+                .zip(self.explained_variance.iter())
+                .map(|(c, var)| {
+                    let z: f64 = c.iter().zip(centered.iter()).map(|(a, b)| a * b).sum();
+                    if self.whiten && *var > 0.0 {
+                        z / var.sqrt()
+                    } else {
+                        z
+                    }
+                })
+                .collect();
+            rows.push(projected);
+        }
+
+        Ok(Matrix {
+            rows,
+            m: x.m,
+            n: self.components.m,
+        })
+    }
+
+    /// Maps projections back to feature space. Exact when every component is kept.
+    pub fn inverse_transform(&self, z: &Matrix<f64>) -> Result<Matrix<f64>, CustomErrors> {
+        if self.mean.is_empty() {
+            return Err(CustomErrors::EmptyVector(EmptyVectorError));
+        }
+        if z.n != self.components.m {
+            return Err(CustomErrors::Mismatch(MismatchError));
+        }
+
+        let mut rows = vec![];
+        for row in &z.rows {
+            let mut restored = self.mean.clone();
+            for ((zi, c), var) in row
+                .iter()
+                .zip(self.components.rows.iter())
+                .zip(self.explained_variance.iter())
+            {
+                let zi = if self.whiten { zi * var.sqrt() } else { *zi };
+                for (r, cj) in restored.iter_mut().zip(c.iter()) {
+                    *r += zi * cj;
+                }
+            }
+            rows.push(restored);
+        }
+
+        Ok(Matrix {
+            rows,
+            m: z.m,
+            n: self.mean.len(),
+        })
+    }
+}
+
+impl Fit for PCA {
+    type Input = Matrix<f64>;
+    type Target = ();
+    type Output = ();
+
+    fn fit(&mut self, x: &Matrix<f64>, _y: &()) -> Result<(), CustomErrors> {
+        PCA::fit(self, x)
+    }
+}
+
+impl Transform for PCA {
+    type Input = Matrix<f64>;
+    type Output = Matrix<f64>;
+
+    fn transform(&self, x: &Matrix<f64>) -> Result<Matrix<f64>, CustomErrors> {
+        PCA::transform(self, x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{data::get_iris_data, matrix::Matrix};
+
+    use super::{NComponents, PCA};
+
+    #[test]
+    fn test_pca_iris() {
+        let (features, _) = get_iris_data();
+        let x = Matrix::new(features).unwrap();
+
+        let mut pca = PCA::new().with_n_components(NComponents::Variance(0.95));
+        pca.fit(&x).unwrap();
+
+        // the UCI copy of iris, scikit-learn's corrected rows 35 and 38 give 0.92461872
+        assert_eq!(pca.n_components(), 2);
+        let ratio = pca.explained_variance_ratio();
+        assert!((ratio[0] - 0.9246162).abs() < 1e-6);
+        assert!((ratio[1] - 0.0530156).abs() < 1e-6);
+
+        let z = pca.transform(&x).unwrap();
+        assert_eq!((z.m, z.n), (150, 2));
+    }
+
+    #[test]
+    fn test_pca_inverse_and_whiten() {
+        let x = Matrix::new(vec![
+            vec![2.0, 0.0, 1.0],
+            vec![0.0, 1.0, 3.0],
+            vec![1.0, 4.0, 0.0],
+            vec![3.0, 2.0, 2.0],
+        ])
+        .unwrap();
+
+        let mut pca = PCA::new().with_whiten(true);
+        pca.fit(&x).unwrap();
+        let z = pca.transform(&x).unwrap();
+
+        // whitened projections have unit sample variance
+        for k in 0..2 {
+            let var: f64 = z.rows.iter().map(|r| r[k] * r[k]).sum::<f64>() / 3.0;
+            assert!((var - 1.0).abs() < 1e-10);
+        }
+
+        let back = pca.inverse_transform(&z).unwrap();
+        for (a, b) in back.rows.iter().flatten().zip(x.rows.iter().flatten()) {
+            assert!((a - b).abs() < 1e-10);
+        }
+
+        assert!(PCA::new()
+            .with_n_components(NComponents::Fixed(4))
+            .fit(&x)
+            .is_err());
+    }
+}
@@ -0,0 +1,143 @@
+use crate::{
+    error::{BadTypeError, CustomErrors, EmptyVectorError},
+    matrix::Matrix,
+    traits::{Fit, Transform},
+};
+
+use super::{safe_scale, scale_columns, unscale_columns};
+
+/// Maps the training range of every column onto `feature_range`, (0, 1) by default.
+/// Constant columns go to the lower end of the range.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MinMaxScaler {
+    feature_range: (f64, f64),
+    data_min: Vec<f64>,
+    data_max: Vec<f64>,
+    offset: Vec<f64>,
+    scale: Vec<f64>,
+}
+
+impl Default for MinMaxScaler {
+    fn default() -> Self {
+        MinMaxScaler::new()
+    }
+}
+
+impl MinMaxScaler {
+    pub fn new() -> MinMaxScaler {
+        MinMaxScaler {
+            feature_range: (0.0, 1.0),
+            data_min: vec![],
+            data_max: vec![],
+            offset: vec![],
+            scale: vec![],
+        }
+    }
+
+    pub fn with_range(mut self, min: f64, max: f64) -> MinMaxScaler {
+        self.feature_range = (min, max);
+        self
+    }
+
+    pub fn data_min(&self) -> &Vec<f64> {
+        &self.data_min
+    }
+
+    pub fn data_max(&self) -> &Vec<f64> {
+        &self.data_max
+    }
+
+    pub fn fit(&mut self, x: &Matrix<f64>) -> Result<(), CustomErrors> {
+        let (lo, hi) = self.feature_range;
+        if lo.is_nan() || hi.is_nan() || lo >= hi {
+            return Err(CustomErrors::BadType(BadTypeError));
+        }
+        if x.m == 0 {
+            return Err(CustomErrors::EmptyVector(EmptyVectorError));
+        }
+
+        let mut data_min = vec![f64::INFINITY; x.n];
+        let mut data_max = vec![f64::NEG_INFINITY; x.n];
+        for row in &x.rows {
+            for ((mn, mx), v) in data_min.iter_mut().zip(data_max.iter_mut()).zip(row.iter()) {
+                *mn = mn.min(*v);
+                *mx = mx.max(*v);
+            }
+        }
+
+        // v' = (v - data_min) * (hi - lo) / range + lo = (v - offset) / scale
+        self.scale = data_min
+            .iter()
+            .zip(data_max.iter())
+            .map(|(mn, mx)| safe_scale(mx - mn) / (hi - lo))
+            .collect();
+        self.offset = data_min
+            .iter()
+            .zip(self.scale.iter())
+            .map(|(mn, s)| mn - lo * s)
+            .collect();
+        self.data_min = data_min;
+        self.data_max = data_max;
+        Ok(())
+    }
+
+    pub fn transform(&self, x: &Matrix<f64>) -> Result<Matrix<f64>, CustomErrors> {
+        scale_columns(x, &self.offset, &self.scale)
+    }
+
+    pub fn inverse_transform(&self, x: &Matrix<f64>) -> Result<Matrix<f64>, CustomErrors> {
+        unscale_columns(x, &self.offset, &self.scale)
+    }
+}
+
+impl Fit for MinMaxScaler {
+    type Input = Matrix<f64>;
+    type Target = ();
+    type Output = ();
+
+    fn fit(&mut self, x: &Matrix<f64>, _y: &()) -> Result<(), CustomErrors> {
+        MinMaxScaler::fit(self, x)
+    }
+}
+
+impl Transform for MinMaxScaler {
+    type Input = Matrix<f64>;
+    type Output = Matrix<f64>;
+
+    fn transform(&self, x: &Matrix<f64>) -> Result<Matrix<f64>, CustomErrors> {
+        MinMaxScaler::transform(self, x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::matrix::Matrix;
+
+    use super::MinMaxScaler;
+
+    #[test]
+    fn test_minmax_scaler() {
+        let train = Matrix::new(vec![vec![2.0, 3.0], vec![4.0, 3.0], vec![6.0, 3.0]]).unwrap();
+
+        let mut scaler = MinMaxScaler::new();
+        scaler.fit(&train).unwrap();
+        let out = scaler.transform(&train).unwrap();
+        assert_eq!(
+            out.rows,
+            vec![vec![0.0, 0.0], vec![0.5, 0.0], vec![1.0, 0.0]]
+        );
+        assert_eq!(scaler.inverse_transform(&out).unwrap(), train);
+
+        // values outside the training range are not clipped
+        let mut scaler = MinMaxScaler::new().with_range(-1.0, 1.0);
+        scaler.fit(&train).unwrap();
+        let test = Matrix::new(vec![vec![8.0, 3.0]]).unwrap();
+        assert_eq!(scaler.transform(&test).unwrap().rows, vec![vec![2.0, -1.0]]);
+
+        assert!(MinMaxScaler::new()
+            .with_range(1.0, 0.0)
+            .fit(&train)
+            .is_err());
+    }
+}
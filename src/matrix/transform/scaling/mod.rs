@@ -1,54 +1,107 @@
+pub mod minmax;
+pub mod normalizer;
+pub mod robust;
+pub mod standard;
+
+pub use minmax::MinMaxScaler;
+pub use normalizer::{Norm, Normalizer};
+pub use robust::RobustScaler;
+pub use standard::StandardScaler;
+
+use crate::error::MismatchError;
 use crate::matrix::CustomErrors;
 use crate::matrix::EmptyVectorError;
 use crate::matrix::Matrix;
-use crate::vector::operations::{mean, stddev};
-
-pub fn standardize<
-    T: Into<f64>
-        + std::marker::Copy
-        + std::convert::From<u8>
-        + std::ops::Add<Output = T>
-        + std::ops::Sub<f64>
-        + std::fmt::Debug
-        + std::ops::AddAssign,
->(
-    matrix: &Matrix<T>,
+use crate::vector::operations::{mean, variance};
+
+/// Standardizes every column with its own mean and standard deviation. Use a
+/// `StandardScaler` to apply training statistics to other data.
+pub fn standardize<T: Copy + Into<f64>>(matrix: &Matrix<T>) -> Result<Matrix<f64>, CustomErrors> {
+    let rows: Vec<Vec<f64>> = matrix
+        .rows
+        .iter()
+        .map(|row| row.iter().map(|v| (*v).into()).collect())
+        .collect();
+    let x = Matrix {
+        rows,
+        m: matrix.m,
+        n: matrix.n,
+    };
+
+    let mut scaler = StandardScaler::new();
+    scaler.fit(&x)?;
+    scaler.transform(&x)
+}
+
+/// Per-column mean and population standard deviation of `matrix`.
+pub(crate) fn column_mean_std(matrix: &Matrix<f64>) -> Result<(Vec<f64>, Vec<f64>), CustomErrors> {
+    if matrix.m == 0 {
+        return Err(CustomErrors::EmptyVector(EmptyVectorError));
+    }
+
+    let mut means = vec![];
+    let mut stds = vec![];
+    for j in 0..matrix.n {
+        let column: Vec<f64> = matrix.rows.iter().map(|row| row[j]).collect();
+        means.push(mean(&column)?);
+        stds.push(variance(&column, 0)?.sqrt());
+    }
+    Ok((means, stds))
+}
+
+/// Replaces a zero scale so constant columns pass through unscaled.
+fn safe_scale(scale: f64) -> f64 {
+    if scale == 0.0 {
+        1.0
+    } else {
+        scale
+    }
+}
+
+/// Maps every element to `(v - offset[j]) / scale[j]`, checking the matrix
+/// against the fitted parameters.
+fn scale_columns(
+    x: &Matrix<f64>,
+    offset: &[f64],
+    scale: &[f64],
 ) -> Result<Matrix<f64>, CustomErrors> {
-    let m = matrix.m;
-    let n = matrix.n;
-    let rows = &matrix.rows;
-    let mut new_rows = vec![];
-    for j in 0..n {
-        let mut new_row = vec![];
-        let mut features: Vec<f64> = vec![];
-        for i in 0..m {
-            let v: f64 = rows[i][j].into();
-            features.push(v)
-        }
-
-        let feature_mn: f64 = match mean(&features) {
-            Ok(mn) => mn,
-            Err(_) => return Err(CustomErrors::EmptyVector(EmptyVectorError)),
-        };
-
-        let stdd = match stddev(&features) {
-            Ok(stdd) => stdd,
-            Err(_) => return Err(CustomErrors::EmptyVector(EmptyVectorError)),
-        };
-
-        for k in 0..m {
-            let old_val: f64 = features[k].into();
-            println!("OLD {:?}  MEAN {:?} ", old_val, feature_mn);
-            let mut new_val = old_val - feature_mn;
-            new_val /= stdd;
-            new_row.push(new_val)
-        }
-
-        new_rows.push(new_row)
+    if offset.is_empty() {
+        return Err(CustomErrors::EmptyVector(EmptyVectorError));
+    }
+    if x.n != offset.len() {
+        return Err(CustomErrors::Mismatch(MismatchError));
     }
-    let x: Matrix<f64> = Matrix::new(new_rows).unwrap();
-    let xt = x.transpose();
-    Ok(xt)
+
+    let rows = x
+        .rows
+        .iter()
+        .map(|row| {
+            row.iter()
+                .zip(offset.iter().zip(scale.iter()))
+                .map(|(v, (o, s))| (v - o) / s)
+                .collect()
+        })
+        .collect();
+    Ok(Matrix {
+        rows,
+        m: x.m,
+        n: x.n,
+    })
+}
+
+/// Inverse of `scale_columns`, `v * scale[j] + offset[j]`.
+fn unscale_columns(
+    x: &Matrix<f64>,
+    offset: &[f64],
+    scale: &[f64],
+) -> Result<Matrix<f64>, CustomErrors> {
+    let inverse_scale: Vec<f64> = scale.iter().map(|s| 1.0 / s).collect();
+    let inverse_offset: Vec<f64> = offset
+        .iter()
+        .zip(scale.iter())
+        .map(|(o, s)| -o / s)
+        .collect();
+    scale_columns(x, &inverse_offset, &inverse_scale)
 }
 
 #[cfg(test)]
@@ -73,4 +126,11 @@ mod tests {
 
         assert_eq!(s, targ_m)
     }
+
+    #[test]
+    fn test_standardize_constant_column() {
+        let m = Matrix::new(vec![vec![1.0, 7.0], vec![3.0, 7.0]]).unwrap();
+        let s = standardize(&m).unwrap();
+        assert_eq!(s.rows, vec![vec![-1.0, 0.0], vec![1.0, 0.0]]);
+    }
 }
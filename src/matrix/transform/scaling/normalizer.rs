@@ -0,0 +1,128 @@
+use crate::{
+    error::{CustomErrors, MismatchError},
+    matrix::Matrix,
+    traits::{Fit, Transform},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Norm {
+    L1,
+    L2,
+    Max,
+}
+
+/// Rescales every row to unit norm. Works row by row, so fitting learns nothing;
+/// all-zero rows are left as they are.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Normalizer {
+    norm: Norm,
+}
+
+impl Default for Normalizer {
+    fn default() -> Self {
+        Normalizer::new(Norm::L2)
+    }
+}
+
+impl Normalizer {
+    pub fn new(norm: Norm) -> Normalizer {
+        Normalizer { norm }
+    }
+
+    /// Norm of every row of `x`, needed to undo the transform.
+    pub fn norms(&self, x: &Matrix<f64>) -> Vec<f64> {
+        x.rows
+            .iter()
+            .map(|row| match self.norm {
+                Norm::L1 => row.iter().map(|v| v.abs()).sum(),
+                Norm::L2 => row.iter().map(|v| v * v).sum::<f64>().sqrt(),
+                Norm::Max => row.iter().fold(0.0, |acc: f64, v| acc.max(v.abs())),
+            })
+            .collect()
+    }
+
+    pub fn transform(&self, x: &Matrix<f64>) -> Result<Matrix<f64>, CustomErrors> {
+        let norms = self.norms(x);
+        let rows = x
+            .rows
+            .iter()
+            .zip(norms.iter())
+            .map(|(row, norm)| {
+                let norm = if *norm == 0.0 { 1.0 } else { *norm };
+                row.iter().map(|v| v / norm).collect()
+            })
+            .collect();
+        Ok(Matrix {
+            rows,
+            m: x.m,
+            n: x.n,
+        })
+    }
+
+    /// Multiplies every row back by its original norm, see `norms`.
+    pub fn inverse_transform(
+        &self,
+        x: &Matrix<f64>,
+        norms: &[f64],
+    ) -> Result<Matrix<f64>, CustomErrors> {
+        if norms.len() != x.m {
+            return Err(CustomErrors::Mismatch(MismatchError));
+        }
+
+        let rows = x
+            .rows
+            .iter()
+            .zip(norms.iter())
+            .map(|(row, norm)| row.iter().map(|v| v * norm).collect())
+            .collect();
+        Ok(Matrix {
+            rows,
+            m: x.m,
+            n: x.n,
+        })
+    }
+}
+
+impl Fit for Normalizer {
+    type Input = Matrix<f64>;
+    type Target = ();
+    type Output = ();
+
+    fn fit(&mut self, _x: &Matrix<f64>, _y: &()) -> Result<(), CustomErrors> {
+        Ok(())
+    }
+}
+
+impl Transform for Normalizer {
+    type Input = Matrix<f64>;
+    type Output = Matrix<f64>;
+
+    fn transform(&self, x: &Matrix<f64>) -> Result<Matrix<f64>, CustomErrors> {
+        Normalizer::transform(self, x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::matrix::Matrix;
+
+    use super::{Norm, Normalizer};
+
+    #[test]
+    fn test_normalizer() {
+        let x = Matrix::new(vec![vec![3.0, -4.0], vec![0.0, 0.0]]).unwrap();
+
+        let l2 = Normalizer::new(Norm::L2);
+        let out = l2.transform(&x).unwrap();
+        assert_eq!(out.rows, vec![vec![0.6, -0.8], vec![0.0, 0.0]]);
+        assert_eq!(l2.inverse_transform(&out, &l2.norms(&x)).unwrap(), x);
+
+        let l1 = Normalizer::new(Norm::L1).transform(&x).unwrap();
+        assert_eq!(l1.rows[0], vec![3.0 / 7.0, -4.0 / 7.0]);
+
+        let max = Normalizer::new(Norm::Max).transform(&x).unwrap();
+        assert_eq!(max.rows[0], vec![0.75, -1.0]);
+    }
+}
@@ -0,0 +1,127 @@
+use crate::{
+    error::{BadTypeError, CustomErrors, EmptyVectorError},
+    matrix::Matrix,
+    traits::{Fit, Transform},
+    vector::operations::quantile,
+};
+
+use super::{safe_scale, scale_columns, unscale_columns};
+
+/// Centers on the median and divides by the interquartile range, so outliers
+/// barely move the fitted parameters. Columns with a zero range are only centered.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RobustScaler {
+    quantile_range: (f64, f64),
+    center: Vec<f64>,
+    scale: Vec<f64>,
+}
+
+impl Default for RobustScaler {
+    fn default() -> Self {
+        RobustScaler::new()
+    }
+}
+
+impl RobustScaler {
+    pub fn new() -> RobustScaler {
+        RobustScaler {
+            quantile_range: (0.25, 0.75),
+            center: vec![],
+            scale: vec![],
+        }
+    }
+
+    /// Quantiles (as fractions) whose difference is used as the scale.
+    pub fn with_quantile_range(mut self, lower: f64, upper: f64) -> RobustScaler {
+        self.quantile_range = (lower, upper);
+        self
+    }
+
+    /// Per-column medians.
+    pub fn center(&self) -> &Vec<f64> {
+        &self.center
+    }
+
+    pub fn scale(&self) -> &Vec<f64> {
+        &self.scale
+    }
+
+    pub fn fit(&mut self, x: &Matrix<f64>) -> Result<(), CustomErrors> {
+        let (lower, upper) = self.quantile_range;
+        if lower.is_nan() || upper.is_nan() || lower >= upper {
+            return Err(CustomErrors::BadType(BadTypeError));
+        }
+        if x.m == 0 {
+            return Err(CustomErrors::EmptyVector(EmptyVectorError));
+        }
+
+        let mut center = vec![];
+        let mut scale = vec![];
+        for column in x.transpose().rows {
+            center.push(quantile(&column, 0.5)?);
+            scale.push(safe_scale(
+                quantile(&column, upper)? - quantile(&column, lower)?,
+            ));
+        }
+        self.center = center;
+        self.scale = scale;
+        Ok(())
+    }
+
+    pub fn transform(&self, x: &Matrix<f64>) -> Result<Matrix<f64>, CustomErrors> {
+        scale_columns(x, &self.center, &self.scale)
+    }
+
+    pub fn inverse_transform(&self, x: &Matrix<f64>) -> Result<Matrix<f64>, CustomErrors> {
+        unscale_columns(x, &self.center, &self.scale)
+    }
+}
+
+impl Fit for RobustScaler {
+    type Input = Matrix<f64>;
+    type Target = ();
+    type Output = ();
+
+    fn fit(&mut self, x: &Matrix<f64>, _y: &()) -> Result<(), CustomErrors> {
+        RobustScaler::fit(self, x)
+    }
+}
+
+impl Transform for RobustScaler {
+    type Input = Matrix<f64>;
+    type Output = Matrix<f64>;
+
+    fn transform(&self, x: &Matrix<f64>) -> Result<Matrix<f64>, CustomErrors> {
+        RobustScaler::transform(self, x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::matrix::Matrix;
+
+    use super::RobustScaler;
+
+    #[test]
+    fn test_robust_scaler() {
+        // the outlier does not change median (3) or IQR (4 - 2)
+        let train = Matrix::new(vec![
+            vec![1.0],
+            vec![2.0],
+            vec![3.0],
+            vec![4.0],
+            vec![1000.0],
+        ])
+        .unwrap();
+
+        let mut scaler = RobustScaler::new();
+        scaler.fit(&train).unwrap();
+        assert_eq!(scaler.center(), &vec![3.0]);
+        assert_eq!(scaler.scale(), &vec![2.0]);
+
+        let out = scaler.transform(&train).unwrap();
+        assert_eq!(out.rows[0], vec![-1.0]);
+        assert_eq!(scaler.inverse_transform(&out).unwrap(), train);
+    }
+}
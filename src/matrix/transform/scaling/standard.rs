@@ -0,0 +1,136 @@
+use crate::{
+    error::CustomErrors,
+    matrix::Matrix,
+    traits::{Fit, Transform},
+};
+
+use super::{column_mean_std, safe_scale, scale_columns, unscale_columns};
+
+/// Removes the training mean and divides by the training standard deviation.
+/// Constant columns are only centered.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StandardScaler {
+    with_mean: bool,
+    with_std: bool,
+    mean: Vec<f64>,
+    scale: Vec<f64>,
+}
+
+impl Default for StandardScaler {
+    fn default() -> Self {
+        StandardScaler::new()
+    }
+}
+
+impl StandardScaler {
+    pub fn new() -> StandardScaler {
+        StandardScaler {
+            with_mean: true,
+            with_std: true,
+            mean: vec![],
+            scale: vec![],
+        }
+    }
+
+    pub fn with_mean(mut self, with_mean: bool) -> StandardScaler {
+        self.with_mean = with_mean;
+        self
+    }
+
+    pub fn with_std(mut self, with_std: bool) -> StandardScaler {
+        self.with_std = with_std;
+        self
+    }
+
+    /// Per-column offset, the training mean or 0 without centering.
+    pub fn mean(&self) -> &Vec<f64> {
+        &self.mean
+    }
+
+    /// Per-column divisor, the standard deviation or 1 for constant columns.
+    pub fn scale(&self) -> &Vec<f64> {
+        &self.scale
+    }
+
+    pub fn fit(&mut self, x: &Matrix<f64>) -> Result<(), CustomErrors> {
+        let (mean, std) = column_mean_std(x)?;
+        self.mean = if self.with_mean { mean } else { vec![0.0; x.n] };
+        self.scale = if self.with_std {
+            std.into_iter().map(safe_scale).collect()
+        } else {
+            vec![1.0; x.n]
+        };
+        Ok(())
+    }
+
+    pub fn transform(&self, x: &Matrix<f64>) -> Result<Matrix<f64>, CustomErrors> {
+        scale_columns(x, &self.mean, &self.scale)
+    }
+
+    pub fn inverse_transform(&self, x: &Matrix<f64>) -> Result<Matrix<f64>, CustomErrors> {
+        unscale_columns(x, &self.mean, &self.scale)
+    }
+}
+
+impl Fit for StandardScaler {
+    type Input = Matrix<f64>;
+    type Target = ();
+    type Output = ();
+
+    fn fit(&mut self, x: &Matrix<f64>, _y: &()) -> Result<(), CustomErrors> {
+        StandardScaler::fit(self, x)
+    }
+}
+
+impl Transform for StandardScaler {
+    type Input = Matrix<f64>;
+    type Output = Matrix<f64>;
+
+    fn transform(&self, x: &Matrix<f64>) -> Result<Matrix<f64>, CustomErrors> {
+        StandardScaler::transform(self, x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::matrix::Matrix;
+
+    use super::StandardScaler;
+
+    #[test]
+    fn test_standard_scaler() {
+        let train = Matrix::new(vec![vec![1.0, 5.0], vec![3.0, 5.0]]).unwrap();
+        let test = Matrix::new(vec![vec![5.0, 7.0]]).unwrap();
+
+        let mut scaler = StandardScaler::new();
+        scaler.fit(&train).unwrap();
+        assert_eq!(scaler.mean(), &vec![2.0, 5.0]);
+
+        // training statistics are reused, the constant column is only centered
+        let out = scaler.transform(&test).unwrap();
+        assert_eq!(out.rows, vec![vec![3.0, 2.0]]);
+        assert_eq!(scaler.inverse_transform(&out).unwrap(), test);
+
+        assert!(StandardScaler::new().transform(&test).is_err());
+    }
+
+    #[test]
+    fn test_standard_scaler_options() {
+        let train = Matrix::new(vec![vec![1.0], vec![3.0]]).unwrap();
+
+        let mut scaler = StandardScaler::new().with_mean(false);
+        scaler.fit(&train).unwrap();
+        assert_eq!(
+            scaler.transform(&train).unwrap().rows,
+            vec![vec![1.0], vec![3.0]]
+        );
+
+        let mut scaler = StandardScaler::new().with_std(false);
+        scaler.fit(&train).unwrap();
+        assert_eq!(
+            scaler.transform(&train).unwrap().rows,
+            vec![vec![-1.0], vec![1.0]]
+        );
+    }
+}
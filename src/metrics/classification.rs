@@ -0,0 +1,339 @@
+use crate::{
+    error::{BadTypeError, CustomErrors},
+    models::classifier::logreg::bce_loss,
+};
+
+use super::check_lengths;
+
+/// How per-class scores are combined into one.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Average {
+    /// Counts true/false positives over all classes at once.
+    Micro,
+    /// Unweighted mean of the per-class scores.
+    Macro,
+    /// Mean of the per-class scores weighted by the class support.
+    Weighted,
+}
+
+/// Share of predictions equal to the target.
+pub fn accuracy(y_true: &[f64], y_pred: &[f64]) -> Result<f64, CustomErrors> {
+    check_lengths(y_true, y_pred)?;
+    let correct = y_true
+        .iter()
+        .zip(y_pred.iter())
+        .filter(|(t, p)| t == p)
+        .count();
+    Ok(correct as f64 / y_true.len() as f64)
+}
+
+/// Counts of every (true, predicted) label pair, rows are true labels and
+/// columns predicted ones, both in the order of `labels`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfusionMatrix {
+    pub labels: Vec<f64>,
+    pub counts: Vec<Vec<usize>>,
+}
+
+impl ConfusionMatrix {
+    fn true_positives(&self, k: usize) -> usize {
+        self.counts[k][k]
+    }
+
+    /// Number of samples with true label `k`.
+    fn support(&self, k: usize) -> usize {
+        self.counts[k].iter().sum()
+    }
+
+    /// Number of samples predicted as label `k`.
+    fn predicted(&self, k: usize) -> usize {
+        self.counts.iter().map(|row| row[k]).sum()
+    }
+}
+
+/// Labels are the sorted union of the values in `y_true` and `y_pred`.
+pub fn confusion_matrix(y_true: &[f64], y_pred: &[f64]) -> Result<ConfusionMatrix, CustomErrors> {
+    check_lengths(y_true, y_pred)?;
+
+    let mut labels: Vec<f64> = y_true.iter().chain(y_pred.iter()).copied().collect();
+    if labels.iter().any(|v| v.is_nan()) {
+        return Err(CustomErrors::BadType(BadTypeError));
+    }
+    labels.sort_by(|a, b| a.total_cmp(b));
+    labels.dedup();
+
+    let position = |v: &f64| labels.iter().position(|l| l == v).unwrap();
+    let mut counts = vec![vec![0; labels.len()]; labels.len()];
+    for (t, p) in y_true.iter().zip(y_pred.iter()) {
+        counts[position(t)][position(p)] += 1;
+    }
+
+    Ok(ConfusionMatrix { labels, counts })
+}
+
+fn ratio(numerator: usize, denominator: usize) -> f64 {
+    if denominator == 0 {
+        0.0
+    } else {
+        numerator as f64 / denominator as f64
+    }
+}
+
+fn harmonic(precision: f64, recall: f64) -> f64 {
+    if precision + recall == 0.0 {
+        0.0
+    } else {
+        2.0 * precision * recall / (precision + recall)
+    }
+}
+
+/// Per-class (precision, recall) pairs along with the class supports.
+fn per_class(cm: &ConfusionMatrix) -> Vec<(f64, f64, usize)> {
+    (0..cm.labels.len())
+        .map(|k| {
+            let tp = cm.true_positives(k);
+            (
+                ratio(tp, cm.predicted(k)),
+                ratio(tp, cm.support(k)),
+                cm.support(k),
+            )
+        })
+        .collect()
+}
+
+fn averaged(scores: &[(f64, usize)], average: Average) -> f64 {
+    match average {
+        Average::Weighted => {
+            let total: usize = scores.iter().map(|(_, s)| s).sum();
+            scores.iter().map(|(v, s)| v * *s as f64).sum::<f64>() / total as f64
+        }
+        _ => scores.iter().map(|(v, _)| v).sum::<f64>() / scores.len() as f64,
+    }
+}
+
+/// Classes without predictions score 0.
+pub fn precision_score(
+    y_true: &[f64],
+    y_pred: &[f64],
+    average: Average,
+) -> Result<f64, CustomErrors> {
+    if average == Average::Micro {
+        // every wrong prediction is a false positive of one class
+        return accuracy(y_true, y_pred);
+    }
+    let cm = confusion_matrix(y_true, y_pred)?;
+    let scores: Vec<(f64, usize)> = per_class(&cm).iter().map(|(p, _, s)| (*p, *s)).collect();
+    Ok(averaged(&scores, average))
+}
+
+pub fn recall_score(y_true: &[f64], y_pred: &[f64], average: Average) -> Result<f64, CustomErrors> {
+    if average == Average::Micro {
+        return accuracy(y_true, y_pred);
+    }
+    let cm = confusion_matrix(y_true, y_pred)?;
+    let scores: Vec<(f64, usize)> = per_class(&cm).iter().map(|(_, r, s)| (*r, *s)).collect();
+    Ok(averaged(&scores, average))
+}
+
+/// Harmonic mean of precision and recall, averaged per class for `Macro` and `Weighted`.
+pub fn f1_score(y_true: &[f64], y_pred: &[f64], average: Average) -> Result<f64, CustomErrors> {
+    if average == Average::Micro {
+        return accuracy(y_true, y_pred);
+    }
+    let cm = confusion_matrix(y_true, y_pred)?;
+    let scores: Vec<(f64, usize)> = per_class(&cm)
+        .iter()
+        .map(|(p, r, s)| (harmonic(*p, *r), *s))
+        .collect();
+    Ok(averaged(&scores, average))
+}
+
+/// Distinct scores with the cumulative false and true positives at each.
+type BinaryCounts = (Vec<f64>, Vec<usize>, Vec<usize>);
+
+/// Highest score first. Targets must be 0 or 1, with both present.
+fn binary_counts(y_true: &[f64], scores: &[f64]) -> Result<BinaryCounts, CustomErrors> {
+    check_lengths(y_true, scores)?;
+    if y_true.iter().any(|v| *v != 0.0 && *v != 1.0) || scores.iter().any(|v| v.is_nan()) {
+        return Err(CustomErrors::BadType(BadTypeError));
+    }
+    let positives = y_true.iter().filter(|v| **v == 1.0).count();
+    if positives == 0 || positives == y_true.len() {
+        return Err(CustomErrors::BadType(BadTypeError));
+    }
+
+    let mut order: Vec<usize> = (0..scores.len()).collect();
+    order.sort_by(|a, b| scores[*b].total_cmp(&scores[*a]));
+
+    let mut thresholds = vec![];
+    let mut fps = vec![];
+    let mut tps = vec![];
+    let (mut fp, mut tp) = (0, 0);
+    for (k, i) in order.iter().enumerate() {
+        if y_true[*i] == 1.0 {
+            tp += 1;
+        } else {
+            fp += 1;
+        }
+        // only keep the last sample of a run of tied scores
+        if order.get(k + 1).is_none_or(|j| scores[*j] != scores[*i]) {
+            thresholds.push(scores[*i]);
+            fps.push(fp);
+            tps.push(tp);
+        }
+    }
+    Ok((thresholds, fps, tps))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RocCurve {
+    pub fpr: Vec<f64>,
+    pub tpr: Vec<f64>,
+    /// Decreasing, the first one is infinite so the curve starts at (0, 0).
+    pub thresholds: Vec<f64>,
+}
+
+/// Receiver operating characteristic of binary targets (1.0 is the positive
+/// class) against scores, predicting positive when `score >= threshold`.
+pub fn roc_curve(y_true: &[f64], scores: &[f64]) -> Result<RocCurve, CustomErrors> {
+    let (cuts, fps, tps) = binary_counts(y_true, scores)?;
+    let positives = *tps.last().unwrap() as f64;
+    let negatives = *fps.last().unwrap() as f64;
+
+    let mut thresholds = vec![f64::INFINITY];
+    let mut fpr = vec![0.0];
+    let mut tpr = vec![0.0];
+    for ((t, fp), tp) in cuts.iter().zip(fps.iter()).zip(tps.iter()) {
+        thresholds.push(*t);
+        fpr.push(*fp as f64 / negatives);
+        tpr.push(*tp as f64 / positives);
+    }
+
+    Ok(RocCurve {
+        fpr,
+        tpr,
+        thresholds,
+    })
+}
+
+/// Area under the ROC curve by the trapezoidal rule.
+pub fn roc_auc_score(y_true: &[f64], scores: &[f64]) -> Result<f64, CustomErrors> {
+    let curve = roc_curve(y_true, scores)?;
+    let mut area = 0.0;
+    for k in 1..curve.fpr.len() {
+        area += (curve.fpr[k] - curve.fpr[k - 1]) * (curve.tpr[k] + curve.tpr[k - 1]) / 2.0;
+    }
+    Ok(area)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrecisionRecallCurve {
+    pub precision: Vec<f64>,
+    pub recall: Vec<f64>,
+    /// Decreasing, one per distinct score.
+    pub thresholds: Vec<f64>,
+}
+
+/// Precision and recall of binary targets at every distinct score.
+pub fn precision_recall_curve(
+    y_true: &[f64],
+    scores: &[f64],
+) -> Result<PrecisionRecallCurve, CustomErrors> {
+    let (thresholds, fps, tps) = binary_counts(y_true, scores)?;
+    let positives = *tps.last().unwrap();
+
+    let precision = tps
+        .iter()
+        .zip(fps.iter())
+        .map(|(tp, fp)| ratio(*tp, tp + fp))
+        .collect();
+    let recall = tps.iter().map(|tp| ratio(*tp, positives)).collect();
+
+    Ok(PrecisionRecallCurve {
+        precision,
+        recall,
+        thresholds,
+    })
+}
+
+/// Area under the precision-recall curve as average precision, the sum of the
+/// precisions weighted by the recall gained at each threshold.
+pub fn average_precision_score(y_true: &[f64], scores: &[f64]) -> Result<f64, CustomErrors> {
+    let curve = precision_recall_curve(y_true, scores)?;
+    let mut area = 0.0;
+    let mut previous_recall = 0.0;
+    for (p, r) in curve.precision.iter().zip(curve.recall.iter()) {
+        area += (r - previous_recall) * p;
+        previous_recall = *r;
+    }
+    Ok(area)
+}
+
+/// Binary cross-entropy of 0/1 targets against predicted probabilities.
+pub fn log_loss(y_true: &[f64], y_prob: &[f64]) -> Result<f64, CustomErrors> {
+    check_lengths(y_true, y_prob)?;
+    bce_loss(&y_true.to_vec(), &y_prob.to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        accuracy, average_precision_score, confusion_matrix, f1_score, log_loss, precision_score,
+        recall_score, roc_auc_score, roc_curve, Average,
+    };
+
+    #[test]
+    fn test_classification_scores() {
+        // reference values from scikit-learn
+        let y_true = [0.0, 1.0, 2.0, 0.0, 1.0, 2.0];
+        let y_pred = [0.0, 2.0, 1.0, 0.0, 0.0, 1.0];
+
+        let cm = confusion_matrix(&y_true, &y_pred).unwrap();
+        assert_eq!(cm.labels, vec![0.0, 1.0, 2.0]);
+        assert_eq!(cm.counts, vec![vec![2, 0, 0], vec![1, 0, 1], vec![0, 2, 0]]);
+
+        assert!((accuracy(&y_true, &y_pred).unwrap() - 1.0 / 3.0).abs() < 1e-12);
+        assert!(
+            (precision_score(&y_true, &y_pred, Average::Macro).unwrap() - 2.0 / 9.0).abs() < 1e-12
+        );
+        assert!(
+            (recall_score(&y_true, &y_pred, Average::Macro).unwrap() - 1.0 / 3.0).abs() < 1e-12
+        );
+        assert!(
+            (f1_score(&y_true, &y_pred, Average::Macro).unwrap() - 0.26666666666666666).abs()
+                < 1e-12
+        );
+        assert!((f1_score(&y_true, &y_pred, Average::Micro).unwrap() - 1.0 / 3.0).abs() < 1e-12);
+
+        let y_true = [0.0, 0.0, 0.0, 1.0, 1.0];
+        let y_pred = [0.0, 0.0, 1.0, 1.0, 0.0];
+        assert!((f1_score(&y_true, &y_pred, Average::Weighted).unwrap() - 0.6).abs() < 1e-12);
+
+        assert!(accuracy(&y_true, &y_pred[..3]).is_err());
+    }
+
+    #[test]
+    fn test_ranking_scores() {
+        // reference values from scikit-learn
+        let y_true = [0.0, 0.0, 1.0, 1.0];
+        let scores = [0.1, 0.4, 0.35, 0.8];
+
+        let roc = roc_curve(&y_true, &scores).unwrap();
+        assert_eq!(roc.fpr, vec![0.0, 0.0, 0.5, 0.5, 1.0]);
+        assert_eq!(roc.tpr, vec![0.0, 0.5, 0.5, 1.0, 1.0]);
+        assert_eq!(roc.thresholds[1..], [0.8, 0.4, 0.35, 0.1]);
+        assert_eq!(roc_auc_score(&y_true, &scores).unwrap(), 0.75);
+        assert!(
+            (average_precision_score(&y_true, &scores).unwrap() - 0.8333333333333333).abs() < 1e-12
+        );
+
+        // ties share a threshold
+        let roc = roc_curve(&[0.0, 1.0, 1.0], &[0.5, 0.5, 0.9]).unwrap();
+        assert_eq!(roc.thresholds[1..], [0.9, 0.5]);
+
+        assert!(roc_auc_score(&[1.0, 1.0], &[0.2, 0.3]).is_err());
+
+        let loss = log_loss(&[1.0, 0.0], &[0.8, 0.1]).unwrap();
+        assert!((loss - 0.164252033486018).abs() < 1e-6);
+    }
+}
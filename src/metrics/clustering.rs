@@ -0,0 +1,143 @@
+use crate::{
+    distance::{pairwise_distances, Distance},
+    error::{BadTypeError, CustomErrors},
+    matrix::Matrix,
+};
+
+use super::check_lengths;
+
+/// Silhouette coefficient of every sample, `(b - a) / max(a, b)` where `a` is
+/// the mean distance to the rest of its cluster and `b` the mean distance to
+/// the closest other cluster. Samples alone in their cluster score 0.
+pub fn silhouette_samples<D: Distance + ?Sized>(
+    x: &Matrix<f64>,
+    labels: &[usize],
+    metric: &D,
+) -> Result<Vec<f64>, CustomErrors> {
+    check_lengths(&x.rows, labels)?;
+    let n_clusters = labels.iter().max().unwrap() + 1;
+    let mut sizes = vec![0; n_clusters];
+    for label in labels {
+        sizes[*label] += 1;
+    }
+    let used = sizes.iter().filter(|s| **s > 0).count();
+    if used < 2 || used == labels.len() {
+        return Err(CustomErrors::BadType(BadTypeError));
+    }
+
+    let distances = pairwise_distances(x, x, metric)?;
+    let mut scores = vec![];
+    for (row, own) in distances.rows.iter().zip(labels.iter()) {
+        if sizes[*own] == 1 {
+            scores.push(0.0);
+            continue;
+        }
+
+        let mut sums = vec![0.0; n_clusters];
+        for (d, label) in row.iter().zip(labels.iter()) {
+            sums[*label] += d;
+        }
+        let a = sums[*own] / (sizes[*own] - 1) as f64;
+        let b = sums
+            .iter()
+            .zip(sizes.iter())
+            .enumerate()
+            .filter(|(k, (_, size))| k != own && **size > 0)
+            .map(|(_, (sum, size))| sum / *size as f64)
+            .fold(f64::INFINITY, f64::min);
+
+        let denominator = a.max(b);
+        scores.push(if denominator > 0.0 {
+            (b - a) / denominator
+        } else {
+            0.0
+        });
+    }
+    Ok(scores)
+}
+
+/// Mean silhouette coefficient, between -1 and 1, higher for dense and well
+/// separated clusters. Needs between 2 and `n - 1` clusters.
+pub fn silhouette_score<D: Distance + ?Sized>(
+    x: &Matrix<f64>,
+    labels: &[usize],
+    metric: &D,
+) -> Result<f64, CustomErrors> {
+    let scores = silhouette_samples(x, labels, metric)?;
+    Ok(scores.iter().sum::<f64>() / scores.len() as f64)
+}
+
+fn pairs(n: usize) -> f64 {
+    (n * n.saturating_sub(1)) as f64 / 2.0
+}
+
+/// Rand index corrected for chance: 1 for identical partitions (up to a
+/// renaming of the labels), around 0 for random ones.
+pub fn adjusted_rand_score(
+    labels_true: &[usize],
+    labels_pred: &[usize],
+) -> Result<f64, CustomErrors> {
+    check_lengths(labels_true, labels_pred)?;
+    let n_true = labels_true.iter().max().unwrap() + 1;
+    let n_pred = labels_pred.iter().max().unwrap() + 1;
+
+    let mut contingency = vec![vec![0; n_pred]; n_true];
+    for (t, p) in labels_true.iter().zip(labels_pred.iter()) {
+        contingency[*t][*p] += 1;
+    }
+
+    let index: f64 = contingency.iter().flatten().map(|c| pairs(*c)).sum();
+    let sum_true: f64 = contingency.iter().map(|row| pairs(row.iter().sum())).sum();
+    let sum_pred: f64 = (0..n_pred)
+        .map(|j| pairs(contingency.iter().map(|row| row[j]).sum()))
+        .sum();
+
+    let expected = sum_true * sum_pred / pairs(labels_true.len());
+    let maximum = (sum_true + sum_pred) / 2.0;
+    // both partitions trivial, e.g. a single cluster each
+    if maximum == expected {
+        return Ok(1.0);
+    }
+    Ok((index - expected) / (maximum - expected))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{distance::Euclidean, matrix::Matrix};
+
+    use super::{adjusted_rand_score, silhouette_samples, silhouette_score};
+
+    #[test]
+    fn test_silhouette() {
+        let x = Matrix::new(vec![
+            vec![0.0, 0.0],
+            vec![0.0, 1.0],
+            vec![4.0, 0.0],
+            vec![4.0, 1.0],
+        ])
+        .unwrap();
+
+        // a = 1, b = (4 + sqrt(17)) / 2
+        let b = (4.0 + 17.0_f64.sqrt()) / 2.0;
+        let score = silhouette_score(&x, &[0, 0, 1, 1], &Euclidean).unwrap();
+        assert!((score - (b - 1.0) / b).abs() < 1e-12);
+
+        let bad = silhouette_samples(&x, &[0, 1, 1, 1], &Euclidean).unwrap();
+        assert_eq!(bad[0], 0.0);
+        assert!(silhouette_score(&x, &[0, 0, 0, 0], &Euclidean).is_err());
+        assert!(silhouette_score(&x, &[0, 1, 2, 3], &Euclidean).is_err());
+    }
+
+    #[test]
+    fn test_adjusted_rand_score() {
+        assert_eq!(
+            adjusted_rand_score(&[0, 0, 1, 1], &[1, 1, 0, 0]).unwrap(),
+            1.0
+        );
+        // reference value from scikit-learn
+        let ari = adjusted_rand_score(&[0, 0, 1, 2], &[0, 0, 1, 1]).unwrap();
+        assert!((ari - 0.5714285714285714).abs() < 1e-12);
+        assert_eq!(adjusted_rand_score(&[0, 0, 0], &[0, 0, 0]).unwrap(), 1.0);
+        assert!(adjusted_rand_score(&[0, 1], &[0]).is_err());
+    }
+}
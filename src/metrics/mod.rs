@@ -0,0 +1,26 @@
+pub mod classification;
+pub mod clustering;
+pub mod regression;
+
+pub use classification::{
+    accuracy, average_precision_score, confusion_matrix, f1_score, log_loss,
+    precision_recall_curve, precision_score, recall_score, roc_auc_score, roc_curve, Average,
+    ConfusionMatrix, PrecisionRecallCurve, RocCurve,
+};
+pub use clustering::{adjusted_rand_score, silhouette_samples, silhouette_score};
+pub use regression::{
+    explained_variance_score, mean_absolute_error, mean_squared_error, r2_score,
+    root_mean_squared_error,
+};
+
+use crate::error::{CustomErrors, EmptyVectorError, MismatchError};
+
+fn check_lengths<A, B>(y_true: &[A], y_pred: &[B]) -> Result<(), CustomErrors> {
+    if y_true.is_empty() {
+        return Err(CustomErrors::EmptyVector(EmptyVectorError));
+    }
+    if y_true.len() != y_pred.len() {
+        return Err(CustomErrors::Mismatch(MismatchError));
+    }
+    Ok(())
+}
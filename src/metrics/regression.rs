@@ -0,0 +1,87 @@
+use crate::{
+    error::CustomErrors,
+    vector::operations::{mean, variance},
+};
+
+use super::check_lengths;
+
+fn residuals(y_true: &[f64], y_pred: &[f64]) -> Vec<f64> {
+    y_true
+        .iter()
+        .zip(y_pred.iter())
+        .map(|(t, p)| t - p)
+        .collect()
+}
+
+/// Compares `1 - numerator / denominator` the way R^2 style scores do when the
+/// targets are constant: 1 for a perfect fit, 0 otherwise.
+fn explained(numerator: f64, denominator: f64) -> f64 {
+    if denominator == 0.0 {
+        return if numerator == 0.0 { 1.0 } else { 0.0 };
+    }
+    1.0 - numerator / denominator
+}
+
+pub fn mean_squared_error(y_true: &[f64], y_pred: &[f64]) -> Result<f64, CustomErrors> {
+    check_lengths(y_true, y_pred)?;
+    let squared: Vec<f64> = residuals(y_true, y_pred).iter().map(|r| r * r).collect();
+    mean(&squared)
+}
+
+pub fn root_mean_squared_error(y_true: &[f64], y_pred: &[f64]) -> Result<f64, CustomErrors> {
+    Ok(mean_squared_error(y_true, y_pred)?.sqrt())
+}
+
+pub fn mean_absolute_error(y_true: &[f64], y_pred: &[f64]) -> Result<f64, CustomErrors> {
+    check_lengths(y_true, y_pred)?;
+    let absolute: Vec<f64> = residuals(y_true, y_pred).iter().map(|r| r.abs()).collect();
+    mean(&absolute)
+}
+
+/// Coefficient of determination, `1 - SS_res / SS_tot`.
+pub fn r2_score(y_true: &[f64], y_pred: &[f64]) -> Result<f64, CustomErrors> {
+    check_lengths(y_true, y_pred)?;
+    Ok(explained(
+        mean_squared_error(y_true, y_pred)?,
+        variance(y_true, 0)?,
+    ))
+}
+
+/// `1 - Var(y_true - y_pred) / Var(y_true)`, ignores a constant bias in the predictions.
+pub fn explained_variance_score(y_true: &[f64], y_pred: &[f64]) -> Result<f64, CustomErrors> {
+    check_lengths(y_true, y_pred)?;
+    Ok(explained(
+        variance(&residuals(y_true, y_pred), 0)?,
+        variance(y_true, 0)?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        explained_variance_score, mean_absolute_error, mean_squared_error, r2_score,
+        root_mean_squared_error,
+    };
+
+    #[test]
+    fn test_regression_metrics() {
+        // reference values from scikit-learn
+        let y_true = [3.0, -0.5, 2.0, 7.0];
+        let y_pred = [2.5, 0.0, 2.0, 8.0];
+
+        assert_eq!(mean_squared_error(&y_true, &y_pred).unwrap(), 0.375);
+        assert_eq!(
+            root_mean_squared_error(&y_true, &y_pred).unwrap(),
+            0.375_f64.sqrt()
+        );
+        assert_eq!(mean_absolute_error(&y_true, &y_pred).unwrap(), 0.5);
+        assert!((r2_score(&y_true, &y_pred).unwrap() - 0.9486081370449679).abs() < 1e-12);
+        assert!(
+            (explained_variance_score(&y_true, &y_pred).unwrap() - 0.9571734475374732).abs()
+                < 1e-12
+        );
+
+        assert!(r2_score(&y_true, &y_pred[..2]).is_err());
+        assert!(mean_squared_error(&[], &[]).is_err());
+    }
+}
@@ -0,0 +1,5 @@
+pub mod search;
+pub mod split;
+
+pub use search::{cross_val_score, GridResult, GridSearch, Params};
+pub use split::{train_test_split, CrossValidator, Folds, KFold, StratifiedKFold, TrainTestSplit};
@@ -0,0 +1,263 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    error::{CustomErrors, EmptyVectorError},
+    matrix::Matrix,
+    traits::{Fit, Predict},
+};
+
+use super::split::{CrossValidator, KFold};
+
+/// Hyperparameter values by name.
+pub type Params = BTreeMap<String, f64>;
+
+/// Scores a fresh clone of `estimator` on every fold of `cv`: fitted on the
+/// training part, `scoring(y_true, y_pred)` on the held out part.
+pub fn cross_val_score<E, C, S>(
+    estimator: &E,
+    x: &Matrix<f64>,
+    y: &[f64],
+    cv: &C,
+    scoring: S,
+) -> Result<Vec<f64>, CustomErrors>
+where
+    E: Fit<Input = Matrix<f64>, Target = [f64]>
+        + Predict<Input = Matrix<f64>, Output = Vec<f64>>
+        + Clone,
+    C: CrossValidator + ?Sized,
+    S: Fn(&[f64], &[f64]) -> Result<f64, CustomErrors>,
+{
+    let mut scores = vec![];
+    for (train, test) in cv.folds(x, y)? {
+        let y_train: Vec<f64> = train.iter().map(|i| y[*i]).collect();
+        let y_test: Vec<f64> = test.iter().map(|i| y[*i]).collect();
+
+        let mut model = estimator.clone();
+        model.fit(&x.select_rows(&train), &y_train)?;
+        let pred = model.predict(&x.select_rows(&test))?;
+        scores.push(scoring(&y_test, &pred)?);
+    }
+    Ok(scores)
+}
+
+/// Cross-validation scores of one hyperparameter combination.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GridResult {
+    pub params: Params,
+    pub scores: Vec<f64>,
+    pub mean_score: f64,
+}
+
+type Scoring = Box<dyn Fn(&[f64], &[f64]) -> Result<f64, CustomErrors>>;
+
+/// Exhaustive search over every combination of the given hyperparameter
+/// values. `build` makes an estimator from a combination, higher scores are
+/// better (negate error metrics). The best combination is refitted on the
+/// whole data.
+pub struct GridSearch<E> {
+    build: Box<dyn Fn(&Params) -> E>,
+    scoring: Scoring,
+    cv: Box<dyn CrossValidator>,
+    grid: Vec<(String, Vec<f64>)>,
+    results: Vec<GridResult>,
+    best: Option<(usize, E)>,
+}
+
+impl<E> GridSearch<E>
+where
+    E: Fit<Input = Matrix<f64>, Target = [f64]>
+        + Predict<Input = Matrix<f64>, Output = Vec<f64>>
+        + Clone,
+{
+    /// Uses 5-fold cross-validation unless told otherwise.
+    pub fn new<B, S>(build: B, scoring: S) -> GridSearch<E>
+    where
+        B: Fn(&Params) -> E + 'static,
+        S: Fn(&[f64], &[f64]) -> Result<f64, CustomErrors> + 'static,
+    {
+        GridSearch {
+            build: Box::new(build),
+            scoring: Box::new(scoring),
+            cv: Box::new(KFold::new(5)),
+            grid: vec![],
+            results: vec![],
+            best: None,
+        }
+    }
+
+    pub fn with_param(mut self, name: &str, values: &[f64]) -> GridSearch<E> {
+        self.grid.push((name.to_string(), values.to_vec()));
+        self
+    }
+
+    pub fn with_cv<C: CrossValidator + 'static>(mut self, cv: C) -> GridSearch<E> {
+        self.cv = Box::new(cv);
+        self
+    }
+
+    /// Every combination in grid order, the last parameter varying fastest.
+    fn combinations(&self) -> Vec<Params> {
+        let mut combinations = vec![Params::new()];
+        for (name, values) in &self.grid {
+            let mut extended = vec![];
+            for params in &combinations {
+                for value in values {
+                    let mut params = params.clone();
+                    params.insert(name.clone(), *value);
+                    extended.push(params);
+                }
+            }
+            combinations = extended;
+        }
+        combinations
+    }
+
+    /// Fails with `EmptyVector` when a parameter was given no values, as
+    /// there is then nothing to search.
+    pub fn fit(&mut self, x: &Matrix<f64>, y: &[f64]) -> Result<(), CustomErrors> {
+        let combinations = self.combinations();
+        if combinations.is_empty() {
+            return Err(CustomErrors::EmptyVector(EmptyVectorError));
+        }
+
+        let mut results = vec![];
+        for params in combinations {
+            let estimator = (self.build)(&params);
+            let scores = cross_val_score(&estimator, x, y, self.cv.as_ref(), &self.scoring)?;
+            let mean_score = scores.iter().sum::<f64>() / scores.len() as f64;
+            results.push(GridResult {
+                params,
+                scores,
+                mean_score,
+            });
+        }
+
+        // the first of equal scores wins, a NaN score never does
+        let mut best = 0;
+        for (k, result) in results.iter().enumerate() {
+            if result.mean_score > results[best].mean_score || results[best].mean_score.is_nan() {
+                best = k;
+            }
+        }
+
+        let mut estimator = (self.build)(&results[best].params);
+        estimator.fit(x, y)?;
+
+        self.results = results;
+        self.best = Some((best, estimator));
+        Ok(())
+    }
+
+    pub fn results(&self) -> &Vec<GridResult> {
+        &self.results
+    }
+
+    pub fn best_params(&self) -> Option<&Params> {
+        self.best.as_ref().map(|(b, _)| &self.results[*b].params)
+    }
+
+    pub fn best_score(&self) -> Option<f64> {
+        self.best.as_ref().map(|(b, _)| self.results[*b].mean_score)
+    }
+
+    /// The best combination, fitted on all the data given to `fit`.
+    pub fn best_estimator(&self) -> Option<&E> {
+        self.best.as_ref().map(|(_, estimator)| estimator)
+    }
+
+    pub fn predict(&self, x: &Matrix<f64>) -> Result<Vec<f64>, CustomErrors> {
+        match &self.best {
+            Some((_, estimator)) => estimator.predict(x),
+            None => Err(CustomErrors::EmptyVector(EmptyVectorError)),
+        }
+    }
+}
+
+impl<E> Fit for GridSearch<E>
+where
+    E: Fit<Input = Matrix<f64>, Target = [f64]>
+        + Predict<Input = Matrix<f64>, Output = Vec<f64>>
+        + Clone,
+{
+    type Input = Matrix<f64>;
+    type Target = [f64];
+    type Output = ();
+
+    fn fit(&mut self, x: &Matrix<f64>, y: &[f64]) -> Result<(), CustomErrors> {
+        GridSearch::fit(self, x, y)
+    }
+}
+
+impl<E> Predict for GridSearch<E>
+where
+    E: Fit<Input = Matrix<f64>, Target = [f64]>
+        + Predict<Input = Matrix<f64>, Output = Vec<f64>>
+        + Clone,
+{
+    type Input = Matrix<f64>;
+    type Output = Vec<f64>;
+
+    fn predict(&self, x: &Matrix<f64>) -> Result<Vec<f64>, CustomErrors> {
+        GridSearch::predict(self, x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        data::{encoding::LabelEncoder, get_iris_data},
+        error::CustomErrors,
+        matrix::Matrix,
+        metrics::{accuracy, mean_squared_error},
+        model_selection::split::StratifiedKFold,
+        models::{linear::penalized::Ridge, neighbors::knn::KNeighborsClassifier},
+    };
+
+    use super::{cross_val_score, GridSearch};
+
+    #[test]
+    fn test_cross_val_score_iris() {
+        let (features, labels) = get_iris_data();
+        let x = Matrix::new(features).unwrap();
+        let y = LabelEncoder::new().fit_transform(&labels).unwrap();
+
+        let cv = StratifiedKFold::new(5).with_shuffle(0);
+        let scores = cross_val_score(&KNeighborsClassifier::new(5), &x, &y, &cv, accuracy).unwrap();
+        assert_eq!(scores.len(), 5);
+        assert!(scores.iter().all(|s| *s > 0.85));
+    }
+
+    #[test]
+    fn test_grid_search() {
+        let x = Matrix::new((0..20).map(|i| vec![i as f64]).collect()).unwrap();
+        let y: Vec<f64> = (0..20).map(|i| 2.0 * i as f64 + 1.0).collect();
+
+        let mut search = GridSearch::new(
+            |p| Ridge::new(p["alpha"]),
+            |t: &[f64], p: &[f64]| Ok(-mean_squared_error(t, p)?),
+        )
+        .with_param("alpha", &[10.0, 0.0, 1.0]);
+        search.fit(&x, &y).unwrap();
+
+        // noiseless data, no shrinkage is best
+        assert_eq!(search.results().len(), 3);
+        assert_eq!(search.best_params().unwrap()["alpha"], 0.0);
+        assert!(search.best_score().unwrap().abs() < 1e-10);
+        let pred = search.predict(&x).unwrap();
+        assert!((pred[3] - 7.0).abs() < 1e-10);
+
+        let search = GridSearch::new(|p| Ridge::new(p["alpha"]), mean_squared_error)
+            .with_param("alpha", &[1.0, 2.0])
+            .with_param("unused", &[0.0, 1.0, 2.0]);
+        assert_eq!(search.combinations().len(), 6);
+
+        let mut search = GridSearch::new(|p| Ridge::new(p["alpha"]), mean_squared_error)
+            .with_param("alpha", &[1.0, 2.0])
+            .with_param("beta", &[]);
+        assert!(matches!(
+            search.fit(&x, &y),
+            Err(CustomErrors::EmptyVector(_))
+        ));
+        assert!(search.best_params().is_none());
+    }
+}
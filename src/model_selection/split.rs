@@ -0,0 +1,299 @@
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+use crate::{
+    error::{BadTypeError, CustomErrors, EmptyVectorError, MismatchError},
+    matrix::Matrix,
+};
+
+/// Sample indices grouped by label, labels in increasing order.
+fn class_indices(y: &[f64]) -> Result<Vec<Vec<usize>>, CustomErrors> {
+    if y.iter().any(|v| v.is_nan()) {
+        return Err(CustomErrors::BadType(BadTypeError));
+    }
+    let mut order: Vec<usize> = (0..y.len()).collect();
+    order.sort_by(|a, b| y[*a].total_cmp(&y[*b]));
+
+    let mut groups: Vec<Vec<usize>> = vec![];
+    for i in order {
+        match groups.last_mut() {
+            Some(group) if y[group[0]] == y[i] => group.push(i),
+            _ => groups.push(vec![i]),
+        }
+    }
+    Ok(groups)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrainTestSplit {
+    pub x_train: Matrix<f64>,
+    pub x_test: Matrix<f64>,
+    pub y_train: Vec<f64>,
+    pub y_test: Vec<f64>,
+}
+
+/// Shuffles the rows and holds out `test_size` of them (rounded up). With
+/// `stratify` every label keeps about the same share in both parts.
+pub fn train_test_split(
+    x: &Matrix<f64>,
+    y: &[f64],
+    test_size: f64,
+    seed: u64,
+    stratify: bool,
+) -> Result<TrainTestSplit, CustomErrors> {
+    if x.m == 0 {
+        return Err(CustomErrors::EmptyVector(EmptyVectorError));
+    }
+    if x.m != y.len() {
+        return Err(CustomErrors::Mismatch(MismatchError));
+    }
+    if test_size.is_nan() || test_size <= 0.0 || test_size >= 1.0 {
+        return Err(CustomErrors::BadType(BadTypeError));
+    }
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let groups = if stratify {
+        class_indices(y)?
+    } else {
+        vec![(0..x.m).collect()]
+    };
+
+    let mut train = vec![];
+    let mut test = vec![];
+    for mut group in groups {
+        group.shuffle(&mut rng);
+        let n_test = if stratify {
+            (test_size * group.len() as f64).round() as usize
+        } else {
+            (test_size * group.len() as f64).ceil() as usize
+        };
+        test.extend_from_slice(&group[..n_test]);
+        train.extend_from_slice(&group[n_test..]);
+    }
+    if train.is_empty() || test.is_empty() {
+        return Err(CustomErrors::BadType(BadTypeError));
+    }
+    // mix the labels back together
+    if stratify {
+        train.shuffle(&mut rng);
+        test.shuffle(&mut rng);
+    }
+
+    Ok(TrainTestSplit {
+        x_train: x.select_rows(&train),
+        x_test: x.select_rows(&test),
+        y_train: train.iter().map(|i| y[*i]).collect(),
+        y_test: test.iter().map(|i| y[*i]).collect(),
+    })
+}
+
+/// Train and test indices of every fold, both in increasing order.
+#[derive(Debug, Clone)]
+pub struct Folds {
+    test_folds: Vec<Vec<usize>>,
+    n_samples: usize,
+    next: usize,
+}
+
+impl Folds {
+    fn new(mut test_folds: Vec<Vec<usize>>, n_samples: usize) -> Folds {
+        for fold in test_folds.iter_mut() {
+            fold.sort();
+        }
+        Folds {
+            test_folds,
+            n_samples,
+            next: 0,
+        }
+    }
+}
+
+impl Iterator for Folds {
+    type Item = (Vec<usize>, Vec<usize>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let test = self.test_folds.get(self.next)?.clone();
+        self.next += 1;
+
+        let mut in_test = vec![false; self.n_samples];
+        for i in &test {
+            in_test[*i] = true;
+        }
+        let train = (0..self.n_samples).filter(|i| !in_test[*i]).collect();
+        Some((train, test))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let left = self.test_folds.len() - self.next;
+        (left, Some(left))
+    }
+}
+
+/// Anything that splits a dataset into cross-validation folds.
+pub trait CrossValidator {
+    fn folds(&self, x: &Matrix<f64>, y: &[f64]) -> Result<Folds, CustomErrors>;
+}
+
+fn check_splits(n_splits: usize, n_samples: usize) -> Result<(), CustomErrors> {
+    if n_samples == 0 {
+        return Err(CustomErrors::EmptyVector(EmptyVectorError));
+    }
+    if n_splits < 2 || n_splits > n_samples {
+        return Err(CustomErrors::BadType(BadTypeError));
+    }
+    Ok(())
+}
+
+/// Consecutive folds of (almost) equal size, the first `n_samples % n_splits`
+/// ones get an extra sample.
+#[derive(Debug, Clone)]
+pub struct KFold {
+    n_splits: usize,
+    seed: Option<u64>,
+}
+
+impl KFold {
+    pub fn new(n_splits: usize) -> KFold {
+        KFold {
+            n_splits,
+            seed: None,
+        }
+    }
+
+    /// Shuffle the samples before cutting the folds.
+    pub fn with_shuffle(mut self, seed: u64) -> KFold {
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn split(&self, n_samples: usize) -> Result<Folds, CustomErrors> {
+        check_splits(self.n_splits, n_samples)?;
+
+        let mut order: Vec<usize> = (0..n_samples).collect();
+        if let Some(seed) = self.seed {
+            order.shuffle(&mut StdRng::seed_from_u64(seed));
+        }
+
+        let mut test_folds = vec![];
+        let mut start = 0;
+        for k in 0..self.n_splits {
+            let size = n_samples / self.n_splits + usize::from(k < n_samples % self.n_splits);
+            test_folds.push(order[start..start + size].to_vec());
+            start += size;
+        }
+        Ok(Folds::new(test_folds, n_samples))
+    }
+}
+
+impl CrossValidator for KFold {
+    fn folds(&self, x: &Matrix<f64>, _y: &[f64]) -> Result<Folds, CustomErrors> {
+        self.split(x.m)
+    }
+}
+
+/// Folds that keep the label proportions of `y`: the samples of every label
+/// are dealt over the folds in turn.
+#[derive(Debug, Clone)]
+pub struct StratifiedKFold {
+    n_splits: usize,
+    seed: Option<u64>,
+}
+
+impl StratifiedKFold {
+    pub fn new(n_splits: usize) -> StratifiedKFold {
+        StratifiedKFold {
+            n_splits,
+            seed: None,
+        }
+    }
+
+    /// Shuffle the samples of every label before dealing them.
+    pub fn with_shuffle(mut self, seed: u64) -> StratifiedKFold {
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn split(&self, y: &[f64]) -> Result<Folds, CustomErrors> {
+        check_splits(self.n_splits, y.len())?;
+
+        let mut rng = self.seed.map(StdRng::seed_from_u64);
+        let mut test_folds = vec![vec![]; self.n_splits];
+        let mut k = 0;
+        for mut group in class_indices(y)? {
+            if let Some(rng) = rng.as_mut() {
+                group.shuffle(rng);
+            }
+            for i in group {
+                test_folds[k % self.n_splits].push(i);
+                k += 1;
+            }
+        }
+        Ok(Folds::new(test_folds, y.len()))
+    }
+}
+
+impl CrossValidator for StratifiedKFold {
+    fn folds(&self, x: &Matrix<f64>, y: &[f64]) -> Result<Folds, CustomErrors> {
+        if x.m != y.len() {
+            return Err(CustomErrors::Mismatch(MismatchError));
+        }
+        self.split(y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::matrix::Matrix;
+
+    use super::{train_test_split, KFold, StratifiedKFold};
+
+    #[test]
+    fn test_train_test_split() {
+        let x = Matrix::new((0..10).map(|i| vec![i as f64]).collect()).unwrap();
+        let y: Vec<f64> = (0..10).map(|i| if i < 6 { 0.0 } else { 1.0 }).collect();
+
+        let split = train_test_split(&x, &y, 0.25, 7, false).unwrap();
+        assert_eq!((split.x_train.m, split.x_test.m), (7, 3));
+        // rows and targets stay paired
+        for (row, target) in split.x_test.rows.iter().zip(split.y_test.iter()) {
+            assert_eq!(y[row[0] as usize], *target);
+        }
+        assert_eq!(split, train_test_split(&x, &y, 0.25, 7, false).unwrap());
+
+        let split = train_test_split(&x, &y, 0.5, 7, true).unwrap();
+        assert_eq!(split.y_test.iter().filter(|v| **v == 1.0).count(), 2);
+        assert_eq!(split.y_test.len(), 5);
+
+        assert!(train_test_split(&x, &y, 1.0, 7, false).is_err());
+    }
+
+    #[test]
+    fn test_kfold() {
+        let folds: Vec<_> = KFold::new(3).split(7).unwrap().collect();
+        let tests: Vec<Vec<usize>> = folds.iter().map(|(_, test)| test.clone()).collect();
+        assert_eq!(tests, vec![vec![0, 1, 2], vec![3, 4], vec![5, 6]]);
+        assert_eq!(folds[1].0, vec![0, 1, 2, 5, 6]);
+
+        // shuffled folds still cover every sample once
+        let mut all: Vec<usize> = KFold::new(3)
+            .with_shuffle(1)
+            .split(7)
+            .unwrap()
+            .flat_map(|(_, test)| test)
+            .collect();
+        all.sort();
+        assert_eq!(all, (0..7).collect::<Vec<usize>>());
+
+        assert!(KFold::new(8).split(7).is_err());
+    }
+
+    #[test]
+    fn test_stratified_kfold() {
+        let y = [0.0, 0.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 1.0];
+        for (train, test) in StratifiedKFold::new(2).with_shuffle(3).split(&y).unwrap() {
+            assert_eq!(train.len(), 5);
+            assert_eq!(test.iter().filter(|i| y[**i] == 1.0).count(), 2);
+        }
+    }
+}
@@ -1,8 +1,8 @@
-use std::f64::consts;
-
 use crate::{
     error::CustomErrors,
     matrix::{operations::multiply_matrix_vector, Matrix},
+    special::sigmoid,
+    traits::{Fit, Predict, PredictProba},
     vector::{
         operations::{
             add_vec, mean, multiply_vec, scalar_add, scalar_multiply, sub_from_scalar, sub_vec, sum,
@@ -29,12 +29,12 @@ pub fn vector_log(vec: &Vec<f64>) -> Vec<f64> {
 }
 
 pub fn logistic(x: f64) -> f64 {
-    return 1.0 / (1.0 + consts::E.powf(-x));
+    sigmoid(x)
 }
 
 pub fn bce_loss(y_true: &Vec<f64>, y_pred: &Vec<f64>) -> Result<f64, CustomErrors> {
     let epsilon = 1e-9;
-    let e_y_pred = match scalar_add(y_true, epsilon) {
+    let e_y_pred = match scalar_add(y_pred, epsilon) {
         Ok(e_y_pred) => e_y_pred,
         Err(err) => return Err(err),
     };
@@ -47,11 +47,7 @@ pub fn bce_loss(y_true: &Vec<f64>, y_pred: &Vec<f64>) -> Result<f64, CustomError
     };
 
     let left2 = sub_from_scalar(1.0, y_true);
-    let ypred_ep = match scalar_add(y_pred, epsilon) {
-        Ok(ypred_e) => ypred_e,
-        Err(err) => return Err(err),
-    };
-    let right2 = sub_from_scalar(1.0, &ypred_ep);
+    let right2 = sub_from_scalar(1.0 + epsilon, y_pred);
     let log_right2 = vector_log(&right2);
 
     let y2 = match multiply_vec(&left2, &log_right2) {
@@ -72,6 +68,8 @@ pub fn bce_loss(y_true: &Vec<f64>, y_pred: &Vec<f64>) -> Result<f64, CustomError
     Ok(loss)
 }
 
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LogisticRegression {
     lr: f64,
     n_iterations: u32,
@@ -91,6 +89,16 @@ impl LogisticRegression {
         }
     }
 
+    pub fn with_learning_rate(mut self, lr: f64) -> LogisticRegression {
+        self.lr = lr;
+        self
+    }
+
+    pub fn with_iterations(mut self, n_iterations: u32) -> LogisticRegression {
+        self.n_iterations = n_iterations;
+        self
+    }
+
     pub fn fit(
         &mut self,
         features: Matrix<f64>,
@@ -189,12 +197,39 @@ impl LogisticRegression {
     }
 }
 
+impl Fit for LogisticRegression {
+    type Input = Matrix<f64>;
+    type Target = [f64];
+    /// Training loss after every iteration.
+    type Output = Vec<f64>;
 
-#[cfg(test)]
-mod tests {
-    use super::{logistic, vector_logistic};
+    fn fit(&mut self, x: &Matrix<f64>, y: &[f64]) -> Result<Vec<f64>, CustomErrors> {
+        LogisticRegression::fit(self, x.clone(), y.to_vec())
+    }
+}
 
+impl Predict for LogisticRegression {
+    type Input = Matrix<f64>;
+    type Output = Vec<f64>;
 
+    fn predict(&self, x: &Matrix<f64>) -> Result<Vec<f64>, CustomErrors> {
+        LogisticRegression::predict(self, x)
+    }
+}
+
+impl PredictProba for LogisticRegression {
+    type Input = Matrix<f64>;
+    /// Probability of the positive class.
+    type Output = Vec<f64>;
+
+    fn predict_proba(&self, x: &Matrix<f64>) -> Result<Vec<f64>, CustomErrors> {
+        self.predict_prob(x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{bce_loss, logistic, vector_logistic};
 
     #[test]
     fn test_logistic() {
@@ -203,27 +238,35 @@ mod tests {
         let assumed = 0.7310585786300049;
 
         let output = logistic(input);
-    
-        assert_eq!(assumed, output)
-
-
 
+        assert_eq!(assumed, output)
     }
 
     #[test]
     fn test_vector_logistic() {
-        let input: Vec<f64> = vec![
-            1.0, 2.0, 3.0
-        ];
+        let input: Vec<f64> = vec![1.0, 2.0, 3.0];
 
-        let assumed: Vec<f64> = vec![
-            0.7310585786300049,
-            0.8807970779778823,
-            0.9525741268224331,
-        ];
+        let assumed: Vec<f64> = vec![0.7310585786300049, 0.8807970779778823, 0.9525741268224331];
 
         let output = vector_logistic(&input);
 
-        assert_eq!(assumed,output)
+        assert_eq!(assumed.len(), output.len());
+        for (a, o) in assumed.iter().zip(&output) {
+            assert!((a - o).abs() < 1e-15);
+        }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_bce_loss() {
+        // -(ln 0.8 + ln 0.9) / 2
+        let loss = bce_loss(&vec![1.0, 0.0], &vec![0.8, 0.1]).unwrap();
+        assert!((loss - 0.164252033486018).abs() < 1e-8);
+        // -(ln 0.8 + ln 0.7 + ln 0.9) / 3, depends on y_pred not y_true
+        let loss = bce_loss(&vec![0.0, 1.0, 1.0], &vec![0.2, 0.7, 0.9]).unwrap();
+        assert!((loss - 0.22839300363692283).abs() < 1e-8);
+
+        // confident and right is close to zero, not NaN
+        let loss = bce_loss(&vec![1.0, 0.0], &vec![1.0, 0.0]).unwrap();
+        assert!(loss.abs() < 1e-8);
+    }
+}
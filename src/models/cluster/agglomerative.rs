@@ -0,0 +1,279 @@
+use crate::{
+    distance::{Distance, Euclidean},
+    error::{CustomErrors, EmptyVectorError, MismatchError},
+    matrix::Matrix,
+    traits::Fit,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Linkage {
+    Single,
+    Complete,
+    Average,
+    Ward,
+}
+
+/// One merge step. Leaves are numbered `0..n_samples`, the cluster created by
+/// merge `i` gets the id `n_samples + i` (the scipy linkage matrix convention).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Merge {
+    pub left: usize,
+    pub right: usize,
+    pub distance: f64,
+    pub size: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Dendrogram {
+    pub merges: Vec<Merge>,
+    pub n_samples: usize,
+}
+
+impl Dendrogram {
+    /// Flat labels obtained by cutting the tree so that `n_clusters` clusters remain.
+    /// Labels are numbered in order of first appearance.
+    pub fn labels(&self, n_clusters: usize) -> Result<Vec<usize>, CustomErrors> {
+        if n_clusters == 0 {
+            return Err(CustomErrors::EmptyVector(EmptyVectorError));
+        }
+        if n_clusters > self.n_samples {
+            return Err(CustomErrors::Mismatch(MismatchError));
+        }
+
+        let n = self.n_samples;
+        let mut parent: Vec<usize> = (0..(2 * n)).collect();
+        for (i, merge) in self.merges.iter().take(n - n_clusters).enumerate() {
+            parent[merge.left] = n + i;
+            parent[merge.right] = n + i;
+        }
+
+        let mut roots: Vec<usize> = vec![];
+        let mut labels = vec![];
+        for leaf in 0..n {
+            let mut root = leaf;
+            while parent[root] != root {
+                root = parent[root]
+            }
+
+            let label = match roots.iter().position(|&r| r == root) {
+                Some(pos) => pos,
+                None => {
+                    roots.push(root);
+                    roots.len() - 1
+                }
+            };
+            labels.push(label)
+        }
+
+        Ok(labels)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AgglomerativeResult {
+    pub labels: Vec<usize>,
+    pub dendrogram: Dendrogram,
+}
+
+/// Ward linkage is only meaningful with the default euclidean metric.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AgglomerativeClustering<D: Distance = Euclidean> {
+    n_clusters: usize,
+    linkage: Linkage,
+    metric: D,
+}
+
+impl AgglomerativeClustering {
+    pub fn new(n_clusters: usize, linkage: Linkage) -> AgglomerativeClustering {
+        AgglomerativeClustering {
+            n_clusters,
+            linkage,
+            metric: Euclidean,
+        }
+    }
+}
+
+impl<D: Distance> AgglomerativeClustering<D> {
+    pub fn with_metric<E: Distance>(self, metric: E) -> AgglomerativeClustering<E> {
+        AgglomerativeClustering {
+            n_clusters: self.n_clusters,
+            linkage: self.linkage,
+            metric,
+        }
+    }
+
+    pub fn fit(&self, data: &Matrix<f64>) -> Result<AgglomerativeResult, CustomErrors> {
+        let dendrogram = self.build_dendrogram(data)?;
+        let labels = dendrogram.labels(self.n_clusters)?;
+
+        Ok(AgglomerativeResult { labels, dendrogram })
+    }
+
+    /// Builds the full merge tree using Lance-Williams distance updates.
+    pub fn build_dendrogram(&self, data: &Matrix<f64>) -> Result<Dendrogram, CustomErrors> {
+        let n = data.m;
+        if n == 0 {
+            return Err(CustomErrors::EmptyVector(EmptyVectorError));
+        }
+
+        let mut dist: Vec<Vec<f64>> = vec![vec![0.0; n]; n];
+        for (i, p1) in data.rows.iter().enumerate() {
+            for (j, p2) in data.rows.iter().enumerate().skip(i + 1) {
+                let d = self.metric.distance(p1, p2)?;
+                dist[i][j] = d;
+                dist[j][i] = d;
+            }
+        }
+
+        // slot -> dendrogram id / cluster size, a merged cluster reuses the lower slot
+        let mut ids: Vec<usize> = (0..n).collect();
+        let mut sizes: Vec<usize> = vec![1; n];
+        let mut active: Vec<bool> = vec![true; n];
+        let mut merges: Vec<Merge> = vec![];
+
+        for step in 0..(n - 1) {
+            let mut best = (0, 0, f64::MAX);
+            for a in 0..n {
+                if !active[a] {
+                    continue;
+                }
+                for b in (a + 1)..n {
+                    if active[b] && dist[a][b] < best.2 {
+                        best = (a, b, dist[a][b])
+                    }
+                }
+            }
+            let (a, b, d_ab) = best;
+
+            for k in 0..n {
+                if !active[k] || k == a || k == b {
+                    continue;
+                }
+                let updated =
+                    self.lance_williams(dist[k][a], dist[k][b], d_ab, sizes[a], sizes[b], sizes[k]);
+                dist[k][a] = updated;
+                dist[a][k] = updated;
+            }
+
+            merges.push(Merge {
+                left: ids[a].min(ids[b]),
+                right: ids[a].max(ids[b]),
+                distance: d_ab,
+                size: sizes[a] + sizes[b],
+            });
+
+            ids[a] = n + step;
+            sizes[a] += sizes[b];
+            active[b] = false;
+        }
+
+        Ok(Dendrogram {
+            merges,
+            n_samples: n,
+        })
+    }
+
+    fn lance_williams(
+        &self,
+        d_ki: f64,
+        d_kj: f64,
+        d_ij: f64,
+        n_i: usize,
+        n_j: usize,
+        n_k: usize,
+    ) -> f64 {
+        let (n_i, n_j, n_k) = (n_i as f64, n_j as f64, n_k as f64);
+        match self.linkage {
+            Linkage::Single => d_ki.min(d_kj),
+            Linkage::Complete => d_ki.max(d_kj),
+            Linkage::Average => (n_i * d_ki + n_j * d_kj) / (n_i + n_j),
+            Linkage::Ward => {
+                let total = n_i + n_j + n_k;
+                let sq = ((n_k + n_i) * d_ki.powi(2) + (n_k + n_j) * d_kj.powi(2)
+                    - n_k * d_ij.powi(2))
+                    / total;
+                sq.max(0.0).sqrt()
+            }
+        }
+    }
+}
+
+impl<D: Distance> Fit for AgglomerativeClustering<D> {
+    type Input = Matrix<f64>;
+    type Target = ();
+    type Output = AgglomerativeResult;
+
+    fn fit(&mut self, x: &Matrix<f64>, _y: &()) -> Result<AgglomerativeResult, CustomErrors> {
+        AgglomerativeClustering::fit(self, x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{distance::Manhattan, matrix::Matrix};
+
+    use super::{AgglomerativeClustering, Linkage, Merge};
+
+    fn line() -> Matrix<f64> {
+        Matrix::new(vec![vec![0.0], vec![1.0], vec![5.0], vec![6.0], vec![20.0]]).unwrap()
+    }
+
+    #[test]
+    fn test_single_linkage() {
+        let model = AgglomerativeClustering::new(3, Linkage::Single);
+        let res = model.fit(&line()).unwrap();
+
+        assert_eq!(res.labels, vec![0, 0, 1, 1, 2]);
+        assert_eq!(res.dendrogram.merges.len(), 4);
+        assert_eq!(
+            res.dendrogram.merges[0],
+            Merge {
+                left: 0,
+                right: 1,
+                distance: 1.0,
+                size: 2
+            }
+        );
+        // {0, 1} and {5, 6} are 4 apart at their closest points
+        assert_eq!(res.dendrogram.merges[2].distance, 4.0);
+        assert_eq!(res.dendrogram.merges[3].size, 5);
+    }
+
+    #[test]
+    fn test_linkages_differ() {
+        let complete = AgglomerativeClustering::new(1, Linkage::Complete)
+            .build_dendrogram(&line())
+            .unwrap();
+        let average = AgglomerativeClustering::new(1, Linkage::Average)
+            .build_dendrogram(&line())
+            .unwrap();
+        let ward = AgglomerativeClustering::new(1, Linkage::Ward)
+            .build_dendrogram(&line())
+            .unwrap();
+
+        // {0, 1} to {5, 6}
+        assert_eq!(complete.merges[2].distance, 6.0);
+        assert_eq!(average.merges[2].distance, 5.0);
+        // sqrt(2 * n_a * n_b / (n_a + n_b)) * |centroid_a - centroid_b|
+        assert!((ward.merges[2].distance - 5.0 * 2.0_f64.sqrt()).abs() < 1e-12);
+
+        assert_eq!(complete.labels(2).unwrap(), vec![0, 0, 0, 0, 1]);
+    }
+
+    #[test]
+    fn test_agglomerative_metric() {
+        let data = Matrix::new(vec![vec![0.0, 0.0], vec![1.0, 1.0], vec![5.0, 5.0]]).unwrap();
+        let dendrogram = AgglomerativeClustering::new(1, Linkage::Single)
+            .with_metric(Manhattan)
+            .build_dendrogram(&data)
+            .unwrap();
+
+        assert_eq!(dendrogram.merges[0].distance, 2.0);
+        assert_eq!(dendrogram.merges[1].distance, 8.0);
+    }
+}
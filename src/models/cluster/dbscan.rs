@@ -0,0 +1,180 @@
+use std::collections::VecDeque;
+
+use crate::{
+    distance::{Distance, Euclidean},
+    error::{CustomErrors, EmptyVectorError},
+    matrix::Matrix,
+    models::neighbors::{Algorithm, NeighborIndex, NeighborSearch},
+    traits::Fit,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DbscanResult {
+    /// Cluster id of every sample, `None` marks noise.
+    pub labels: Vec<Option<usize>>,
+    pub core_samples: Vec<usize>,
+    pub n_clusters: usize,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DBSCAN<D: Distance + Clone = Euclidean> {
+    eps: f64,
+    min_samples: usize,
+    metric: D,
+    algorithm: Algorithm,
+}
+
+impl DBSCAN {
+    /// `min_samples` counts the point itself, as in the original paper.
+    pub fn new(eps: f64, min_samples: usize) -> DBSCAN {
+        DBSCAN {
+            eps,
+            min_samples,
+            metric: Euclidean,
+            algorithm: Algorithm::Brute,
+        }
+    }
+}
+
+impl<D: Distance + Clone> DBSCAN<D> {
+    pub fn with_metric<E: Distance + Clone>(self, metric: E) -> DBSCAN<E> {
+        DBSCAN {
+            eps: self.eps,
+            min_samples: self.min_samples,
+            metric,
+            algorithm: self.algorithm,
+        }
+    }
+
+    /// Index used for the eps-neighborhood queries.
+    pub fn with_algorithm(mut self, algorithm: Algorithm) -> DBSCAN<D> {
+        self.algorithm = algorithm;
+        self
+    }
+
+    pub fn fit(&self, data: &Matrix<f64>) -> Result<DbscanResult, CustomErrors> {
+        if data.m == 0 {
+            return Err(CustomErrors::EmptyVector(EmptyVectorError));
+        }
+
+        let index = NeighborSearch::new(data, self.metric.clone(), self.algorithm)?;
+        let mut neighborhoods: Vec<Vec<usize>> = vec![];
+        for pt in &data.rows {
+            let found = index.query_radius(pt, self.eps)?;
+            neighborhoods.push(found.iter().map(|n| n.index).collect());
+        }
+
+        let is_core: Vec<bool> = neighborhoods
+            .iter()
+            .map(|n| n.len() >= self.min_samples)
+            .collect();
+
+        let mut labels: Vec<Option<usize>> = vec![None; data.m];
+        let mut n_clusters = 0;
+
+        for i in 0..data.m {
+            if labels[i].is_some() || !is_core[i] {
+                continue;
+            }
+
+            labels[i] = Some(n_clusters);
+            let mut queue: VecDeque<usize> = VecDeque::from(vec![i]);
+
+            while let Some(p) = queue.pop_front() {
+                if !is_core[p] {
+                    continue;
+                }
+                for &q in &neighborhoods[p] {
+                    if labels[q].is_none() {
+                        labels[q] = Some(n_clusters);
+                        queue.push_back(q);
+                    }
+                }
+            }
+
+            n_clusters += 1;
+        }
+
+        let core_samples = (0..data.m).filter(|&i| is_core[i]).collect();
+
+        Ok(DbscanResult {
+            labels,
+            core_samples,
+            n_clusters,
+        })
+    }
+}
+
+impl<D: Distance + Clone> Fit for DBSCAN<D> {
+    type Input = Matrix<f64>;
+    type Target = ();
+    type Output = DbscanResult;
+
+    fn fit(&mut self, x: &Matrix<f64>, _y: &()) -> Result<DbscanResult, CustomErrors> {
+        DBSCAN::fit(self, x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{distance::Chebyshev, matrix::Matrix, models::neighbors::Algorithm};
+
+    use super::DBSCAN;
+
+    #[test]
+    fn test_dbscan() {
+        let data = Matrix::new(vec![
+            vec![0.0, 0.0],
+            vec![0.0, 0.1],
+            vec![0.1, 0.0],
+            vec![5.0, 5.0],
+            vec![5.1, 5.0],
+            vec![5.0, 5.1],
+            vec![20.0, -20.0],
+        ])
+        .unwrap();
+
+        let res = DBSCAN::new(0.5, 3).fit(&data).unwrap();
+        let tree_res = DBSCAN::new(0.5, 3)
+            .with_algorithm(Algorithm::KdTree)
+            .fit(&data)
+            .unwrap();
+        assert_eq!(res, tree_res);
+
+        assert_eq!(res.n_clusters, 2);
+        assert_eq!(
+            res.labels,
+            vec![Some(0), Some(0), Some(0), Some(1), Some(1), Some(1), None]
+        );
+        assert_eq!(res.core_samples, vec![0, 1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_dbscan_border_points() {
+        // a chain where only the middle point is dense enough to be a core sample
+        let data = Matrix::new(vec![vec![0.0], vec![1.0], vec![2.0], vec![10.0]]).unwrap();
+
+        let res = DBSCAN::new(1.0, 3).fit(&data).unwrap();
+
+        assert_eq!(res.core_samples, vec![1]);
+        assert_eq!(res.labels, vec![Some(0), Some(0), Some(0), None]);
+    }
+
+    #[test]
+    fn test_dbscan_metric() {
+        // the diagonal neighbours are sqrt(2) apart in euclidean terms, 1 in chebyshev
+        let data = Matrix::new(vec![vec![0.0, 0.0], vec![1.0, 1.0], vec![2.0, 2.0]]).unwrap();
+
+        let euclidean = DBSCAN::new(1.0, 2).fit(&data).unwrap();
+        assert_eq!(euclidean.n_clusters, 0);
+
+        let chebyshev = DBSCAN::new(1.0, 2)
+            .with_metric(Chebyshev)
+            .fit(&data)
+            .unwrap();
+        assert_eq!(chebyshev.n_clusters, 1);
+        assert_eq!(chebyshev.labels, vec![Some(0), Some(0), Some(0)]);
+    }
+}
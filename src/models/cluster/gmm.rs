@@ -0,0 +1,370 @@
+use std::f64::consts::PI;
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use crate::{
+    error::{CustomErrors, EmptyVectorError, MismatchError},
+    matrix::{
+        decomposition::{cholesky, solve_lower_triangular},
+        Matrix,
+    },
+    special::logsumexp,
+    traits::{Fit, Predict, PredictProba},
+};
+
+use super::{kpp_init, nearest_centroid};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CovarianceType {
+    Full,
+    Diagonal,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MixtureResult {
+    pub labels: Vec<usize>,
+    /// Mean per-sample log-likelihood of the data under the fitted mixture.
+    pub log_likelihood: f64,
+    pub n_iter: usize,
+    pub converged: bool,
+}
+
+/// Gaussian mixture fitted by expectation-maximization. Means are seeded with
+/// k-means++, the first M-step runs on the resulting hard assignment.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GaussianMixture {
+    n_components: usize,
+    covariance_type: CovarianceType,
+    max_iter: usize,
+    tol: f64,
+    reg_covar: f64,
+    seed: Option<u64>,
+    weights: Vec<f64>,
+    means: Vec<Vec<f64>>,
+    covariances: Vec<Matrix<f64>>,
+}
+
+impl GaussianMixture {
+    pub fn new(n_components: usize, covariance_type: CovarianceType) -> GaussianMixture {
+        GaussianMixture {
+            n_components,
+            covariance_type,
+            max_iter: 100,
+            tol: 1e-3,
+            reg_covar: 1e-6,
+            seed: None,
+            weights: vec![],
+            means: vec![],
+            covariances: vec![],
+        }
+    }
+
+    pub fn with_max_iter(mut self, max_iter: usize) -> GaussianMixture {
+        self.max_iter = max_iter;
+        self
+    }
+
+    /// Convergence threshold on the change of the mean log-likelihood.
+    pub fn with_tol(mut self, tol: f64) -> GaussianMixture {
+        self.tol = tol;
+        self
+    }
+
+    /// Added to the covariance diagonals to keep them positive definite.
+    pub fn with_reg_covar(mut self, reg_covar: f64) -> GaussianMixture {
+        self.reg_covar = reg_covar;
+        self
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> GaussianMixture {
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn weights(&self) -> &Vec<f64> {
+        &self.weights
+    }
+
+    pub fn means(&self) -> &Vec<Vec<f64>> {
+        &self.means
+    }
+
+    pub fn covariances(&self) -> &Vec<Matrix<f64>> {
+        &self.covariances
+    }
+
+    pub fn fit(&mut self, data: &Matrix<f64>) -> Result<MixtureResult, CustomErrors> {
+        if data.m == 0 || self.n_components == 0 {
+            return Err(CustomErrors::EmptyVector(EmptyVectorError));
+        }
+
+        let mut rng = match self.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+
+        let centers = kpp_init(data, self.n_components, &mut rng)?;
+        let mut resp: Vec<Vec<f64>> = vec![];
+        for pt in &data.rows {
+            let (idx, _) = nearest_centroid(pt, &centers)?;
+            let mut row = vec![0.0; self.n_components];
+            row[idx] = 1.0;
+            resp.push(row)
+        }
+        self.m_step(data, &resp)?;
+
+        let mut lower_bound = f64::NEG_INFINITY;
+        let mut converged = false;
+        let mut n_iter = 0;
+
+        while n_iter < self.max_iter {
+            n_iter += 1;
+
+            let (new_resp, new_bound) = self.e_step(data)?;
+            self.m_step(data, &new_resp)?;
+
+            let change = (new_bound - lower_bound).abs();
+            lower_bound = new_bound;
+            if change < self.tol {
+                converged = true;
+                break;
+            }
+        }
+
+        let (resp, log_likelihood) = self.e_step(data)?;
+        let labels = resp.iter().map(|r| argmax(r)).collect();
+
+        Ok(MixtureResult {
+            labels,
+            log_likelihood,
+            n_iter,
+            converged,
+        })
+    }
+
+    pub fn predict(&self, data: &Matrix<f64>) -> Result<Vec<usize>, CustomErrors> {
+        let proba = self.predict_proba(data)?;
+        Ok(proba.iter().map(|r| argmax(r)).collect())
+    }
+
+    /// Posterior probability of every component for every sample.
+    pub fn predict_proba(&self, data: &Matrix<f64>) -> Result<Vec<Vec<f64>>, CustomErrors> {
+        if self.means.is_empty() {
+            return Err(CustomErrors::EmptyVector(EmptyVectorError));
+        }
+        let (resp, _) = self.e_step(data)?;
+        Ok(resp)
+    }
+
+    /// Mean per-sample log-likelihood of `data`.
+    pub fn score(&self, data: &Matrix<f64>) -> Result<f64, CustomErrors> {
+        if self.means.is_empty() {
+            return Err(CustomErrors::EmptyVector(EmptyVectorError));
+        }
+        let (_, bound) = self.e_step(data)?;
+        Ok(bound)
+    }
+
+    fn e_step(&self, data: &Matrix<f64>) -> Result<(Vec<Vec<f64>>, f64), CustomErrors> {
+        let d = self.means[0].len();
+        if data.n != d {
+            return Err(CustomErrors::Mismatch(MismatchError));
+        }
+
+        let mut factors = vec![];
+        for cov in &self.covariances {
+            factors.push(cholesky(cov)?);
+        }
+
+        let mut resp = vec![];
+        let mut total = 0.0;
+        for pt in &data.rows {
+            let mut log_prob = vec![];
+            for (k, factor) in factors.iter().enumerate() {
+                let lp = log_gaussian(pt, &self.means[k], factor)?;
+                log_prob.push(self.weights[k].ln() + lp);
+            }
+
+            let norm = logsumexp(&log_prob);
+            total += norm;
+            resp.push(log_prob.iter().map(|lp| (lp - norm).exp()).collect());
+        }
+
+        Ok((resp, total / data.m as f64))
+    }
+
+    fn m_step(&mut self, data: &Matrix<f64>, resp: &[Vec<f64>]) -> Result<(), CustomErrors> {
+        let m = data.m;
+        let d = data.n;
+
+        let mut weights = vec![];
+        let mut means = vec![];
+        let mut covariances = vec![];
+
+        for k in 0..self.n_components {
+            // the small constant keeps a component that lost every sample from dividing by zero
+            let nk: f64 = resp.iter().map(|r| r[k]).sum::<f64>() + 10.0 * f64::EPSILON;
+
+            let mut mean = vec![0.0; d];
+            for (pt, r) in data.rows.iter().zip(resp.iter()) {
+                for j in 0..d {
+                    mean[j] += r[k] * pt[j];
+                }
+            }
+            for v in mean.iter_mut() {
+                *v /= nk;
+            }
+
+            let mut cov = vec![vec![0.0; d]; d];
+            for (pt, r) in data.rows.iter().zip(resp.iter()) {
+                for a in 0..d {
+                    let da = pt[a] - mean[a];
+                    match self.covariance_type {
+                        CovarianceType::Full => {
+                            for b in 0..d {
+                                cov[a][b] += r[k] * da * (pt[b] - mean[b]);
+                            }
+                        }
+                        CovarianceType::Diagonal => cov[a][a] += r[k] * da * da,
+                    }
+                }
+            }
+            for (a, row) in cov.iter_mut().enumerate() {
+                for v in row.iter_mut() {
+                    *v /= nk;
+                }
+                row[a] += self.reg_covar;
+            }
+
+            weights.push(nk / m as f64);
+            means.push(mean);
+            covariances.push(Matrix::new(cov)?);
+        }
+
+        self.weights = weights;
+        self.means = means;
+        self.covariances = covariances;
+        Ok(())
+    }
+}
+
+/// Log density of a multivariate normal given the Cholesky factor of its covariance.
+fn log_gaussian(x: &[f64], mean: &[f64], chol: &Matrix<f64>) -> Result<f64, CustomErrors> {
+    let d = mean.len();
+    let diff: Vec<f64> = x.iter().zip(mean.iter()).map(|(a, b)| a - b).collect();
+    let z = solve_lower_triangular(chol, &diff)?;
+    let maha: f64 = z.iter().map(|v| v * v).sum();
+
+    let mut log_det = 0.0;
+    for i in 0..d {
+        log_det += 2.0 * chol.rows[i][i].ln();
+    }
+
+    Ok(-0.5 * (d as f64 * (2.0 * PI).ln() + log_det + maha))
+}
+
+fn argmax(values: &[f64]) -> usize {
+    let mut idx = 0;
+    for (i, v) in values.iter().enumerate() {
+        if *v > values[idx] {
+            idx = i
+        }
+    }
+    idx
+}
+
+impl Fit for GaussianMixture {
+    type Input = Matrix<f64>;
+    type Target = ();
+    type Output = MixtureResult;
+
+    fn fit(&mut self, x: &Matrix<f64>, _y: &()) -> Result<MixtureResult, CustomErrors> {
+        GaussianMixture::fit(self, x)
+    }
+}
+
+impl Predict for GaussianMixture {
+    type Input = Matrix<f64>;
+    type Output = Vec<usize>;
+
+    fn predict(&self, x: &Matrix<f64>) -> Result<Vec<usize>, CustomErrors> {
+        GaussianMixture::predict(self, x)
+    }
+}
+
+impl PredictProba for GaussianMixture {
+    type Input = Matrix<f64>;
+    type Output = Vec<Vec<f64>>;
+
+    fn predict_proba(&self, x: &Matrix<f64>) -> Result<Vec<Vec<f64>>, CustomErrors> {
+        GaussianMixture::predict_proba(self, x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::matrix::Matrix;
+
+    use super::{CovarianceType, GaussianMixture};
+
+    fn two_groups() -> Matrix<f64> {
+        Matrix::new(vec![
+            vec![0.0, 0.1],
+            vec![0.2, -0.1],
+            vec![-0.1, 0.0],
+            vec![0.1, 0.2],
+            vec![-0.2, -0.2],
+            vec![5.0, 5.1],
+            vec![5.2, 4.9],
+            vec![4.9, 5.0],
+            vec![5.1, 5.2],
+            vec![4.8, 4.8],
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn test_gmm_full() {
+        let data = two_groups();
+        let mut gmm = GaussianMixture::new(2, CovarianceType::Full).with_seed(1);
+        let res = gmm.fit(&data).unwrap();
+
+        assert!(res.converged);
+        for i in 1..5 {
+            assert_eq!(res.labels[i], res.labels[0]);
+            assert_eq!(res.labels[i + 5], res.labels[5]);
+        }
+        assert_ne!(res.labels[0], res.labels[5]);
+
+        let low = res.labels[0];
+        assert!((gmm.weights()[low] - 0.5).abs() < 1e-6);
+        assert!(gmm.means()[low][0].abs() < 1e-6);
+        assert!((gmm.means()[1 - low][0] - 5.0).abs() < 1e-6);
+
+        let proba = gmm.predict_proba(&data).unwrap();
+        assert!((proba[0].iter().sum::<f64>() - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_gmm_diagonal() {
+        let data = two_groups();
+        let mut gmm = GaussianMixture::new(2, CovarianceType::Diagonal).with_seed(3);
+        let res = gmm.fit(&data).unwrap();
+
+        for cov in gmm.covariances() {
+            assert_eq!(cov.rows[0][1], 0.0);
+            assert_eq!(cov.rows[1][0], 0.0);
+        }
+
+        let new_points = Matrix::new(vec![vec![0.0, 0.0], vec![5.0, 5.0]]).unwrap();
+        let labels = gmm.predict(&new_points).unwrap();
+        assert_eq!(labels, vec![res.labels[0], res.labels[5]]);
+
+        let score = gmm.score(&data).unwrap();
+        assert!((score - res.log_likelihood).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,526 @@
+pub mod agglomerative;
+pub mod dbscan;
+pub mod gmm;
+
+use core::f64;
+use rand::prelude::*;
+use rand::rngs::StdRng;
+
+use crate::{
+    distance::{Distance, Euclidean},
+    error::{CustomErrors, EmptyVectorError, MismatchError},
+    matrix::Matrix,
+    traits::{Fit, Predict},
+    vector::operations::{add_vec, scalar_divide},
+};
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ClusterResult {
+    pub labels: Vec<usize>,
+    pub centroids: Vec<Vec<f64>>,
+    pub inertia: f64,
+    pub n_iter: usize,
+}
+
+/// Euclidean distance, kept for existing callers of the cluster module. See
+/// `crate::distance` for the other metrics.
+pub fn cartesian_distance(p1: &[f64], p2: &[f64]) -> Result<f64, CustomErrors> {
+    Euclidean.distance(p1, p2)
+}
+
+/// Squared distance from `pt` to its nearest centroid, along with that centroid's index.
+fn nearest_centroid(pt: &[f64], centroids: &[Vec<f64>]) -> Result<(usize, f64), CustomErrors> {
+    let mut min_dist = f64::MAX;
+    let mut min_idx: usize = 0;
+
+    for (c, centroid) in centroids.iter().enumerate() {
+        let dist = cartesian_distance(pt, centroid)?.powi(2);
+        if dist < min_dist {
+            min_dist = dist;
+            min_idx = c
+        }
+    }
+
+    Ok((min_idx, min_dist))
+}
+
+/// k-means++ seeding: the first centroid is drawn uniformly, every following one
+/// with probability proportional to its squared distance to the closest centroid
+/// chosen so far.
+pub fn kpp_init<R: Rng>(
+    data: &Matrix<f64>,
+    n_centroids: usize,
+    rng: &mut R,
+) -> Result<Vec<Vec<f64>>, CustomErrors> {
+    let rows = &data.rows;
+    let m = data.m;
+
+    if n_centroids == 0 || m == 0 {
+        return Err(CustomErrors::EmptyVector(EmptyVectorError));
+    }
+    if n_centroids > m {
+        return Err(CustomErrors::Mismatch(MismatchError));
+    }
+
+    let mut centroids: Vec<Vec<f64>> = vec![];
+    let y = rng.gen_range(0..m);
+    centroids.push(rows[y].to_owned());
+
+    for _ in 1..n_centroids {
+        let mut dists: Vec<f64> = vec![];
+        for pt in rows {
+            let (_, d) = nearest_centroid(pt, &centroids)?;
+            dists.push(d)
+        }
+
+        let total: f64 = dists.iter().sum();
+
+        // every point already coincides with a centroid, fall back to a uniform draw
+        if total <= 0.0 {
+            let idx = rng.gen_range(0..m);
+            centroids.push(rows[idx].to_owned());
+            continue;
+        }
+
+        let target = rng.gen::<f64>() * total;
+        let mut cumulative = 0.0;
+        let mut chosen = m - 1;
+        for (i, d) in dists.iter().enumerate() {
+            cumulative += d;
+            if cumulative > target {
+                chosen = i;
+                break;
+            }
+        }
+
+        centroids.push(rows[chosen].to_owned())
+    }
+
+    Ok(centroids)
+}
+
+pub fn init_clusters(n_centroids: i32) -> Vec<Vec<Vec<f64>>> {
+    let mut clusters: Vec<Vec<Vec<f64>>> = vec![];
+
+    for _ in 0..n_centroids {
+        clusters.push(vec![])
+    }
+
+    clusters
+}
+
+/// Mean of every cluster. An empty cluster has no mean and yields an `EmptyVector` error.
+pub fn new_centroids(clusters: &Vec<Vec<Vec<f64>>>) -> Result<Vec<Vec<f64>>, CustomErrors> {
+    let mut new_centroids: Vec<Vec<f64>> = vec![];
+    for c in clusters {
+        if c.is_empty() {
+            return Err(CustomErrors::EmptyVector(EmptyVectorError));
+        }
+
+        let mut new_centroid: Vec<f64> = c[0].to_vec();
+        for pt in c.iter().skip(1) {
+            new_centroid = add_vec(&new_centroid, pt)?;
+        }
+        new_centroid = scalar_divide(&new_centroid, c.len() as f64)?;
+
+        new_centroids.push(new_centroid);
+    }
+    Ok(new_centroids)
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KMeans {
+    n_centroids: usize,
+    max_iter: usize,
+    tol: f64,
+    n_init: usize,
+    seed: Option<u64>,
+    centroids: Vec<Vec<f64>>,
+}
+
+impl KMeans {
+    pub fn new(n_centroids: usize) -> KMeans {
+        KMeans {
+            n_centroids,
+            max_iter: 300,
+            tol: 1e-4,
+            n_init: 10,
+            seed: None,
+            centroids: vec![],
+        }
+    }
+
+    pub fn with_max_iter(mut self, max_iter: usize) -> KMeans {
+        self.max_iter = max_iter;
+        self
+    }
+
+    /// Convergence threshold on the summed squared movement of the centroids
+    /// between two iterations.
+    pub fn with_tol(mut self, tol: f64) -> KMeans {
+        self.tol = tol;
+        self
+    }
+
+    /// Number of k-means++ restarts, the run with the lowest inertia is kept.
+    pub fn with_n_init(mut self, n_init: usize) -> KMeans {
+        self.n_init = n_init;
+        self
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> KMeans {
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn centroids(&self) -> &Vec<Vec<f64>> {
+        &self.centroids
+    }
+
+    pub fn fit(&mut self, data: &Matrix<f64>) -> Result<ClusterResult, CustomErrors> {
+        if self.n_init == 0 {
+            return Err(CustomErrors::EmptyVector(EmptyVectorError));
+        }
+
+        let mut rng = match self.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+
+        let mut best: Option<ClusterResult> = None;
+        for _ in 0..self.n_init {
+            let res = self.single_run(data, &mut rng)?;
+            let better = match &best {
+                Some(b) => res.inertia < b.inertia,
+                None => true,
+            };
+            if better {
+                best = Some(res)
+            }
+        }
+
+        // n_init > 0 so at least one run was recorded
+        let best = best.unwrap();
+        self.centroids = best.centroids.clone();
+        Ok(best)
+    }
+
+    pub fn predict(&self, data: &Matrix<f64>) -> Result<Vec<usize>, CustomErrors> {
+        if self.centroids.is_empty() {
+            return Err(CustomErrors::EmptyVector(EmptyVectorError));
+        }
+
+        let mut labels = vec![];
+        for pt in &data.rows {
+            let (idx, _) = nearest_centroid(pt, &self.centroids)?;
+            labels.push(idx)
+        }
+        Ok(labels)
+    }
+
+    fn single_run(
+        &self,
+        data: &Matrix<f64>,
+        rng: &mut StdRng,
+    ) -> Result<ClusterResult, CustomErrors> {
+        let mut centroids = kpp_init(data, self.n_centroids, rng)?;
+        let mut labels: Vec<usize> = vec![0; data.m];
+        let mut dists: Vec<f64> = vec![0.0; data.m];
+        let mut n_iter = 0;
+
+        while n_iter < self.max_iter {
+            n_iter += 1;
+
+            for (i, pt) in data.rows.iter().enumerate() {
+                let (idx, d) = nearest_centroid(pt, &centroids)?;
+                labels[i] = idx;
+                dists[i] = d;
+            }
+
+            let updated = self.update_centroids(data, &labels, &mut dists)?;
+
+            let mut shift = 0.0;
+            for (old, new) in centroids.iter().zip(updated.iter()) {
+                shift += cartesian_distance(old, new)?.powi(2);
+            }
+            centroids = updated;
+
+            if shift <= self.tol {
+                break;
+            }
+        }
+
+        // final assignment against the converged centroids
+        let mut inertia = 0.0;
+        for (i, pt) in data.rows.iter().enumerate() {
+            let (idx, d) = nearest_centroid(pt, &centroids)?;
+            labels[i] = idx;
+            inertia += d;
+        }
+
+        Ok(ClusterResult {
+            labels,
+            centroids,
+            inertia,
+            n_iter,
+        })
+    }
+
+    /// Means of the current assignment. A centroid that lost all of its points is
+    /// moved onto the point that is currently worst served by its own centroid.
+    fn update_centroids(
+        &self,
+        data: &Matrix<f64>,
+        labels: &[usize],
+        dists: &mut [f64],
+    ) -> Result<Vec<Vec<f64>>, CustomErrors> {
+        let mut sums: Vec<Vec<f64>> = vec![vec![0.0; data.n]; self.n_centroids];
+        let mut counts: Vec<usize> = vec![0; self.n_centroids];
+
+        for (pt, &label) in data.rows.iter().zip(labels.iter()) {
+            sums[label] = add_vec(&sums[label], pt)?;
+            counts[label] += 1;
+        }
+
+        let mut centroids = vec![];
+        for c in 0..self.n_centroids {
+            if counts[c] > 0 {
+                centroids.push(scalar_divide(&sums[c], counts[c] as f64)?);
+                continue;
+            }
+
+            let mut far_idx = 0;
+            for i in 1..dists.len() {
+                if dists[i] > dists[far_idx] {
+                    far_idx = i
+                }
+            }
+            // claim the point so a second empty cluster picks a different one
+            dists[far_idx] = 0.0;
+            centroids.push(data.rows[far_idx].to_vec());
+        }
+
+        Ok(centroids)
+    }
+}
+
+pub fn kmeans(data: &Matrix<f64>, n_centroids: i32) -> Result<ClusterResult, CustomErrors> {
+    let n_centroids: usize = match n_centroids.try_into() {
+        Ok(n) => n,
+        Err(_) => return Err(CustomErrors::EmptyVector(EmptyVectorError)),
+    };
+
+    KMeans::new(n_centroids).fit(data)
+}
+
+impl Fit for KMeans {
+    type Input = Matrix<f64>;
+    type Target = ();
+    type Output = ClusterResult;
+
+    fn fit(&mut self, x: &Matrix<f64>, _y: &()) -> Result<ClusterResult, CustomErrors> {
+        KMeans::fit(self, x)
+    }
+}
+
+impl Predict for KMeans {
+    type Input = Matrix<f64>;
+    type Output = Vec<usize>;
+
+    fn predict(&self, x: &Matrix<f64>) -> Result<Vec<usize>, CustomErrors> {
+        KMeans::predict(self, x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use crate::data::generators::make_blobs;
+    use crate::error::{CustomErrors, EmptyVectorError, MismatchError};
+    use crate::matrix::Matrix;
+    use crate::metrics::adjusted_rand_score;
+    use crate::models::cluster::kmeans;
+
+    use super::{cartesian_distance, init_clusters, kpp_init, new_centroids, KMeans};
+
+    fn two_blobs() -> Matrix<f64> {
+        let rows: Vec<Vec<f64>> = vec![
+            vec![0.0, 0.0],
+            vec![0.0, 0.5],
+            vec![0.5, 0.0],
+            vec![4.0, 4.0],
+            vec![4.5, 4.0],
+            vec![4.0, 5.0],
+        ];
+        Matrix::new(rows).unwrap()
+    }
+
+    #[test]
+    fn test_kmeans() {
+        let target_centroids_a: Vec<Vec<f64>> = vec![
+            vec![0.16666666666666666, 0.16666666666666666],
+            vec![4.166666666666667, 4.333333333333333],
+        ];
+        let target_centroids_b = vec![
+            vec![4.166666666666667, 4.333333333333333],
+            vec![0.16666666666666666, 0.16666666666666666],
+        ];
+        let data = two_blobs();
+        let n_centroids = 2;
+        let res = kmeans(&data, n_centroids).unwrap();
+
+        assert_eq!(res.centroids.len(), 2);
+        if res.centroids[0][0] == 0.16666666666666666 {
+            assert_eq!(res.centroids, target_centroids_a)
+        } else {
+            assert_eq!(res.centroids, target_centroids_b)
+        }
+
+        assert_eq!(res.labels[0], res.labels[1]);
+        assert_eq!(res.labels[0], res.labels[2]);
+        assert_eq!(res.labels[3], res.labels[4]);
+        assert_ne!(res.labels[0], res.labels[3]);
+    }
+
+    #[test]
+    fn test_kmeans_seeded() {
+        let data = two_blobs();
+
+        let mut model_a = KMeans::new(2).with_seed(42).with_n_init(3);
+        let mut model_b = KMeans::new(2).with_seed(42).with_n_init(3);
+        let res_a = model_a.fit(&data).unwrap();
+        let res_b = model_b.fit(&data).unwrap();
+
+        assert_eq!(res_a, res_b);
+
+        // 12/36 for the blob around the origin, 30/36 for the other one
+        let expected_inertia = 7.0 / 6.0;
+        assert!((res_a.inertia - expected_inertia).abs() < 1e-9);
+
+        let new_points = Matrix::new(vec![vec![0.1, 0.1], vec![5.0, 5.0]]).unwrap();
+        let labels = model_a.predict(&new_points).unwrap();
+        assert_eq!(labels[0], res_a.labels[0]);
+        assert_eq!(labels[1], res_a.labels[3]);
+    }
+
+    #[test]
+    fn test_kmeans_blobs() {
+        let centers = vec![vec![0.0, 0.0], vec![8.0, 0.0], vec![0.0, 8.0]];
+        let (data, truth) = make_blobs(300, &centers, 1.0, 11).unwrap();
+        let res = KMeans::new(3)
+            .with_seed(5)
+            .with_n_init(3)
+            .fit(&data)
+            .unwrap();
+
+        let truth: Vec<usize> = truth.iter().map(|t| *t as usize).collect();
+        assert_eq!(adjusted_rand_score(&truth, &res.labels).unwrap(), 1.0);
+    }
+
+    #[test]
+    fn test_kmeans_duplicate_points() {
+        // more clusters than distinct points forces an empty cluster
+        let data = Matrix::new(vec![vec![1.0, 1.0], vec![1.0, 1.0], vec![1.0, 1.0]]).unwrap();
+        let res = KMeans::new(2).with_seed(7).fit(&data).unwrap();
+
+        assert_eq!(res.centroids.len(), 2);
+        assert_eq!(res.inertia, 0.0);
+        for c in res.centroids {
+            assert!(c.iter().all(|v| v.is_finite()))
+        }
+    }
+
+    #[test]
+    fn test_cartesian_distance() {
+        let p1 = vec![1.0, 0.0];
+        let p2 = vec![0.0, 1.0];
+
+        let mut d: f64 = 2.0;
+        d = d.powf(0.5);
+
+        let target_d = cartesian_distance(&p1, &p2).unwrap();
+
+        assert_eq!(target_d, d);
+
+        let p1 = vec![1.0];
+        let p2 = vec![0.0, 1.0];
+        match cartesian_distance(&p1, &p2) {
+            Ok(_) => 0.0,
+            Err(err) => {
+                assert_eq!(err, CustomErrors::Mismatch(MismatchError));
+                0.0
+            }
+        };
+    }
+    #[test]
+    fn test_kpp_init() {
+        let rows = vec![
+            vec![0.0, 0.0],
+            vec![-1.0, -1.0],
+            vec![2.0, 2.0],
+            vec![3.0, 3.0],
+        ];
+        let m = Matrix::new(rows).unwrap();
+        let n_centroids = 3;
+        let mut rng = StdRng::seed_from_u64(0);
+        let centroids = kpp_init(&m, n_centroids, &mut rng).unwrap();
+
+        let targ_len: usize = 3;
+
+        assert_eq!(centroids.len(), targ_len);
+
+        // a point already chosen has zero weight and can't be drawn again
+        for i in 0..centroids.len() {
+            for j in (i + 1)..centroids.len() {
+                assert_ne!(centroids[i], centroids[j])
+            }
+        }
+
+        let mut rng_a = StdRng::seed_from_u64(3);
+        let mut rng_b = StdRng::seed_from_u64(3);
+        assert_eq!(
+            kpp_init(&m, 2, &mut rng_a).unwrap(),
+            kpp_init(&m, 2, &mut rng_b).unwrap()
+        );
+
+        assert_eq!(
+            kpp_init(&m, 5, &mut rng).unwrap_err(),
+            CustomErrors::Mismatch(MismatchError)
+        );
+    }
+
+    #[test]
+    fn test_cluster_init() {
+        let n_centroids = 3;
+        let c = init_clusters(n_centroids);
+        let targ_size: usize = 3;
+        assert_eq!(targ_size, c.len())
+    }
+
+    #[test]
+    fn test_new_centroids() {
+        let clusters: Vec<Vec<Vec<f64>>> = vec![
+            vec![vec![1.0, 1.0], vec![2.0, 2.0]],
+            vec![vec![5.0, 5.0], vec![6.0, 6.0]],
+            vec![vec![-1.0, -1.0], vec![-2.0, -2.0]],
+        ];
+
+        let target_centroids: Vec<Vec<f64>> =
+            vec![vec![1.5, 1.5], vec![5.5, 5.5], vec![-1.5, -1.5]];
+
+        let centroids = new_centroids(&clusters).unwrap();
+
+        assert_eq!(target_centroids, centroids);
+
+        let clusters: Vec<Vec<Vec<f64>>> = vec![vec![vec![1.0, 1.0]], vec![]];
+        assert_eq!(
+            new_centroids(&clusters).unwrap_err(),
+            CustomErrors::EmptyVector(EmptyVectorError)
+        );
+    }
+}
@@ -0,0 +1,386 @@
+pub mod penalized;
+
+use crate::{
+    error::{BadTypeError, CustomErrors, EmptyVectorError, MismatchError, SingularMatrixError},
+    matrix::{
+        decomposition::{check_full_rank, qr, solve_upper_triangular, upper_triangular_inverse},
+        Matrix,
+    },
+    traits::{Fit, Predict},
+};
+
+/// Validates a design matrix against its targets and returns the sample weights,
+/// all ones when none were given.
+pub(crate) fn check_xy(
+    x: &Matrix<f64>,
+    y: &[f64],
+    sample_weight: Option<&[f64]>,
+) -> Result<Vec<f64>, CustomErrors> {
+    if x.m == 0 || x.n == 0 {
+        return Err(CustomErrors::EmptyVector(EmptyVectorError));
+    }
+    if x.m != y.len() {
+        return Err(CustomErrors::Mismatch(MismatchError));
+    }
+
+    match sample_weight {
+        None => Ok(vec![1.0; x.m]),
+        Some(w) => {
+            if w.len() != x.m {
+                return Err(CustomErrors::Mismatch(MismatchError));
+            }
+            if w.iter().any(|v| *v < 0.0 || !v.is_finite()) || w.iter().sum::<f64>() <= 0.0 {
+                return Err(CustomErrors::BadType(BadTypeError));
+            }
+            Ok(w.to_vec())
+        }
+    }
+}
+
+/// Weighted column means of `x` and weighted mean of `y`.
+pub(crate) fn weighted_means(x: &Matrix<f64>, y: &[f64], w: &[f64]) -> (Vec<f64>, f64) {
+    let total: f64 = w.iter().sum();
+    let mut x_mean = vec![0.0; x.n];
+    let mut y_mean = 0.0;
+    for ((row, yi), wi) in x.rows.iter().zip(y.iter()).zip(w.iter()) {
+        for (m, v) in x_mean.iter_mut().zip(row.iter()) {
+            *m += wi * v;
+        }
+        y_mean += wi * yi;
+    }
+    for m in x_mean.iter_mut() {
+        *m /= total;
+    }
+    (x_mean, y_mean / total)
+}
+
+pub(crate) fn predict_linear(
+    x: &Matrix<f64>,
+    coefficients: &[f64],
+    intercept: f64,
+) -> Result<Vec<f64>, CustomErrors> {
+    if coefficients.is_empty() {
+        return Err(CustomErrors::EmptyVector(EmptyVectorError));
+    }
+    if x.n != coefficients.len() {
+        return Err(CustomErrors::Mismatch(MismatchError));
+    }
+
+    Ok(x.rows
+        .iter()
+        .map(|row| {
+            intercept
+                + row
+                    .iter()
+                    .zip(coefficients.iter())
+                    .map(|(a, b)| a * b)
+                    .sum::<f64>()
+        })
+        .collect())
+}
+
+/// Weighted coefficient of determination, `1 - SS_res / SS_tot`.
+pub(crate) fn weighted_r_squared(y: &[f64], pred: &[f64], w: &[f64]) -> f64 {
+    let total: f64 = w.iter().sum();
+    let mean: f64 = y.iter().zip(w.iter()).map(|(a, b)| a * b).sum::<f64>() / total;
+
+    let mut ss_res = 0.0;
+    let mut ss_tot = 0.0;
+    for ((yi, pi), wi) in y.iter().zip(pred.iter()).zip(w.iter()) {
+        ss_res += wi * (yi - pi).powi(2);
+        ss_tot += wi * (yi - mean).powi(2);
+    }
+
+    if ss_tot == 0.0 {
+        // constant targets: perfect fit or nothing explained
+        return if ss_res == 0.0 { 1.0 } else { 0.0 };
+    }
+    1.0 - ss_res / ss_tot
+}
+
+/// Inference statistics of an ordinary least squares fit.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OlsSummary {
+    pub standard_errors: Vec<f64>,
+    pub t_stats: Vec<f64>,
+    pub intercept_standard_error: Option<f64>,
+    pub intercept_t_stat: Option<f64>,
+    pub r_squared: f64,
+    /// NaN when there are no residual degrees of freedom, like the standard
+    /// errors.
+    pub adjusted_r_squared: f64,
+    /// Residual degrees of freedom, `n_samples - n_parameters`.
+    pub df_residual: usize,
+    pub residual_std_error: f64,
+}
+
+/// Ordinary least squares solved through a QR decomposition of the (weighted)
+/// design matrix, which avoids forming `X^T X`.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LinearRegression {
+    fit_intercept: bool,
+    coefficients: Vec<f64>,
+    intercept: f64,
+    summary: Option<OlsSummary>,
+}
+
+impl Default for LinearRegression {
+    fn default() -> Self {
+        LinearRegression::new()
+    }
+}
+
+impl LinearRegression {
+    pub fn new() -> LinearRegression {
+        LinearRegression {
+            fit_intercept: true,
+            coefficients: vec![],
+            intercept: 0.0,
+            summary: None,
+        }
+    }
+
+    /// Whether an intercept column is added to the features, on by default.
+    pub fn with_intercept(mut self, fit_intercept: bool) -> LinearRegression {
+        self.fit_intercept = fit_intercept;
+        self
+    }
+
+    pub fn coefficients(&self) -> &Vec<f64> {
+        &self.coefficients
+    }
+
+    pub fn intercept(&self) -> f64 {
+        self.intercept
+    }
+
+    /// Standard errors, t statistics and R^2 of the last fit.
+    pub fn summary(&self) -> Option<&OlsSummary> {
+        self.summary.as_ref()
+    }
+
+    pub fn fit(&mut self, x: &Matrix<f64>, y: &[f64]) -> Result<(), CustomErrors> {
+        self.fit_weighted(x, y, None)
+    }
+
+    /// Weighted least squares, minimizes `sum(w_i * (y_i - x_i b)^2)`.
+    pub fn fit_weighted(
+        &mut self,
+        x: &Matrix<f64>,
+        y: &[f64],
+        sample_weight: Option<&[f64]>,
+    ) -> Result<(), CustomErrors> {
+        let w = check_xy(x, y, sample_weight)?;
+
+        let n_params = x.n + usize::from(self.fit_intercept);
+        if x.m < n_params {
+            return Err(CustomErrors::SingularMatrix(SingularMatrixError));
+        }
+
+        // scale every row by sqrt(w) to turn WLS into OLS
+        let mut design = vec![];
+        let mut target = vec![];
+        for ((row, yi), wi) in x.rows.iter().zip(y.iter()).zip(w.iter()) {
+            let sw = wi.sqrt();
+            let mut d_row = vec![];
+            if self.fit_intercept {
+                d_row.push(sw);
+            }
+            d_row.extend(row.iter().map(|v| v * sw));
+            design.push(d_row);
+            target.push(yi * sw);
+        }
+        let design = Matrix::new(design)?;
+
+        let (q, r) = qr(&design)?;
+        check_full_rank(&r)?;
+
+        let mut qty = vec![0.0; n_params];
+        for (row, ti) in q.rows.iter().zip(target.iter()) {
+            for (acc, qij) in qty.iter_mut().zip(row.iter()) {
+                *acc += qij * ti;
+            }
+        }
+        let mut beta = solve_upper_triangular(&r, &qty)?;
+
+        if self.fit_intercept {
+            self.intercept = beta.remove(0);
+        } else {
+            self.intercept = 0.0;
+        }
+        self.coefficients = beta;
+
+        // inference: Cov(b) = sigma^2 (X^T W X)^-1 = sigma^2 R^-1 R^-T
+        let pred = self.predict(x)?;
+        let rss: f64 = y
+            .iter()
+            .zip(pred.iter())
+            .zip(w.iter())
+            .map(|((yi, pi), wi)| wi * (yi - pi).powi(2))
+            .sum();
+        let df_residual = x.m - n_params;
+        let sigma2 = if df_residual > 0 {
+            rss / df_residual as f64
+        } else {
+            f64::NAN
+        };
+
+        let r_inv = upper_triangular_inverse(&r)?;
+        let mut errors: Vec<f64> = r_inv
+            .rows
+            .iter()
+            .map(|row| (sigma2 * row.iter().map(|v| v * v).sum::<f64>()).sqrt())
+            .collect();
+
+        let (intercept_standard_error, intercept_t_stat) = if self.fit_intercept {
+            let se = errors.remove(0);
+            (Some(se), Some(self.intercept / se))
+        } else {
+            (None, None)
+        };
+        let t_stats = self
+            .coefficients
+            .iter()
+            .zip(errors.iter())
+            .map(|(b, se)| b / se)
+            .collect();
+
+        let r_squared = weighted_r_squared(y, &pred, &w);
+        let dof_total = x.m as f64 - f64::from(u8::from(self.fit_intercept));
+        let adjusted_r_squared = if df_residual > 0 {
+            1.0 - (1.0 - r_squared) * dof_total / df_residual as f64
+        } else {
+            f64::NAN
+        };
+
+        self.summary = Some(OlsSummary {
+            standard_errors: errors,
+            t_stats,
+            intercept_standard_error,
+            intercept_t_stat,
+            r_squared,
+            adjusted_r_squared,
+            df_residual,
+            residual_std_error: sigma2.sqrt(),
+        });
+        Ok(())
+    }
+
+    pub fn predict(&self, x: &Matrix<f64>) -> Result<Vec<f64>, CustomErrors> {
+        predict_linear(x, &self.coefficients, self.intercept)
+    }
+
+    /// R^2 of the predictions for `x` against `y`.
+    pub fn score(&self, x: &Matrix<f64>, y: &[f64]) -> Result<f64, CustomErrors> {
+        let w = check_xy(x, y, None)?;
+        let pred = self.predict(x)?;
+        Ok(weighted_r_squared(y, &pred, &w))
+    }
+}
+
+impl Fit for LinearRegression {
+    type Input = Matrix<f64>;
+    type Target = [f64];
+    type Output = ();
+
+    fn fit(&mut self, x: &Matrix<f64>, y: &[f64]) -> Result<(), CustomErrors> {
+        LinearRegression::fit(self, x, y)
+    }
+}
+
+impl Predict for LinearRegression {
+    type Input = Matrix<f64>;
+    type Output = Vec<f64>;
+
+    fn predict(&self, x: &Matrix<f64>) -> Result<Vec<f64>, CustomErrors> {
+        LinearRegression::predict(self, x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        error::{CustomErrors, SingularMatrixError},
+        matrix::Matrix,
+    };
+
+    use super::LinearRegression;
+
+    #[test]
+    fn test_ols_intercept() {
+        let x = Matrix::new(vec![vec![1.0], vec![2.0], vec![3.0], vec![4.0]]).unwrap();
+        let y = vec![3.0, 5.0, 7.0, 9.0];
+
+        let mut model = LinearRegression::new();
+        model.fit(&x, &y).unwrap();
+
+        assert!((model.intercept() - 1.0).abs() < 1e-12);
+        assert!((model.coefficients()[0] - 2.0).abs() < 1e-12);
+        assert!((model.score(&x, &y).unwrap() - 1.0).abs() < 1e-12);
+
+        let pred = model
+            .predict(&Matrix::new(vec![vec![10.0]]).unwrap())
+            .unwrap();
+        assert!((pred[0] - 21.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_ols_summary() {
+        // reference values from statsmodels OLS on the same data
+        let x = Matrix::new(vec![vec![1.0], vec![2.0], vec![3.0], vec![4.0], vec![5.0]]).unwrap();
+        let y = vec![1.0, 3.0, 2.0, 5.0, 4.0];
+
+        let mut model = LinearRegression::new();
+        model.fit(&x, &y).unwrap();
+        let summary = model.summary().unwrap();
+
+        assert!((model.coefficients()[0] - 0.8).abs() < 1e-12);
+        assert!((model.intercept() - 0.6).abs() < 1e-12);
+        assert_eq!(summary.df_residual, 3);
+        assert!((summary.r_squared - 0.64).abs() < 1e-12);
+        assert!((summary.adjusted_r_squared - 0.52).abs() < 1e-12);
+        // sigma^2 = 3.6 / 3, se(b) = sqrt(1.2 / 10), se(a) = sqrt(1.2 * (1/5 + 9/10))
+        assert!((summary.standard_errors[0] - 0.12_f64.sqrt()).abs() < 1e-12);
+        assert!((summary.intercept_standard_error.unwrap() - 1.32_f64.sqrt()).abs() < 1e-12);
+        assert!((summary.t_stats[0] - 0.8 / 0.12_f64.sqrt()).abs() < 1e-10);
+
+        // two points and two parameters fit exactly, nothing is left to estimate with
+        let x = Matrix::new(vec![vec![1.0], vec![2.0]]).unwrap();
+        let mut model = LinearRegression::new();
+        model.fit(&x, &[1.0, 3.0]).unwrap();
+        let summary = model.summary().unwrap();
+        assert_eq!(summary.df_residual, 0);
+        assert!(summary.adjusted_r_squared.is_nan());
+        assert!(summary.residual_std_error.is_nan());
+    }
+
+    #[test]
+    fn test_ols_weighted() {
+        // zero weight drops the outlier entirely
+        let x = Matrix::new(vec![vec![0.0], vec![1.0], vec![2.0], vec![3.0]]).unwrap();
+        let y = vec![1.0, 2.0, 3.0, 100.0];
+
+        let mut model = LinearRegression::new();
+        model
+            .fit_weighted(&x, &y, Some(&[1.0, 1.0, 1.0, 0.0]))
+            .unwrap();
+        assert!((model.intercept() - 1.0).abs() < 1e-12);
+        assert!((model.coefficients()[0] - 1.0).abs() < 1e-12);
+
+        assert!(model
+            .fit_weighted(&x, &y, Some(&[1.0, -1.0, 1.0, 1.0]))
+            .is_err());
+    }
+
+    #[test]
+    fn test_ols_collinear() {
+        let x = Matrix::new(vec![vec![1.0, 2.0], vec![2.0, 4.0], vec![3.0, 6.0]]).unwrap();
+        let mut model = LinearRegression::new();
+        assert_eq!(
+            model.fit(&x, &[1.0, 2.0, 3.0]).unwrap_err(),
+            CustomErrors::SingularMatrix(SingularMatrixError)
+        );
+    }
+}
@@ -0,0 +1,469 @@
+use crate::{
+    error::{BadTypeError, CustomErrors},
+    matrix::{decomposition::solve_least_squares, Matrix},
+    traits::{Fit, Predict},
+};
+
+use super::{check_xy, predict_linear, weighted_means, weighted_r_squared};
+
+/// Centers `x` and `y` around their weighted means when an intercept is fitted,
+/// so the intercept drops out of the penalized problem.
+fn center(
+    x: &Matrix<f64>,
+    y: &[f64],
+    w: &[f64],
+    fit_intercept: bool,
+) -> (Vec<Vec<f64>>, Vec<f64>, Vec<f64>, f64) {
+    let (x_mean, y_mean) = if fit_intercept {
+        weighted_means(x, y, w)
+    } else {
+        (vec![0.0; x.n], 0.0)
+    };
+
+    let xc = x
+        .rows
+        .iter()
+        .map(|row| row.iter().zip(x_mean.iter()).map(|(v, m)| v - m).collect())
+        .collect();
+    let yc = y.iter().map(|v| v - y_mean).collect();
+    (xc, yc, x_mean, y_mean)
+}
+
+fn intercept_from_means(x_mean: &[f64], y_mean: f64, coefficients: &[f64]) -> f64 {
+    y_mean
+        - x_mean
+            .iter()
+            .zip(coefficients.iter())
+            .map(|(m, b)| m * b)
+            .sum::<f64>()
+}
+
+/// L2 penalized least squares, minimizes `sum(w_i * r_i^2) + alpha * ||b||^2`.
+/// The intercept is not penalized.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Ridge {
+    alpha: f64,
+    fit_intercept: bool,
+    coefficients: Vec<f64>,
+    intercept: f64,
+}
+
+impl Ridge {
+    pub fn new(alpha: f64) -> Ridge {
+        Ridge {
+            alpha,
+            fit_intercept: true,
+            coefficients: vec![],
+            intercept: 0.0,
+        }
+    }
+
+    pub fn with_intercept(mut self, fit_intercept: bool) -> Ridge {
+        self.fit_intercept = fit_intercept;
+        self
+    }
+
+    pub fn coefficients(&self) -> &Vec<f64> {
+        &self.coefficients
+    }
+
+    pub fn intercept(&self) -> f64 {
+        self.intercept
+    }
+
+    pub fn fit(&mut self, x: &Matrix<f64>, y: &[f64]) -> Result<(), CustomErrors> {
+        self.fit_weighted(x, y, None)
+    }
+
+    pub fn fit_weighted(
+        &mut self,
+        x: &Matrix<f64>,
+        y: &[f64],
+        sample_weight: Option<&[f64]>,
+    ) -> Result<(), CustomErrors> {
+        if self.alpha.is_nan() || self.alpha < 0.0 {
+            return Err(CustomErrors::BadType(BadTypeError));
+        }
+        let w = check_xy(x, y, sample_weight)?;
+        let (xc, yc, x_mean, y_mean) = center(x, y, &w, self.fit_intercept);
+
+        // the penalty becomes sqrt(alpha) * I rows appended below the data
+        let mut design = vec![];
+        let mut target = vec![];
+        for ((row, yi), wi) in xc.iter().zip(yc.iter()).zip(w.iter()) {
+            let sw = wi.sqrt();
+            design.push(row.iter().map(|v| v * sw).collect::<Vec<f64>>());
+            target.push(yi * sw);
+        }
+        for j in 0..x.n {
+            let mut row = vec![0.0; x.n];
+            row[j] = self.alpha.sqrt();
+            design.push(row);
+            target.push(0.0);
+        }
+
+        self.coefficients = solve_least_squares(&Matrix::new(design)?, &target)?;
+        self.intercept = intercept_from_means(&x_mean, y_mean, &self.coefficients);
+        Ok(())
+    }
+
+    pub fn predict(&self, x: &Matrix<f64>) -> Result<Vec<f64>, CustomErrors> {
+        predict_linear(x, &self.coefficients, self.intercept)
+    }
+
+    pub fn score(&self, x: &Matrix<f64>, y: &[f64]) -> Result<f64, CustomErrors> {
+        let w = check_xy(x, y, None)?;
+        Ok(weighted_r_squared(y, &self.predict(x)?, &w))
+    }
+}
+
+/// Mixed L1/L2 penalty fitted by cyclic coordinate descent. The objective is
+///
+/// `1 / (2 * sum(w)) * sum(w_i * r_i^2) + alpha * l1_ratio * ||b||_1
+///     + alpha * (1 - l1_ratio) / 2 * ||b||^2`
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ElasticNet {
+    alpha: f64,
+    l1_ratio: f64,
+    fit_intercept: bool,
+    max_iter: usize,
+    tol: f64,
+    coefficients: Vec<f64>,
+    intercept: f64,
+    n_iter: usize,
+}
+
+impl ElasticNet {
+    pub fn new(alpha: f64, l1_ratio: f64) -> ElasticNet {
+        ElasticNet {
+            alpha,
+            l1_ratio,
+            fit_intercept: true,
+            max_iter: 1000,
+            tol: 1e-4,
+            coefficients: vec![],
+            intercept: 0.0,
+            n_iter: 0,
+        }
+    }
+
+    pub fn with_intercept(mut self, fit_intercept: bool) -> ElasticNet {
+        self.fit_intercept = fit_intercept;
+        self
+    }
+
+    pub fn with_max_iter(mut self, max_iter: usize) -> ElasticNet {
+        self.max_iter = max_iter;
+        self
+    }
+
+    /// Stop once no coefficient moves by more than `tol` in a full sweep.
+    pub fn with_tol(mut self, tol: f64) -> ElasticNet {
+        self.tol = tol;
+        self
+    }
+
+    pub fn coefficients(&self) -> &Vec<f64> {
+        &self.coefficients
+    }
+
+    pub fn intercept(&self) -> f64 {
+        self.intercept
+    }
+
+    /// Number of sweeps run by the last fit.
+    pub fn n_iter(&self) -> usize {
+        self.n_iter
+    }
+
+    pub fn fit(&mut self, x: &Matrix<f64>, y: &[f64]) -> Result<(), CustomErrors> {
+        self.fit_weighted(x, y, None)
+    }
+
+    pub fn fit_weighted(
+        &mut self,
+        x: &Matrix<f64>,
+        y: &[f64],
+        sample_weight: Option<&[f64]>,
+    ) -> Result<(), CustomErrors> {
+        if self.alpha.is_nan()
+            || self.alpha < 0.0
+            || self.l1_ratio.is_nan()
+            || !(0.0..=1.0).contains(&self.l1_ratio)
+        {
+            return Err(CustomErrors::BadType(BadTypeError));
+        }
+        let w = check_xy(x, y, sample_weight)?;
+        let total: f64 = w.iter().sum();
+        let (xc, yc, x_mean, y_mean) = center(x, y, &w, self.fit_intercept);
+
+        let l1 = self.alpha * self.l1_ratio;
+        let l2 = self.alpha * (1.0 - self.l1_ratio);
+
+        // weighted squared norm of each column, scaled like the loss
+        let mut col_norm = vec![0.0; x.n];
+        for (row, wi) in xc.iter().zip(w.iter()) {
+            for (c, v) in col_norm.iter_mut().zip(row.iter()) {
+                *c += wi * v * v / total;
+            }
+        }
+
+        let mut beta = vec![0.0; x.n];
+        let mut residual = yc;
+        self.n_iter = 0;
+
+        for _ in 0..self.max_iter {
+            self.n_iter += 1;
+            let mut max_change: f64 = 0.0;
+
+            for j in 0..x.n {
+                if col_norm[j] == 0.0 {
+                    continue;
+                }
+                let old = beta[j];
+
+                // correlation of column j with the partial residual
+                let mut rho = 0.0;
+                for ((row, r), wi) in xc.iter().zip(residual.iter()).zip(w.iter()) {
+                    rho += wi * row[j] * (r + row[j] * old);
+                }
+                rho /= total;
+
+                let new = soft_threshold(rho, l1) / (col_norm[j] + l2);
+                if new != old {
+                    for (row, r) in xc.iter().zip(residual.iter_mut()) {
+                        *r -= row[j] * (new - old);
+                    }
+                    beta[j] = new;
+                }
+                max_change = max_change.max((new - old).abs());
+            }
+
+            if max_change <= self.tol {
+                break;
+            }
+        }
+
+        self.intercept = intercept_from_means(&x_mean, y_mean, &beta);
+        self.coefficients = beta;
+        Ok(())
+    }
+
+    pub fn predict(&self, x: &Matrix<f64>) -> Result<Vec<f64>, CustomErrors> {
+        predict_linear(x, &self.coefficients, self.intercept)
+    }
+
+    pub fn score(&self, x: &Matrix<f64>, y: &[f64]) -> Result<f64, CustomErrors> {
+        let w = check_xy(x, y, None)?;
+        Ok(weighted_r_squared(y, &self.predict(x)?, &w))
+    }
+}
+
+fn soft_threshold(value: f64, threshold: f64) -> f64 {
+    if value > threshold {
+        value - threshold
+    } else if value < -threshold {
+        value + threshold
+    } else {
+        0.0
+    }
+}
+
+/// L1 penalized least squares, an `ElasticNet` with `l1_ratio = 1`.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Lasso {
+    inner: ElasticNet,
+}
+
+impl Lasso {
+    pub fn new(alpha: f64) -> Lasso {
+        Lasso {
+            inner: ElasticNet::new(alpha, 1.0),
+        }
+    }
+
+    pub fn with_intercept(self, fit_intercept: bool) -> Lasso {
+        Lasso {
+            inner: self.inner.with_intercept(fit_intercept),
+        }
+    }
+
+    pub fn with_max_iter(self, max_iter: usize) -> Lasso {
+        Lasso {
+            inner: self.inner.with_max_iter(max_iter),
+        }
+    }
+
+    pub fn with_tol(self, tol: f64) -> Lasso {
+        Lasso {
+            inner: self.inner.with_tol(tol),
+        }
+    }
+
+    pub fn coefficients(&self) -> &Vec<f64> {
+        self.inner.coefficients()
+    }
+
+    pub fn intercept(&self) -> f64 {
+        self.inner.intercept()
+    }
+
+    pub fn n_iter(&self) -> usize {
+        self.inner.n_iter()
+    }
+
+    pub fn fit(&mut self, x: &Matrix<f64>, y: &[f64]) -> Result<(), CustomErrors> {
+        self.inner.fit(x, y)
+    }
+
+    pub fn fit_weighted(
+        &mut self,
+        x: &Matrix<f64>,
+        y: &[f64],
+        sample_weight: Option<&[f64]>,
+    ) -> Result<(), CustomErrors> {
+        self.inner.fit_weighted(x, y, sample_weight)
+    }
+
+    pub fn predict(&self, x: &Matrix<f64>) -> Result<Vec<f64>, CustomErrors> {
+        self.inner.predict(x)
+    }
+
+    pub fn score(&self, x: &Matrix<f64>, y: &[f64]) -> Result<f64, CustomErrors> {
+        self.inner.score(x, y)
+    }
+}
+
+impl Fit for Ridge {
+    type Input = Matrix<f64>;
+    type Target = [f64];
+    type Output = ();
+
+    fn fit(&mut self, x: &Matrix<f64>, y: &[f64]) -> Result<(), CustomErrors> {
+        Ridge::fit(self, x, y)
+    }
+}
+
+impl Predict for Ridge {
+    type Input = Matrix<f64>;
+    type Output = Vec<f64>;
+
+    fn predict(&self, x: &Matrix<f64>) -> Result<Vec<f64>, CustomErrors> {
+        Ridge::predict(self, x)
+    }
+}
+
+impl Fit for ElasticNet {
+    type Input = Matrix<f64>;
+    type Target = [f64];
+    type Output = ();
+
+    fn fit(&mut self, x: &Matrix<f64>, y: &[f64]) -> Result<(), CustomErrors> {
+        ElasticNet::fit(self, x, y)
+    }
+}
+
+impl Predict for ElasticNet {
+    type Input = Matrix<f64>;
+    type Output = Vec<f64>;
+
+    fn predict(&self, x: &Matrix<f64>) -> Result<Vec<f64>, CustomErrors> {
+        ElasticNet::predict(self, x)
+    }
+}
+
+impl Fit for Lasso {
+    type Input = Matrix<f64>;
+    type Target = [f64];
+    type Output = ();
+
+    fn fit(&mut self, x: &Matrix<f64>, y: &[f64]) -> Result<(), CustomErrors> {
+        Lasso::fit(self, x, y)
+    }
+}
+
+impl Predict for Lasso {
+    type Input = Matrix<f64>;
+    type Output = Vec<f64>;
+
+    fn predict(&self, x: &Matrix<f64>) -> Result<Vec<f64>, CustomErrors> {
+        Lasso::predict(self, x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{matrix::Matrix, models::linear::LinearRegression};
+
+    use super::{ElasticNet, Lasso, Ridge};
+
+    fn data() -> (Matrix<f64>, Vec<f64>) {
+        // y = 1 + 2 * x0 + 0 * x1, x1 is pure noise
+        let rows = vec![
+            vec![0.0, 0.3],
+            vec![1.0, -0.2],
+            vec![2.0, 0.5],
+            vec![3.0, -0.4],
+            vec![4.0, 0.1],
+            vec![5.0, 0.0],
+        ];
+        let y = rows.iter().map(|r| 1.0 + 2.0 * r[0]).collect();
+        (Matrix::new(rows).unwrap(), y)
+    }
+
+    #[test]
+    fn test_ridge() {
+        let (x, y) = data();
+
+        // no penalty is plain least squares
+        let mut ridge = Ridge::new(0.0);
+        ridge.fit(&x, &y).unwrap();
+        let mut ols = LinearRegression::new();
+        ols.fit(&x, &y).unwrap();
+        for (a, b) in ridge.coefficients().iter().zip(ols.coefficients().iter()) {
+            assert!((a - b).abs() < 1e-10);
+        }
+
+        // single centered feature: b = sxy / (sxx + alpha)
+        let x = Matrix::new(vec![vec![-1.0], vec![0.0], vec![1.0]]).unwrap();
+        let mut ridge = Ridge::new(2.0);
+        ridge.fit(&x, &[1.0, 2.0, 3.0]).unwrap();
+        assert!((ridge.coefficients()[0] - 0.5).abs() < 1e-12);
+        assert!((ridge.intercept() - 2.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_lasso_sparsity() {
+        let (x, y) = data();
+
+        let mut lasso = Lasso::new(0.1).with_tol(1e-10);
+        lasso.fit(&x, &y).unwrap();
+        assert_eq!(lasso.coefficients()[1], 0.0);
+        assert!(lasso.coefficients()[0] > 1.9 && lasso.coefficients()[0] < 2.0);
+        assert!(lasso.score(&x, &y).unwrap() > 0.99);
+
+        // a large enough penalty zeroes everything, the intercept is the mean
+        let mut lasso = Lasso::new(100.0);
+        lasso.fit(&x, &y).unwrap();
+        assert_eq!(lasso.coefficients(), &vec![0.0, 0.0]);
+        assert!((lasso.intercept() - 6.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_elastic_net() {
+        // single centered feature: b = S(sxy / n, l1) / (sxx / n + l2)
+        let x = Matrix::new(vec![vec![-1.0], vec![0.0], vec![1.0]]).unwrap();
+        let y = [1.0, 2.0, 3.0];
+
+        let mut model = ElasticNet::new(0.3, 0.5).with_tol(1e-12);
+        model.fit(&x, &y).unwrap();
+        let expected = (2.0 / 3.0 - 0.15) / (2.0 / 3.0 + 0.15);
+        assert!((model.coefficients()[0] - expected).abs() < 1e-12);
+        assert!((model.intercept() - 2.0).abs() < 1e-12);
+
+        assert!(ElasticNet::new(0.3, 1.5).fit(&x, &y).is_err());
+    }
+}
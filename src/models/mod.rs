@@ -1,17 +1,23 @@
 pub mod classifier;
 pub mod cluster;
-use crate::matrix::{inverse::cramer_inverse, operations::multiply_matrices, Matrix};
-
-pub fn linear_regression(x: &Matrix<f64>, y: &Matrix<f64>) -> Matrix<f64> {
-    let xt = x.transpose();
-
-    let left = multiply_matrices(&xt, &x).unwrap();
-    let left = cramer_inverse(&left).unwrap();
-    let right = multiply_matrices(&xt, &y).unwrap();
-
-    let beta = multiply_matrices(&left, &right).unwrap();
+pub mod linear;
+pub mod neighbors;
+pub mod pipeline;
+use crate::{error::CustomErrors, matrix::Matrix};
+
+use self::linear::LinearRegression;
+
+/// Least squares coefficients for every column of `y`. No intercept is added,
+/// include a column of ones in `x` for one. See `linear::LinearRegression`.
+pub fn linear_regression(x: &Matrix<f64>, y: &Matrix<f64>) -> Result<Matrix<f64>, CustomErrors> {
+    let mut columns = vec![];
+    for target in y.transpose().rows {
+        let mut model = LinearRegression::new().with_intercept(false);
+        model.fit(x, &target)?;
+        columns.push(model.coefficients().clone());
+    }
 
-    beta
+    Ok(Matrix::new(columns)?.transpose())
 }
 
 #[cfg(test)]
@@ -32,10 +38,13 @@ mod tests {
         let features = Matrix::new(x).unwrap();
         let targets = Matrix::new(y).unwrap();
 
-        let pred_betas = linear_regression(&features, &targets);
+        let pred_betas = linear_regression(&features, &targets).unwrap();
         println!("{:?}", pred_betas);
         println!("{:?}", target_betas);
 
-        assert_eq!(target_betas, pred_betas)
+        assert_eq!((pred_betas.m, pred_betas.n), (2, 1));
+        for (a, b) in target_betas.rows.iter().zip(pred_betas.rows.iter()) {
+            assert!((a[0] - b[0]).abs() < 1e-12)
+        }
     }
 }
@@ -0,0 +1,229 @@
+use crate::{
+    distance::Distance,
+    error::{CustomErrors, EmptyVectorError, NotImplementedError},
+    matrix::Matrix,
+};
+
+use super::{check_query, KBest, Neighbor, NeighborIndex};
+
+const LEAF_SIZE: usize = 10;
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct BallNode {
+    center: Vec<f64>,
+    radius: f64,
+    kind: BallKind,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+enum BallKind {
+    Leaf { indices: Vec<usize> },
+    Split { left: usize, right: usize },
+}
+
+/// Tree of nested hyperspheres. Pruning relies only on the triangle inequality,
+/// so any true metric works. Distances that aren't metrics (`SquaredEuclidean`,
+/// `Cosine`) are rejected.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BallTree<D: Distance> {
+    points: Vec<Vec<f64>>,
+    metric: D,
+    nodes: Vec<BallNode>,
+    root: usize,
+}
+
+impl<D: Distance> BallTree<D> {
+    pub fn new(data: &Matrix<f64>, metric: D) -> Result<BallTree<D>, CustomErrors> {
+        if !metric.is_metric() {
+            return Err(CustomErrors::NotImplemented(NotImplementedError));
+        }
+        if data.m == 0 {
+            return Err(CustomErrors::EmptyVector(EmptyVectorError));
+        }
+
+        let mut tree = BallTree {
+            points: data.rows.clone(),
+            metric,
+            nodes: vec![],
+            root: 0,
+        };
+        let indices: Vec<usize> = (0..data.m).collect();
+        tree.root = tree.build(indices)?;
+        Ok(tree)
+    }
+
+    fn build(&mut self, mut indices: Vec<usize>) -> Result<usize, CustomErrors> {
+        let dims = self.points[indices[0]].len();
+        let mut center = vec![0.0; dims];
+        for &i in &indices {
+            for (c, v) in center.iter_mut().zip(self.points[i].iter()) {
+                *c += v;
+            }
+        }
+        for c in center.iter_mut() {
+            *c /= indices.len() as f64;
+        }
+
+        let mut radius: f64 = 0.0;
+        for &i in &indices {
+            radius = radius.max(self.metric.distance(&center, &self.points[i])?);
+        }
+
+        // split along the coordinate with the largest spread
+        let mut axis = 0;
+        let mut spread = 0.0;
+        for a in 0..dims {
+            let mut lo = f64::INFINITY;
+            let mut hi = f64::NEG_INFINITY;
+            for &i in &indices {
+                lo = lo.min(self.points[i][a]);
+                hi = hi.max(self.points[i][a]);
+            }
+            if hi - lo > spread {
+                axis = a;
+                spread = hi - lo;
+            }
+        }
+
+        let kind = if indices.len() <= LEAF_SIZE || spread == 0.0 {
+            BallKind::Leaf { indices }
+        } else {
+            indices.sort_by(|&a, &b| self.points[a][axis].total_cmp(&self.points[b][axis]));
+            let right_indices = indices.split_off(indices.len() / 2);
+            let left = self.build(indices)?;
+            let right = self.build(right_indices)?;
+            BallKind::Split { left, right }
+        };
+
+        self.nodes.push(BallNode {
+            center,
+            radius,
+            kind,
+        });
+        Ok(self.nodes.len() - 1)
+    }
+
+    /// Smallest possible distance from `point` to anything inside the node.
+    fn min_distance(&self, node: usize, point: &[f64]) -> Result<f64, CustomErrors> {
+        let n = &self.nodes[node];
+        Ok((self.metric.distance(point, &n.center)? - n.radius).max(0.0))
+    }
+
+    fn search_k(&self, node: usize, point: &[f64], best: &mut KBest) -> Result<(), CustomErrors> {
+        match &self.nodes[node].kind {
+            BallKind::Leaf { indices } => {
+                for &index in indices {
+                    let distance = self.metric.distance(point, &self.points[index])?;
+                    best.push(Neighbor { index, distance })
+                }
+            }
+            BallKind::Split { left, right } => {
+                let dl = self.min_distance(*left, point)?;
+                let dr = self.min_distance(*right, point)?;
+                let ordered = if dl <= dr {
+                    [(*left, dl), (*right, dr)]
+                } else {
+                    [(*right, dr), (*left, dl)]
+                };
+
+                for (child, d) in ordered {
+                    if d <= best.bound() {
+                        self.search_k(child, point, best)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn search_radius(
+        &self,
+        node: usize,
+        point: &[f64],
+        radius: f64,
+        found: &mut Vec<Neighbor>,
+    ) -> Result<(), CustomErrors> {
+        if self.min_distance(node, point)? > radius {
+            return Ok(());
+        }
+
+        match &self.nodes[node].kind {
+            BallKind::Leaf { indices } => {
+                for &index in indices {
+                    let distance = self.metric.distance(point, &self.points[index])?;
+                    if distance <= radius {
+                        found.push(Neighbor { index, distance })
+                    }
+                }
+            }
+            BallKind::Split { left, right } => {
+                self.search_radius(*left, point, radius, found)?;
+                self.search_radius(*right, point, radius, found)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<D: Distance> NeighborIndex for BallTree<D> {
+    fn query(&self, point: &[f64], k: usize) -> Result<Vec<Neighbor>, CustomErrors> {
+        check_query(&self.points, point)?;
+        let mut best = KBest::new(k);
+        if k > 0 {
+            self.search_k(self.root, point, &mut best)?;
+        }
+        Ok(best.into_sorted_vec())
+    }
+
+    fn query_radius(&self, point: &[f64], radius: f64) -> Result<Vec<Neighbor>, CustomErrors> {
+        check_query(&self.points, point)?;
+        let mut found = vec![];
+        self.search_radius(self.root, point, radius, &mut found)?;
+        found.sort();
+        Ok(found)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        distance::{Cosine, Manhattan, SquaredEuclidean},
+        error::{CustomErrors, NotImplementedError},
+        matrix::Matrix,
+        models::neighbors::NeighborIndex,
+    };
+
+    use super::BallTree;
+
+    #[test]
+    fn test_balltree() {
+        let rows: Vec<Vec<f64>> = (0..40)
+            .map(|i| vec![(i % 8) as f64, (i / 8) as f64 * 1.5])
+            .collect();
+        let data = Matrix::new(rows).unwrap();
+        let tree = BallTree::new(&data, Manhattan).unwrap();
+
+        let found = tree.query(&[2.1, 3.0], 3).unwrap();
+        let indices: Vec<usize> = found.iter().map(|n| n.index).collect();
+        // (2, 3) is row 18, then (3, 3) and (1, 3) at 0.9 and 1.1
+        assert_eq!(indices, vec![18, 19, 17]);
+    }
+
+    #[test]
+    fn test_balltree_rejects_non_metrics() {
+        // no triangle inequality, pruning could miss true neighbors
+        let data = Matrix::new(vec![vec![1.0, 1.0], vec![-1.0, -1.0]]).unwrap();
+        for result in [
+            BallTree::new(&data, Cosine).map(|_| ()),
+            BallTree::new(&data, SquaredEuclidean).map(|_| ()),
+        ] {
+            assert_eq!(
+                result.unwrap_err(),
+                CustomErrors::NotImplemented(NotImplementedError)
+            );
+        }
+    }
+}
@@ -0,0 +1,224 @@
+use crate::{
+    distance::Distance,
+    error::{CustomErrors, EmptyVectorError, NotImplementedError},
+    matrix::Matrix,
+};
+
+use super::{check_query, KBest, Neighbor, NeighborIndex};
+
+const LEAF_SIZE: usize = 10;
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+enum KdNode {
+    Leaf {
+        indices: Vec<usize>,
+    },
+    Split {
+        axis: usize,
+        value: f64,
+        left: usize,
+        right: usize,
+    },
+}
+
+/// Axis aligned space partitioning tree. Each split is at the median of the axis
+/// with the largest spread. Only metrics with a `coordinate_bound` (the Minkowski
+/// family) can be used.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KDTree<D: Distance> {
+    points: Vec<Vec<f64>>,
+    metric: D,
+    nodes: Vec<KdNode>,
+    root: usize,
+}
+
+impl<D: Distance> KDTree<D> {
+    pub fn new(data: &Matrix<f64>, metric: D) -> Result<KDTree<D>, CustomErrors> {
+        if metric.coordinate_bound(1.0).is_none() {
+            return Err(CustomErrors::NotImplemented(NotImplementedError));
+        }
+        if data.m == 0 {
+            return Err(CustomErrors::EmptyVector(EmptyVectorError));
+        }
+
+        let mut tree = KDTree {
+            points: data.rows.clone(),
+            metric,
+            nodes: vec![],
+            root: 0,
+        };
+        let indices: Vec<usize> = (0..data.m).collect();
+        tree.root = tree.build(indices);
+        Ok(tree)
+    }
+
+    fn build(&mut self, mut indices: Vec<usize>) -> usize {
+        let (axis, spread) = self.widest_axis(&indices);
+
+        if indices.len() <= LEAF_SIZE || spread == 0.0 {
+            self.nodes.push(KdNode::Leaf { indices });
+            return self.nodes.len() - 1;
+        }
+
+        indices.sort_by(|&a, &b| self.points[a][axis].total_cmp(&self.points[b][axis]));
+        let mid = indices.len() / 2;
+        let value = self.points[indices[mid]][axis];
+        let right_indices = indices.split_off(mid);
+
+        let left = self.build(indices);
+        let right = self.build(right_indices);
+        self.nodes.push(KdNode::Split {
+            axis,
+            value,
+            left,
+            right,
+        });
+        self.nodes.len() - 1
+    }
+
+    fn widest_axis(&self, indices: &[usize]) -> (usize, f64) {
+        let dims = self.points[indices[0]].len();
+        let mut best = (0, 0.0);
+        for axis in 0..dims {
+            let mut lo = f64::INFINITY;
+            let mut hi = f64::NEG_INFINITY;
+            for &i in indices {
+                lo = lo.min(self.points[i][axis]);
+                hi = hi.max(self.points[i][axis]);
+            }
+            if hi - lo > best.1 {
+                best = (axis, hi - lo)
+            }
+        }
+        best
+    }
+
+    fn search_k(&self, node: usize, point: &[f64], best: &mut KBest) -> Result<(), CustomErrors> {
+        match &self.nodes[node] {
+            KdNode::Leaf { indices } => {
+                for &index in indices {
+                    let distance = self.metric.distance(point, &self.points[index])?;
+                    best.push(Neighbor { index, distance })
+                }
+            }
+            KdNode::Split {
+                axis,
+                value,
+                left,
+                right,
+            } => {
+                let diff = point[*axis] - value;
+                let (near, far) = if diff < 0.0 {
+                    (*left, *right)
+                } else {
+                    (*right, *left)
+                };
+
+                self.search_k(near, point, best)?;
+                if self.bound(diff) <= best.bound() {
+                    self.search_k(far, point, best)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn search_radius(
+        &self,
+        node: usize,
+        point: &[f64],
+        radius: f64,
+        found: &mut Vec<Neighbor>,
+    ) -> Result<(), CustomErrors> {
+        match &self.nodes[node] {
+            KdNode::Leaf { indices } => {
+                for &index in indices {
+                    let distance = self.metric.distance(point, &self.points[index])?;
+                    if distance <= radius {
+                        found.push(Neighbor { index, distance })
+                    }
+                }
+            }
+            KdNode::Split {
+                axis,
+                value,
+                left,
+                right,
+            } => {
+                let diff = point[*axis] - value;
+                let (near, far) = if diff < 0.0 {
+                    (*left, *right)
+                } else {
+                    (*right, *left)
+                };
+
+                self.search_radius(near, point, radius, found)?;
+                if self.bound(diff) <= radius {
+                    self.search_radius(far, point, radius, found)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn bound(&self, diff: f64) -> f64 {
+        // checked in `new`
+        self.metric.coordinate_bound(diff).unwrap_or(0.0)
+    }
+}
+
+impl<D: Distance> NeighborIndex for KDTree<D> {
+    fn query(&self, point: &[f64], k: usize) -> Result<Vec<Neighbor>, CustomErrors> {
+        check_query(&self.points, point)?;
+        let mut best = KBest::new(k);
+        if k > 0 {
+            self.search_k(self.root, point, &mut best)?;
+        }
+        Ok(best.into_sorted_vec())
+    }
+
+    fn query_radius(&self, point: &[f64], radius: f64) -> Result<Vec<Neighbor>, CustomErrors> {
+        check_query(&self.points, point)?;
+        let mut found = vec![];
+        self.search_radius(self.root, point, radius, &mut found)?;
+        found.sort();
+        Ok(found)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        distance::{Cosine, Euclidean},
+        error::{CustomErrors, NotImplementedError},
+        matrix::Matrix,
+        models::neighbors::NeighborIndex,
+    };
+
+    use super::KDTree;
+
+    #[test]
+    fn test_kdtree() {
+        let rows: Vec<Vec<f64>> = (0..50).map(|i| vec![i as f64, (i % 7) as f64]).collect();
+        let data = Matrix::new(rows).unwrap();
+        let tree = KDTree::new(&data, Euclidean).unwrap();
+
+        let found = tree.query(&[10.2, 3.0], 1).unwrap();
+        assert_eq!(found[0].index, 10);
+
+        let within: Vec<usize> = tree
+            .query_radius(&[20.0, 6.0], 1.5)
+            .unwrap()
+            .iter()
+            .map(|n| n.index)
+            .collect();
+        assert_eq!(within, vec![20, 19]);
+
+        assert_eq!(
+            KDTree::new(&data, Cosine).unwrap_err(),
+            CustomErrors::NotImplemented(NotImplementedError)
+        );
+    }
+}
@@ -0,0 +1,382 @@
+use crate::{
+    distance::{Distance, Euclidean},
+    error::{CustomErrors, EmptyVectorError, MismatchError},
+    matrix::Matrix,
+    traits::{Fit, Predict, PredictProba},
+};
+
+use super::{Algorithm, Neighbor, NeighborIndex, NeighborSearch};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Weights {
+    /// Every neighbor counts the same.
+    Uniform,
+    /// Neighbors count by the inverse of their distance, exact matches take all weight.
+    Distance,
+}
+
+fn neighbor_weights(neighbors: &[Neighbor], weights: Weights) -> Vec<f64> {
+    match weights {
+        Weights::Uniform => vec![1.0; neighbors.len()],
+        Weights::Distance => {
+            if neighbors.iter().any(|n| n.distance == 0.0) {
+                neighbors
+                    .iter()
+                    .map(|n| if n.distance == 0.0 { 1.0 } else { 0.0 })
+                    .collect()
+            } else {
+                neighbors.iter().map(|n| 1.0 / n.distance).collect()
+            }
+        }
+    }
+}
+
+fn check_fit(features: &Matrix<f64>, targets: &[f64], k: usize) -> Result<(), CustomErrors> {
+    if features.m == 0 || k == 0 {
+        return Err(CustomErrors::EmptyVector(EmptyVectorError));
+    }
+    if features.m != targets.len() {
+        return Err(CustomErrors::Mismatch(MismatchError));
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KNeighborsClassifier<D: Distance + Clone = Euclidean> {
+    k: usize,
+    weights: Weights,
+    algorithm: Algorithm,
+    metric: D,
+    index: Option<NeighborSearch<D>>,
+    targets: Vec<f64>,
+    classes: Vec<f64>,
+}
+
+impl KNeighborsClassifier {
+    pub fn new(k: usize) -> KNeighborsClassifier {
+        KNeighborsClassifier {
+            k,
+            weights: Weights::Uniform,
+            algorithm: Algorithm::KdTree,
+            metric: Euclidean,
+            index: None,
+            targets: vec![],
+            classes: vec![],
+        }
+    }
+}
+
+impl<D: Distance + Clone> KNeighborsClassifier<D> {
+    pub fn with_weights(mut self, weights: Weights) -> KNeighborsClassifier<D> {
+        self.weights = weights;
+        self
+    }
+
+    pub fn with_algorithm(mut self, algorithm: Algorithm) -> KNeighborsClassifier<D> {
+        self.algorithm = algorithm;
+        self
+    }
+
+    /// Switches to an algorithm that supports `metric` if the current one
+    /// doesn't, see `Algorithm::supporting`.
+    pub fn with_metric<E: Distance + Clone>(self, metric: E) -> KNeighborsClassifier<E> {
+        KNeighborsClassifier {
+            k: self.k,
+            weights: self.weights,
+            algorithm: self.algorithm.supporting(&metric),
+            metric,
+            index: None,
+            targets: vec![],
+            classes: vec![],
+        }
+    }
+
+    /// Sorted distinct class labels, the column order of `predict_proba`.
+    pub fn classes(&self) -> &Vec<f64> {
+        &self.classes
+    }
+
+    pub fn fit(&mut self, features: &Matrix<f64>, targets: &[f64]) -> Result<(), CustomErrors> {
+        check_fit(features, targets, self.k)?;
+
+        let mut classes = targets.to_vec();
+        classes.sort_by(|a, b| a.total_cmp(b));
+        classes.dedup();
+
+        self.index = Some(NeighborSearch::new(
+            features,
+            self.metric.clone(),
+            self.algorithm,
+        )?);
+        self.targets = targets.to_vec();
+        self.classes = classes;
+        Ok(())
+    }
+
+    pub fn predict_proba(&self, features: &Matrix<f64>) -> Result<Vec<Vec<f64>>, CustomErrors> {
+        let index = match &self.index {
+            Some(index) => index,
+            None => return Err(CustomErrors::EmptyVector(EmptyVectorError)),
+        };
+
+        let mut proba = vec![];
+        for pt in &features.rows {
+            let neighbors = index.query(pt, self.k)?;
+            let weights = neighbor_weights(&neighbors, self.weights);
+
+            let mut votes = vec![0.0; self.classes.len()];
+            for (n, w) in neighbors.iter().zip(weights.iter()) {
+                let target = self.targets[n.index];
+                // classes came from the targets, the label is always found
+                let class = self.classes.iter().position(|c| *c == target).unwrap_or(0);
+                votes[class] += w;
+            }
+
+            let total: f64 = votes.iter().sum();
+            proba.push(votes.iter().map(|v| v / total).collect())
+        }
+        Ok(proba)
+    }
+
+    /// Majority (or distance weighted) vote, ties go to the smaller class label.
+    pub fn predict(&self, features: &Matrix<f64>) -> Result<Vec<f64>, CustomErrors> {
+        let proba = self.predict_proba(features)?;
+
+        let mut pred = vec![];
+        for row in proba {
+            let mut best = 0;
+            for (i, p) in row.iter().enumerate() {
+                if *p > row[best] {
+                    best = i
+                }
+            }
+            pred.push(self.classes[best])
+        }
+        Ok(pred)
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KNeighborsRegressor<D: Distance + Clone = Euclidean> {
+    k: usize,
+    weights: Weights,
+    algorithm: Algorithm,
+    metric: D,
+    index: Option<NeighborSearch<D>>,
+    targets: Vec<f64>,
+}
+
+impl KNeighborsRegressor {
+    pub fn new(k: usize) -> KNeighborsRegressor {
+        KNeighborsRegressor {
+            k,
+            weights: Weights::Uniform,
+            algorithm: Algorithm::KdTree,
+            metric: Euclidean,
+            index: None,
+            targets: vec![],
+        }
+    }
+}
+
+impl<D: Distance + Clone> KNeighborsRegressor<D> {
+    pub fn with_weights(mut self, weights: Weights) -> KNeighborsRegressor<D> {
+        self.weights = weights;
+        self
+    }
+
+    pub fn with_algorithm(mut self, algorithm: Algorithm) -> KNeighborsRegressor<D> {
+        self.algorithm = algorithm;
+        self
+    }
+
+    /// Switches to an algorithm that supports `metric` if the current one
+    /// doesn't, see `Algorithm::supporting`.
+    pub fn with_metric<E: Distance + Clone>(self, metric: E) -> KNeighborsRegressor<E> {
+        KNeighborsRegressor {
+            k: self.k,
+            weights: self.weights,
+            algorithm: self.algorithm.supporting(&metric),
+            metric,
+            index: None,
+            targets: vec![],
+        }
+    }
+
+    pub fn fit(&mut self, features: &Matrix<f64>, targets: &[f64]) -> Result<(), CustomErrors> {
+        check_fit(features, targets, self.k)?;
+
+        self.index = Some(NeighborSearch::new(
+            features,
+            self.metric.clone(),
+            self.algorithm,
+        )?);
+        self.targets = targets.to_vec();
+        Ok(())
+    }
+
+    /// (Weighted) mean of the neighbors' targets.
+    pub fn predict(&self, features: &Matrix<f64>) -> Result<Vec<f64>, CustomErrors> {
+        let index = match &self.index {
+            Some(index) => index,
+            None => return Err(CustomErrors::EmptyVector(EmptyVectorError)),
+        };
+
+        let mut pred = vec![];
+        for pt in &features.rows {
+            let neighbors = index.query(pt, self.k)?;
+            let weights = neighbor_weights(&neighbors, self.weights);
+
+            let mut num = 0.0;
+            let mut den = 0.0;
+            for (n, w) in neighbors.iter().zip(weights.iter()) {
+                num += w * self.targets[n.index];
+                den += w;
+            }
+            pred.push(num / den)
+        }
+        Ok(pred)
+    }
+}
+
+impl<D: Distance + Clone> Fit for KNeighborsClassifier<D> {
+    type Input = Matrix<f64>;
+    type Target = [f64];
+    type Output = ();
+
+    fn fit(&mut self, x: &Matrix<f64>, y: &[f64]) -> Result<(), CustomErrors> {
+        KNeighborsClassifier::fit(self, x, y)
+    }
+}
+
+impl<D: Distance + Clone> Predict for KNeighborsClassifier<D> {
+    type Input = Matrix<f64>;
+    type Output = Vec<f64>;
+
+    fn predict(&self, x: &Matrix<f64>) -> Result<Vec<f64>, CustomErrors> {
+        KNeighborsClassifier::predict(self, x)
+    }
+}
+
+impl<D: Distance + Clone> PredictProba for KNeighborsClassifier<D> {
+    type Input = Matrix<f64>;
+    type Output = Vec<Vec<f64>>;
+
+    fn predict_proba(&self, x: &Matrix<f64>) -> Result<Vec<Vec<f64>>, CustomErrors> {
+        KNeighborsClassifier::predict_proba(self, x)
+    }
+}
+
+impl<D: Distance + Clone> Fit for KNeighborsRegressor<D> {
+    type Input = Matrix<f64>;
+    type Target = [f64];
+    type Output = ();
+
+    fn fit(&mut self, x: &Matrix<f64>, y: &[f64]) -> Result<(), CustomErrors> {
+        KNeighborsRegressor::fit(self, x, y)
+    }
+}
+
+impl<D: Distance + Clone> Predict for KNeighborsRegressor<D> {
+    type Input = Matrix<f64>;
+    type Output = Vec<f64>;
+
+    fn predict(&self, x: &Matrix<f64>) -> Result<Vec<f64>, CustomErrors> {
+        KNeighborsRegressor::predict(self, x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        distance::{Cosine, Hamming, Manhattan},
+        matrix::Matrix,
+        models::neighbors::Algorithm,
+    };
+
+    use super::{KNeighborsClassifier, KNeighborsRegressor, Weights};
+
+    fn line() -> (Matrix<f64>, Vec<f64>) {
+        let x = Matrix::new(vec![
+            vec![0.0],
+            vec![1.0],
+            vec![2.0],
+            vec![10.0],
+            vec![11.0],
+            vec![12.0],
+        ])
+        .unwrap();
+        (x, vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0])
+    }
+
+    #[test]
+    fn test_knn_classifier() {
+        let (x, y) = line();
+        let mut model = KNeighborsClassifier::new(3);
+        model.fit(&x, &y).unwrap();
+
+        let test = Matrix::new(vec![vec![0.5], vec![11.5], vec![4.0]]).unwrap();
+        assert_eq!(model.predict(&test).unwrap(), vec![0.0, 1.0, 0.0]);
+
+        let proba = model.predict_proba(&test).unwrap();
+        assert_eq!(proba[0], vec![1.0, 0.0]);
+        assert_eq!(model.classes(), &vec![0.0, 1.0]);
+
+        // the default KD-tree can't use these, fit falls back instead of failing
+        let mut hamming = KNeighborsClassifier::new(1).with_metric(Hamming);
+        hamming.fit(&x, &y).unwrap();
+        assert_eq!(hamming.predict(&x).unwrap(), y);
+        let cosine = KNeighborsClassifier::new(1).with_metric(Cosine);
+        assert_eq!(cosine.algorithm, Algorithm::Brute);
+    }
+
+    #[test]
+    fn test_knn_distance_weighting() {
+        // with k = 6 the uniform vote is a tie, the weighted vote follows proximity
+        let (x, y) = line();
+        let test = Matrix::new(vec![vec![8.0]]).unwrap();
+
+        let mut uniform = KNeighborsClassifier::new(6).with_algorithm(Algorithm::Brute);
+        uniform.fit(&x, &y).unwrap();
+        assert_eq!(uniform.predict_proba(&test).unwrap()[0], vec![0.5, 0.5]);
+
+        let mut weighted = KNeighborsClassifier::new(6)
+            .with_weights(Weights::Distance)
+            .with_algorithm(Algorithm::BallTree)
+            .with_metric(Manhattan);
+        weighted.fit(&x, &y).unwrap();
+        assert_eq!(weighted.predict(&test).unwrap(), vec![1.0]);
+    }
+
+    #[test]
+    fn test_knn_regressor() {
+        let x = Matrix::new(vec![vec![0.0], vec![1.0], vec![2.0], vec![3.0]]).unwrap();
+        let y = vec![0.0, 2.0, 4.0, 6.0];
+
+        let mut model = KNeighborsRegressor::new(2);
+        model.fit(&x, &y).unwrap();
+        let pred = model
+            .predict(&Matrix::new(vec![vec![0.4]]).unwrap())
+            .unwrap();
+        assert_eq!(pred, vec![1.0]);
+
+        let mut weighted = KNeighborsRegressor::new(2).with_weights(Weights::Distance);
+        weighted.fit(&x, &y).unwrap();
+        // weights 1/0.25 and 1/0.75
+        let pred = weighted
+            .predict(&Matrix::new(vec![vec![0.25]]).unwrap())
+            .unwrap();
+        assert!((pred[0] - 0.5).abs() < 1e-12);
+
+        // exact match takes all the weight
+        let pred = weighted
+            .predict(&Matrix::new(vec![vec![1.0]]).unwrap())
+            .unwrap();
+        assert_eq!(pred, vec![2.0]);
+
+        assert!(model.fit(&x, &[1.0]).is_err());
+    }
+}
@@ -0,0 +1,261 @@
+pub mod balltree;
+pub mod kdtree;
+pub mod knn;
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use crate::{
+    distance::Distance,
+    error::{CustomErrors, EmptyVectorError, MismatchError},
+    matrix::Matrix,
+};
+
+use self::{balltree::BallTree, kdtree::KDTree};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Neighbor {
+    pub index: usize,
+    pub distance: f64,
+}
+
+impl Eq for Neighbor {}
+
+impl PartialOrd for Neighbor {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Neighbor {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.distance
+            .total_cmp(&other.distance)
+            .then(self.index.cmp(&other.index))
+    }
+}
+
+/// Nearest neighbor lookups over a fixed set of points. Results are sorted by
+/// increasing distance, ties by index.
+pub trait NeighborIndex {
+    fn query(&self, point: &[f64], k: usize) -> Result<Vec<Neighbor>, CustomErrors>;
+    fn query_radius(&self, point: &[f64], radius: f64) -> Result<Vec<Neighbor>, CustomErrors>;
+}
+
+/// Bounded max-heap holding the `k` closest candidates seen so far.
+pub(crate) struct KBest {
+    k: usize,
+    heap: BinaryHeap<Neighbor>,
+}
+
+impl KBest {
+    pub(crate) fn new(k: usize) -> KBest {
+        KBest {
+            k,
+            heap: BinaryHeap::new(),
+        }
+    }
+
+    /// Distance a new candidate has to beat, infinite until `k` candidates are held.
+    pub(crate) fn bound(&self) -> f64 {
+        if self.heap.len() < self.k {
+            return f64::INFINITY;
+        }
+        match self.heap.peek() {
+            Some(worst) => worst.distance,
+            None => f64::INFINITY,
+        }
+    }
+
+    pub(crate) fn push(&mut self, candidate: Neighbor) {
+        if self.heap.len() < self.k {
+            self.heap.push(candidate);
+        } else if let Some(worst) = self.heap.peek() {
+            if candidate < *worst {
+                self.heap.pop();
+                self.heap.push(candidate);
+            }
+        }
+    }
+
+    pub(crate) fn into_sorted_vec(self) -> Vec<Neighbor> {
+        self.heap.into_sorted_vec()
+    }
+}
+
+pub(crate) fn check_query(points: &[Vec<f64>], point: &[f64]) -> Result<(), CustomErrors> {
+    if points.is_empty() {
+        return Err(CustomErrors::EmptyVector(EmptyVectorError));
+    }
+    if points[0].len() != point.len() {
+        return Err(CustomErrors::Mismatch(MismatchError));
+    }
+    Ok(())
+}
+
+/// Exhaustive search, works with any `Distance`.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BruteForce<D: Distance> {
+    points: Vec<Vec<f64>>,
+    metric: D,
+}
+
+impl<D: Distance> BruteForce<D> {
+    pub fn new(data: &Matrix<f64>, metric: D) -> BruteForce<D> {
+        BruteForce {
+            points: data.rows.clone(),
+            metric,
+        }
+    }
+}
+
+impl<D: Distance> NeighborIndex for BruteForce<D> {
+    fn query(&self, point: &[f64], k: usize) -> Result<Vec<Neighbor>, CustomErrors> {
+        check_query(&self.points, point)?;
+        let mut best = KBest::new(k);
+        for (index, p) in self.points.iter().enumerate() {
+            let distance = self.metric.distance(point, p)?;
+            best.push(Neighbor { index, distance })
+        }
+        Ok(best.into_sorted_vec())
+    }
+
+    fn query_radius(&self, point: &[f64], radius: f64) -> Result<Vec<Neighbor>, CustomErrors> {
+        check_query(&self.points, point)?;
+        let mut found = vec![];
+        for (index, p) in self.points.iter().enumerate() {
+            let distance = self.metric.distance(point, p)?;
+            if distance <= radius {
+                found.push(Neighbor { index, distance })
+            }
+        }
+        found.sort();
+        Ok(found)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Algorithm {
+    Brute,
+    KdTree,
+    BallTree,
+}
+
+impl Algorithm {
+    /// `self` if it can index with `metric`, otherwise a ball tree for metrics
+    /// without a `coordinate_bound` and brute force for non-metrics.
+    pub fn supporting<D: Distance>(self, metric: &D) -> Algorithm {
+        match self {
+            Algorithm::KdTree if metric.coordinate_bound(1.0).is_none() => {
+                Algorithm::BallTree.supporting(metric)
+            }
+            Algorithm::BallTree if !metric.is_metric() => Algorithm::Brute,
+            algorithm => algorithm,
+        }
+    }
+}
+
+/// One of the indexes, picked at runtime by `Algorithm`.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NeighborSearch<D: Distance> {
+    Brute(BruteForce<D>),
+    KdTree(KDTree<D>),
+    BallTree(BallTree<D>),
+}
+
+impl<D: Distance> NeighborSearch<D> {
+    pub fn new(
+        data: &Matrix<f64>,
+        metric: D,
+        algorithm: Algorithm,
+    ) -> Result<NeighborSearch<D>, CustomErrors> {
+        match algorithm {
+            Algorithm::Brute => Ok(NeighborSearch::Brute(BruteForce::new(data, metric))),
+            Algorithm::KdTree => Ok(NeighborSearch::KdTree(KDTree::new(data, metric)?)),
+            Algorithm::BallTree => Ok(NeighborSearch::BallTree(BallTree::new(data, metric)?)),
+        }
+    }
+}
+
+impl<D: Distance> NeighborIndex for NeighborSearch<D> {
+    fn query(&self, point: &[f64], k: usize) -> Result<Vec<Neighbor>, CustomErrors> {
+        match self {
+            NeighborSearch::Brute(index) => index.query(point, k),
+            NeighborSearch::KdTree(index) => index.query(point, k),
+            NeighborSearch::BallTree(index) => index.query(point, k),
+        }
+    }
+
+    fn query_radius(&self, point: &[f64], radius: f64) -> Result<Vec<Neighbor>, CustomErrors> {
+        match self {
+            NeighborSearch::Brute(index) => index.query_radius(point, radius),
+            NeighborSearch::KdTree(index) => index.query_radius(point, radius),
+            NeighborSearch::BallTree(index) => index.query_radius(point, radius),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use crate::{
+        distance::{Euclidean, Manhattan},
+        matrix::Matrix,
+    };
+
+    use super::{Algorithm, NeighborIndex, NeighborSearch};
+
+    #[test]
+    fn test_indexes_agree() {
+        let mut rng = StdRng::seed_from_u64(11);
+        let rows: Vec<Vec<f64>> = (0..200)
+            .map(|_| (0..3).map(|_| rng.gen_range(-10.0..10.0)).collect())
+            .collect();
+        let data = Matrix::new(rows).unwrap();
+
+        let brute = NeighborSearch::new(&data, Euclidean, Algorithm::Brute).unwrap();
+        let kd = NeighborSearch::new(&data, Euclidean, Algorithm::KdTree).unwrap();
+        let ball = NeighborSearch::new(&data, Euclidean, Algorithm::BallTree).unwrap();
+        let ball_l1 = NeighborSearch::new(&data, Manhattan, Algorithm::BallTree).unwrap();
+        let brute_l1 = NeighborSearch::new(&data, Manhattan, Algorithm::Brute).unwrap();
+
+        for _ in 0..20 {
+            let q: Vec<f64> = (0..3).map(|_| rng.gen_range(-12.0..12.0)).collect();
+
+            let expected = brute.query(&q, 7).unwrap();
+            assert_eq!(kd.query(&q, 7).unwrap(), expected);
+            assert_eq!(ball.query(&q, 7).unwrap(), expected);
+            assert_eq!(
+                ball_l1.query(&q, 7).unwrap(),
+                brute_l1.query(&q, 7).unwrap()
+            );
+
+            let expected = brute.query_radius(&q, 4.0).unwrap();
+            assert_eq!(kd.query_radius(&q, 4.0).unwrap(), expected);
+            assert_eq!(ball.query_radius(&q, 4.0).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_query_sorted() {
+        let data = Matrix::new(vec![vec![0.0], vec![3.0], vec![1.0], vec![7.0]]).unwrap();
+        let brute = NeighborSearch::new(&data, Euclidean, Algorithm::Brute).unwrap();
+
+        let found: Vec<usize> = brute
+            .query(&[0.5], 3)
+            .unwrap()
+            .iter()
+            .map(|n| n.index)
+            .collect();
+        assert_eq!(found, vec![0, 2, 1]);
+
+        // asking for more neighbours than points returns all of them
+        assert_eq!(brute.query(&[0.5], 10).unwrap().len(), 4);
+        assert!(brute.query(&[0.5, 1.0], 1).is_err());
+    }
+}
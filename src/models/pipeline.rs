@@ -0,0 +1,147 @@
+use crate::{
+    error::CustomErrors,
+    matrix::Matrix,
+    traits::{Fit, Predict, PredictProba, Transform},
+};
+
+/// A transformer that can sit inside a `Pipeline`. Implemented for every
+/// unsupervised `Fit + Transform` from matrix to matrix.
+pub trait PipelineStep {
+    fn fit_step(&mut self, x: &Matrix<f64>) -> Result<Matrix<f64>, CustomErrors>;
+    fn transform_step(&self, x: &Matrix<f64>) -> Result<Matrix<f64>, CustomErrors>;
+    fn clone_step(&self) -> Box<dyn PipelineStep>;
+}
+
+impl<T> PipelineStep for T
+where
+    T: Fit<Input = Matrix<f64>, Target = ()>
+        + Transform<Input = Matrix<f64>, Output = Matrix<f64>>
+        + Clone
+        + 'static,
+{
+    fn fit_step(&mut self, x: &Matrix<f64>) -> Result<Matrix<f64>, CustomErrors> {
+        Fit::fit(self, x, &())?;
+        Transform::transform(self, x)
+    }
+
+    fn transform_step(&self, x: &Matrix<f64>) -> Result<Matrix<f64>, CustomErrors> {
+        Transform::transform(self, x)
+    }
+
+    fn clone_step(&self) -> Box<dyn PipelineStep> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn PipelineStep> {
+    fn clone(&self) -> Self {
+        self.clone_step()
+    }
+}
+
+/// Transformers applied in order, followed by a final estimator. Every step is
+/// fitted on the training data only, `predict` reuses the learned parameters.
+#[derive(Clone)]
+pub struct Pipeline<E> {
+    steps: Vec<(String, Box<dyn PipelineStep>)>,
+    estimator: E,
+}
+
+impl<E> Pipeline<E> {
+    pub fn new(estimator: E) -> Pipeline<E> {
+        Pipeline {
+            steps: vec![],
+            estimator,
+        }
+    }
+
+    /// Appends a named transformer, applied after the ones added before it.
+    pub fn with_step<S: PipelineStep + 'static>(mut self, name: &str, step: S) -> Pipeline<E> {
+        self.steps.push((name.to_string(), Box::new(step)));
+        self
+    }
+
+    pub fn step_names(&self) -> Vec<&str> {
+        self.steps.iter().map(|(name, _)| name.as_str()).collect()
+    }
+
+    pub fn estimator(&self) -> &E {
+        &self.estimator
+    }
+
+    /// Runs `x` through the fitted transformers, without the estimator.
+    pub fn transform(&self, x: &Matrix<f64>) -> Result<Matrix<f64>, CustomErrors> {
+        let mut out = x.clone();
+        for (_, step) in &self.steps {
+            out = step.transform_step(&out)?;
+        }
+        Ok(out)
+    }
+}
+
+impl<E: Fit<Input = Matrix<f64>>> Fit for Pipeline<E> {
+    type Input = Matrix<f64>;
+    type Target = E::Target;
+    type Output = E::Output;
+
+    fn fit(&mut self, x: &Matrix<f64>, y: &E::Target) -> Result<E::Output, CustomErrors> {
+        let mut out = x.clone();
+        for (_, step) in self.steps.iter_mut() {
+            out = step.fit_step(&out)?;
+        }
+        self.estimator.fit(&out, y)
+    }
+}
+
+impl<E: Predict<Input = Matrix<f64>>> Predict for Pipeline<E> {
+    type Input = Matrix<f64>;
+    type Output = E::Output;
+
+    fn predict(&self, x: &Matrix<f64>) -> Result<E::Output, CustomErrors> {
+        self.estimator.predict(&self.transform(x)?)
+    }
+}
+
+impl<E: PredictProba<Input = Matrix<f64>>> PredictProba for Pipeline<E> {
+    type Input = Matrix<f64>;
+    type Output = E::Output;
+
+    fn predict_proba(&self, x: &Matrix<f64>) -> Result<E::Output, CustomErrors> {
+        self.estimator.predict_proba(&self.transform(x)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        matrix::{transform::scaling::StandardScaler, Matrix},
+        models::neighbors::knn::KNeighborsClassifier,
+        traits::{Fit, Predict, PredictProba},
+    };
+
+    use super::Pipeline;
+
+    #[test]
+    fn test_pipeline() {
+        let x = Matrix::new(vec![
+            vec![0.0, 0.0],
+            vec![0.1, 1000.0],
+            vec![1.0, 0.0],
+            vec![1.1, 1000.0],
+        ])
+        .unwrap();
+        let y = vec![0.0, 0.0, 1.0, 1.0];
+        let test = Matrix::new(vec![vec![1.05, 100.0]]).unwrap();
+
+        let mut pipe =
+            Pipeline::new(KNeighborsClassifier::new(1)).with_step("scale", StandardScaler::new());
+        pipe.fit(&x, &y).unwrap();
+        assert_eq!(pipe.predict(&test).unwrap(), vec![1.0]);
+        assert_eq!(pipe.predict_proba(&test).unwrap()[0], vec![0.0, 1.0]);
+        assert_eq!(pipe.step_names(), vec!["scale"]);
+
+        // test data goes through the training statistics
+        let scaled = pipe.transform(&test).unwrap();
+        assert_eq!(scaled.rows[0][1], (100.0 - 500.0) / 500.0);
+    }
+}
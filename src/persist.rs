@@ -0,0 +1,175 @@
+use std::{fs, path::Path};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::error::{CustomErrors, DecodeError, ParseError, VersionError};
+
+/// Bumped whenever a saved type changes its layout. Files with another
+/// version are rejected instead of being read into the wrong fields.
+pub const FORMAT_VERSION: u32 = 1;
+
+/// Start of every binary file, followed by the little endian format version.
+const MAGIC: &[u8; 4] = b"PKUS";
+
+#[derive(Serialize)]
+struct Envelope<'a, T> {
+    format_version: u32,
+    crate_version: &'a str,
+    model: &'a T,
+}
+
+#[derive(Deserialize)]
+struct Header {
+    format_version: u32,
+}
+
+#[derive(Deserialize)]
+struct Saved<T> {
+    model: T,
+}
+
+fn check_version(found: u32) -> Result<(), CustomErrors> {
+    if found != FORMAT_VERSION {
+        return Err(CustomErrors::Version(VersionError {
+            found,
+            expected: FORMAT_VERSION,
+        }));
+    }
+    Ok(())
+}
+
+fn json_error(err: serde_json::Error) -> CustomErrors {
+    CustomErrors::Parse(ParseError { line: err.line() })
+}
+
+/// JSON text of `model` with the format and crate versions. JSON has no NaN
+/// or infinity, use `to_bytes` for values holding them.
+pub fn to_json<T: Serialize>(model: &T) -> Result<String, CustomErrors> {
+    let envelope = Envelope {
+        format_version: FORMAT_VERSION,
+        crate_version: env!("CARGO_PKG_VERSION"),
+        model,
+    };
+    serde_json::to_string_pretty(&envelope).map_err(json_error)
+}
+
+pub fn from_json<T: DeserializeOwned>(text: &str) -> Result<T, CustomErrors> {
+    // the version is checked first, an older layout may not parse at all
+    let header: Header = serde_json::from_str(text).map_err(json_error)?;
+    check_version(header.format_version)?;
+    let saved: Saved<T> = serde_json::from_str(text).map_err(json_error)?;
+    Ok(saved.model)
+}
+
+/// Compact bincode encoding of `model` behind a magic number and the format version.
+pub fn to_bytes<T: Serialize>(model: &T) -> Result<Vec<u8>, CustomErrors> {
+    let mut bytes = MAGIC.to_vec();
+    bytes.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+    bincode::serialize_into(&mut bytes, model).map_err(|_| CustomErrors::Decode(DecodeError))?;
+    Ok(bytes)
+}
+
+pub fn from_bytes<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, CustomErrors> {
+    if bytes.len() < 8 || &bytes[..4] != MAGIC {
+        return Err(CustomErrors::Decode(DecodeError));
+    }
+    let mut version = [0; 4];
+    version.copy_from_slice(&bytes[4..8]);
+    check_version(u32::from_le_bytes(version))?;
+    bincode::deserialize(&bytes[8..]).map_err(|_| CustomErrors::Decode(DecodeError))
+}
+
+pub fn save_json<T: Serialize, P: AsRef<Path>>(path: P, model: &T) -> Result<(), CustomErrors> {
+    fs::write(path, to_json(model)?)?;
+    Ok(())
+}
+
+pub fn load_json<T: DeserializeOwned, P: AsRef<Path>>(path: P) -> Result<T, CustomErrors> {
+    from_json(&fs::read_to_string(path)?)
+}
+
+pub fn save_binary<T: Serialize, P: AsRef<Path>>(path: P, model: &T) -> Result<(), CustomErrors> {
+    fs::write(path, to_bytes(model)?)?;
+    Ok(())
+}
+
+pub fn load_binary<T: DeserializeOwned, P: AsRef<Path>>(path: P) -> Result<T, CustomErrors> {
+    from_bytes(&fs::read(path)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        error::{CustomErrors, DecodeError, VersionError},
+        matrix::{transform::scaling::standard::StandardScaler, Matrix},
+        models::{
+            classifier::logreg::LogisticRegression,
+            cluster::{ClusterResult, KMeans},
+        },
+        test_util::temp_path,
+    };
+
+    use super::{
+        from_bytes, from_json, load_binary, load_json, save_binary, save_json, to_bytes, to_json,
+    };
+
+    #[test]
+    fn test_roundtrip() {
+        let x = Matrix::new(vec![
+            vec![0.0, 0.1],
+            vec![0.2, 0.0],
+            vec![5.0, 5.1],
+            vec![5.2, 4.9],
+        ])
+        .unwrap();
+        let y = vec![0.0, 0.0, 1.0, 1.0];
+
+        let mut model = LogisticRegression::new();
+        model.fit(x.clone(), y).unwrap();
+        let expected = model.predict(&x).unwrap();
+
+        let json: LogisticRegression = from_json(&to_json(&model).unwrap()).unwrap();
+        assert_eq!(json.predict(&x).unwrap(), expected);
+        let binary: LogisticRegression = from_bytes(&to_bytes(&model).unwrap()).unwrap();
+        assert_eq!(binary.predict(&x).unwrap(), expected);
+
+        let result = KMeans::new(2).with_seed(1).fit(&x).unwrap();
+        let path = temp_path("persist_test_clusters.json");
+        save_json(&path, &result).unwrap();
+        assert_eq!(load_json::<ClusterResult, _>(&path).unwrap(), result);
+        save_binary(&path, &result).unwrap();
+        assert_eq!(load_binary::<ClusterResult, _>(&path).unwrap(), result);
+        std::fs::remove_file(&path).unwrap();
+
+        let mut scaler = StandardScaler::new();
+        scaler.fit(&x).unwrap();
+        let loaded: StandardScaler = from_bytes(&to_bytes(&scaler).unwrap()).unwrap();
+        assert_eq!(loaded.transform(&x).unwrap(), scaler.transform(&x).unwrap());
+    }
+
+    #[test]
+    fn test_version_mismatch() {
+        let x = Matrix::new(vec![vec![1.0, 2.0]]).unwrap();
+        let old = to_json(&x)
+            .unwrap()
+            .replace("\"format_version\": 1", "\"format_version\": 0");
+        assert_eq!(
+            from_json::<Matrix<f64>>(&old).unwrap_err(),
+            CustomErrors::Version(VersionError {
+                found: 0,
+                expected: 1
+            })
+        );
+
+        let mut bytes = to_bytes(&x).unwrap();
+        bytes[4] = 7;
+        assert!(matches!(
+            from_bytes::<Matrix<f64>>(&bytes),
+            Err(CustomErrors::Version(VersionError { found: 7, .. }))
+        ));
+        assert_eq!(
+            from_bytes::<Matrix<f64>>(&bytes[1..]).unwrap_err(),
+            CustomErrors::Decode(DecodeError)
+        );
+    }
+}
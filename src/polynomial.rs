@@ -1,5 +1,7 @@
 use std::collections::HashMap;
 
+use crate::error::{BadTypeError, CustomErrors};
+
 #[derive(Debug)]
 pub struct Polynomial {
     order: u8,
@@ -13,8 +15,38 @@ impl Polynomial {
         parse_equation(&equation)
     }
 
+    /// Like `new`, but fails on input `parse_equation` cannot handle instead of
+    /// panicking: each term must look like `3`, `-x` or `2x^2`.
+    pub fn parse(equation: &str) -> Result<Polynomial, CustomErrors> {
+        if get_terms(equation).iter().all(|t| is_valid_term(t)) {
+            Ok(parse_equation(equation))
+        } else {
+            Err(CustomErrors::BadType(BadTypeError))
+        }
+    }
+
+    pub fn order(&self) -> u8 {
+        self.order
+    }
+
+    /// Coefficient of `x^power`, 0 for missing terms.
+    pub fn coefficient(&self, power: u8) -> i32 {
+        self.coeffs.get(&power.to_string()).copied().unwrap_or(0)
+    }
+
+    /// Highest power with a non-zero coefficient, unlike `order` which also
+    /// counts terms like `0x^2`.
+    pub fn degree(&self) -> u8 {
+        self.coeffs
+            .iter()
+            .filter(|(_, coeff)| **coeff != 0)
+            .filter_map(|(power, _)| power.parse::<u8>().ok())
+            .max()
+            .unwrap_or(0)
+    }
+
     pub fn get_roots(&self) -> Vec<f64> {
-        match self.order {
+        match self.degree() {
             0 => vec![] as Vec<f64>,
             1 => order_one_root(&self),
             2 => order_two_root(&self),
@@ -27,11 +59,9 @@ pub fn order_one_root(polynomial: &Polynomial) -> Vec<f64> {
     let mut roots: Vec<f64> = vec![];
     let coeffs = &polynomial.coeffs;
 
-    let a = coeffs.get("1").unwrap();
-    let b = coeffs.get("0").unwrap();
-
-    let a = *a;
-    let b = *b;
+    // missing terms have a zero coefficient
+    let a = coeffs.get("1").copied().unwrap_or(0);
+    let b = coeffs.get("0").copied().unwrap_or(0);
 
     let a: f64 = a.into();
     let b: f64 = b.into();
@@ -47,20 +77,19 @@ pub fn order_two_root(polynomial: &Polynomial) -> Vec<f64> {
     let mut roots: Vec<f64> = vec![];
     let coeffs = &polynomial.coeffs;
 
-    let a = coeffs.get("2").unwrap();
-    let b = coeffs.get("1").unwrap();
-    let c = coeffs.get("0").unwrap();
+    // in f64, b^2 and 4ac overflow i32 for coefficients past 2^15
+    let a: f64 = coeffs.get("2").copied().unwrap_or(0).into();
+    let b: f64 = coeffs.get("1").copied().unwrap_or(0).into();
+    let c: f64 = coeffs.get("0").copied().unwrap_or(0).into();
 
     // sqrt term ----> sqrt(b^2 - 4ac)
-    let sqrt_term = b.pow(2) - 4 * a * c;
-    let mut sqrt_term: f64 = sqrt_term.into();
-    sqrt_term = sqrt_term.sqrt();
+    let sqrt_term = (b.powi(2) - 4.0 * a * c).sqrt();
 
     // -b
-    let neg_b: f64 = (-1 * b).into();
+    let neg_b = -b;
 
     // 2a
-    let denominator: f64 = (2 * a).into();
+    let denominator = 2.0 * a;
 
     //root 1
     let root1 = (neg_b + sqrt_term) / denominator;
@@ -219,6 +248,27 @@ pub fn get_order(terms: &Vec<String>) -> u8 {
     order
 }
 
+/// Whether `term` is an integer coefficient, optionally followed by `x` and a
+/// single digit power.
+fn is_valid_term(term: &str) -> bool {
+    let (coeff, power) = match term.split_once('x') {
+        Some((coeff, power)) => (coeff, Some(power)),
+        None => (term, None),
+    };
+    let coeff_ok = match coeff {
+        "" | "-" => power.is_some(),
+        _ => coeff.parse::<i32>().is_ok(),
+    };
+    let power_ok = match power {
+        None | Some("") => true,
+        Some(power) => match power.strip_prefix('^') {
+            Some(digit) => digit.len() == 1 && digit.chars().all(|c| c.is_ascii_digit()),
+            None => false,
+        },
+    };
+    coeff_ok && power_ok
+}
+
 pub fn get_terms(equation: &str) -> Vec<String> {
     let mut equation = equation.replace(" ", "");
     equation = equation.replace("+", " ").replace("-", " -");
@@ -231,3 +281,28 @@ pub fn get_terms(equation: &str) -> Vec<String> {
     }
     terms
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Polynomial;
+
+    #[test]
+    fn test_parse() {
+        let poly = Polynomial::parse("x^2 - 4").unwrap();
+        assert_eq!(poly.order(), 2);
+        assert_eq!(poly.get_roots(), vec![2.0, -2.0]);
+        assert_eq!(Polynomial::parse("2x").unwrap().get_roots(), vec![0.0]);
+
+        assert!(Polynomial::parse("x^2 + y").is_err());
+        assert!(Polynomial::parse("x^12").is_err());
+        assert!(Polynomial::parse("3.5x").is_err());
+
+        // a zero leading term does not count
+        let poly = Polynomial::parse("0x^2 + 2x - 1").unwrap();
+        assert_eq!((poly.order(), poly.degree()), (2, 1));
+        assert_eq!(poly.coefficient(1), 2);
+        assert_eq!(poly.get_roots(), vec![0.5]);
+        let poly = Polynomial::parse("50000x^2 + 50000x").unwrap();
+        assert_eq!(poly.get_roots(), vec![0.0, -1.0]);
+    }
+}
@@ -0,0 +1,445 @@
+use std::{cmp::Ordering, collections::HashMap};
+
+use crate::{
+    error::{BadTypeError, CustomErrors, EmptyVectorError, MismatchError},
+    matrix::Matrix,
+    vector::operations,
+};
+
+/// How `quantile` picks a value between the two closest ranks, the same
+/// choices as NumPy.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Interpolation {
+    Linear,
+    Lower,
+    Higher,
+    Nearest,
+    Midpoint,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Correlation {
+    Pearson,
+    /// Pearson correlation of the ranks.
+    Spearman,
+    /// Kendall's tau-b, which accounts for ties.
+    Kendall,
+}
+
+fn check_empty(x: &[f64]) -> Result<(), CustomErrors> {
+    if x.is_empty() {
+        return Err(CustomErrors::EmptyVector(EmptyVectorError));
+    }
+    Ok(())
+}
+
+pub fn mean(x: &[f64]) -> Result<f64, CustomErrors> {
+    operations::mean(x)
+}
+
+/// Sum of squared deviations over `n - ddof`, `ddof = 0` for the population
+/// variance and `ddof = 1` for the unbiased sample variance.
+pub fn variance(x: &[f64], ddof: usize) -> Result<f64, CustomErrors> {
+    operations::variance(x, ddof)
+}
+
+pub fn std(x: &[f64], ddof: usize) -> Result<f64, CustomErrors> {
+    Ok(variance(x, ddof)?.sqrt())
+}
+
+/// `q`-th quantile (0 <= q <= 1) of `x`.
+pub fn quantile(x: &[f64], q: f64, method: Interpolation) -> Result<f64, CustomErrors> {
+    let (sorted, pos) = operations::quantile_rank(x, q)?;
+    let (lo, hi) = (sorted[pos.floor() as usize], sorted[pos.ceil() as usize]);
+    Ok(match method {
+        Interpolation::Linear => operations::interpolate_rank(&sorted, pos),
+        Interpolation::Lower => lo,
+        Interpolation::Higher => hi,
+        // halfway goes to the even rank, like NumPy
+        Interpolation::Nearest => sorted[pos.round_ties_even() as usize],
+        Interpolation::Midpoint => (lo + hi) / 2.0,
+    })
+}
+
+pub fn median(x: &[f64]) -> Result<f64, CustomErrors> {
+    quantile(x, 0.5, Interpolation::Linear)
+}
+
+/// Most frequent value, the smallest one on ties.
+pub fn mode(x: &[f64]) -> Result<f64, CustomErrors> {
+    check_empty(x)?;
+    let mut counts: HashMap<u64, usize> = HashMap::new();
+    for v in x {
+        // -0.0 and 0.0 are the same value
+        *counts.entry((v + 0.0).to_bits()).or_insert(0) += 1;
+    }
+    let (bits, _) = counts
+        .into_iter()
+        .max_by(|(a, ca), (b, cb)| {
+            ca.cmp(cb)
+                .then_with(|| f64::from_bits(*b).total_cmp(&f64::from_bits(*a)))
+        })
+        .unwrap();
+    Ok(f64::from_bits(bits))
+}
+
+/// `k`-th central moment, `sum((x - mean)^k) / n`.
+fn central_moment(x: &[f64], k: i32) -> Result<f64, CustomErrors> {
+    let mn = mean(x)?;
+    Ok(x.iter().map(|v| (v - mn).powi(k)).sum::<f64>() / x.len() as f64)
+}
+
+/// Fisher-Pearson coefficient of skewness `m3 / m2^1.5`, without bias
+/// correction. NaN for constant data.
+pub fn skewness(x: &[f64]) -> Result<f64, CustomErrors> {
+    let m2 = central_moment(x, 2)?;
+    Ok(central_moment(x, 3)? / m2.powf(1.5))
+}
+
+/// Excess kurtosis `m4 / m2^2 - 3`, 0 for a normal distribution, without bias
+/// correction. NaN for constant data.
+pub fn kurtosis(x: &[f64]) -> Result<f64, CustomErrors> {
+    let m2 = central_moment(x, 2)?;
+    Ok(central_moment(x, 4)? / (m2 * m2) - 3.0)
+}
+
+/// Non-negative weights with a positive sum.
+fn check_weights(x: &[f64], weights: &[f64]) -> Result<f64, CustomErrors> {
+    check_empty(x)?;
+    if x.len() != weights.len() {
+        return Err(CustomErrors::Mismatch(MismatchError));
+    }
+    let total: f64 = weights.iter().sum();
+    if weights.iter().any(|w| *w < 0.0 || w.is_nan()) || total <= 0.0 {
+        return Err(CustomErrors::BadType(BadTypeError));
+    }
+    Ok(total)
+}
+
+pub fn weighted_mean(x: &[f64], weights: &[f64]) -> Result<f64, CustomErrors> {
+    let total = check_weights(x, weights)?;
+    Ok(x.iter().zip(weights).map(|(v, w)| v * w).sum::<f64>() / total)
+}
+
+/// Variance with frequency weights, the weighted sum of squared deviations
+/// over `sum(weights) - ddof`.
+pub fn weighted_variance(x: &[f64], weights: &[f64], ddof: usize) -> Result<f64, CustomErrors> {
+    let total = check_weights(x, weights)?;
+    if total <= ddof as f64 {
+        return Err(CustomErrors::BadType(BadTypeError));
+    }
+    let mn = weighted_mean(x, weights)?;
+    let sqsum: f64 = x
+        .iter()
+        .zip(weights)
+        .map(|(v, w)| w * (v - mn).powi(2))
+        .sum();
+    Ok(sqsum / (total - ddof as f64))
+}
+
+/// Mean, variance and range of values seen one at a time, in constant memory
+/// (Welford's algorithm).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RunningStats {
+    count: usize,
+    mean: f64,
+    /// Sum of squared deviations from the current mean.
+    m2: f64,
+    min: f64,
+    max: f64,
+}
+
+impl Default for RunningStats {
+    fn default() -> Self {
+        RunningStats::new()
+    }
+}
+
+impl RunningStats {
+    pub fn new() -> RunningStats {
+        RunningStats {
+            count: 0,
+            mean: 0.0,
+            m2: 0.0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+        }
+    }
+
+    pub fn push(&mut self, x: f64) {
+        self.count += 1;
+        let delta = x - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (x - self.mean);
+        self.min = self.min.min(x);
+        self.max = self.max.max(x);
+    }
+
+    /// Combines the values seen by `other`, e.g. from another thread.
+    pub fn merge(&mut self, other: &RunningStats) {
+        if other.count == 0 {
+            return;
+        }
+        let count = self.count + other.count;
+        let delta = other.mean - self.mean;
+        self.m2 += other.m2 + delta * delta * (self.count * other.count) as f64 / count as f64;
+        self.mean += delta * other.count as f64 / count as f64;
+        self.count = count;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    pub fn mean(&self) -> Result<f64, CustomErrors> {
+        if self.count == 0 {
+            return Err(CustomErrors::EmptyVector(EmptyVectorError));
+        }
+        Ok(self.mean)
+    }
+
+    pub fn variance(&self, ddof: usize) -> Result<f64, CustomErrors> {
+        if self.count == 0 {
+            return Err(CustomErrors::EmptyVector(EmptyVectorError));
+        }
+        if self.count <= ddof {
+            return Err(CustomErrors::BadType(BadTypeError));
+        }
+        Ok(self.m2 / (self.count - ddof) as f64)
+    }
+
+    pub fn std(&self, ddof: usize) -> Result<f64, CustomErrors> {
+        Ok(self.variance(ddof)?.sqrt())
+    }
+
+    pub fn min(&self) -> Result<f64, CustomErrors> {
+        self.mean()?;
+        Ok(self.min)
+    }
+
+    pub fn max(&self) -> Result<f64, CustomErrors> {
+        self.mean()?;
+        Ok(self.max)
+    }
+}
+
+impl Extend<f64> for RunningStats {
+    fn extend<I: IntoIterator<Item = f64>>(&mut self, iter: I) {
+        for x in iter {
+            self.push(x);
+        }
+    }
+}
+
+/// 1-based ranks, tied values get the average of their ranks.
+pub fn rank(x: &[f64]) -> Vec<f64> {
+    let mut order: Vec<usize> = (0..x.len()).collect();
+    order.sort_by(|a, b| x[*a].total_cmp(&x[*b]));
+
+    let mut ranks = vec![0.0; x.len()];
+    let mut i = 0;
+    while i < order.len() {
+        let mut j = i;
+        while j + 1 < order.len() && x[order[j + 1]] == x[order[i]] {
+            j += 1;
+        }
+        let average = (i + j) as f64 / 2.0 + 1.0;
+        for k in &order[i..=j] {
+            ranks[*k] = average;
+        }
+        i = j + 1;
+    }
+    ranks
+}
+
+fn pearson(x: &[f64], y: &[f64]) -> f64 {
+    let n = x.len() as f64;
+    let (mx, my) = (x.iter().sum::<f64>() / n, y.iter().sum::<f64>() / n);
+    let mut sxy = 0.0;
+    let mut sxx = 0.0;
+    let mut syy = 0.0;
+    for (a, b) in x.iter().zip(y) {
+        sxy += (a - mx) * (b - my);
+        sxx += (a - mx).powi(2);
+        syy += (b - my).powi(2);
+    }
+    sxy / (sxx * syy).sqrt()
+}
+
+fn kendall(x: &[f64], y: &[f64]) -> f64 {
+    let mut concordant = 0.0;
+    let mut discordant = 0.0;
+    let mut tied_x = 0.0;
+    let mut tied_y = 0.0;
+    for i in 0..x.len() {
+        for j in i + 1..x.len() {
+            match (x[i].total_cmp(&x[j]), y[i].total_cmp(&y[j])) {
+                (Ordering::Equal, Ordering::Equal) => {}
+                (Ordering::Equal, _) => tied_x += 1.0,
+                (_, Ordering::Equal) => tied_y += 1.0,
+                (a, b) if a == b => concordant += 1.0,
+                _ => discordant += 1.0,
+            }
+        }
+    }
+    let pairs_x: f64 = concordant + discordant + tied_x;
+    let pairs_y: f64 = concordant + discordant + tied_y;
+    (concordant - discordant) / (pairs_x * pairs_y).sqrt()
+}
+
+/// Correlation of two samples of the same length, NaN if either is constant.
+pub fn correlation(x: &[f64], y: &[f64], method: Correlation) -> Result<f64, CustomErrors> {
+    check_empty(x)?;
+    if x.len() != y.len() {
+        return Err(CustomErrors::Mismatch(MismatchError));
+    }
+    Ok(match method {
+        Correlation::Pearson => pearson(x, y),
+        Correlation::Spearman => pearson(&rank(x), &rank(y)),
+        Correlation::Kendall => kendall(x, y),
+    })
+}
+
+fn columns(x: &Matrix<f64>) -> Result<Vec<Vec<f64>>, CustomErrors> {
+    if x.m == 0 || x.n == 0 {
+        return Err(CustomErrors::EmptyVector(EmptyVectorError));
+    }
+    Ok((0..x.n)
+        .map(|j| x.rows.iter().map(|r| r[j]).collect())
+        .collect())
+}
+
+fn symmetric(n: usize, f: impl Fn(usize, usize) -> f64) -> Matrix<f64> {
+    let mut rows = vec![vec![0.0; n]; n];
+    for (i, j) in (0..n).flat_map(|i| (i..n).map(move |j| (i, j))) {
+        let value = f(i, j);
+        rows[i][j] = value;
+        rows[j][i] = value;
+    }
+    Matrix { rows, m: n, n }
+}
+
+/// Covariance between the columns of `x`, rows being observations.
+pub fn covariance_matrix(x: &Matrix<f64>, ddof: usize) -> Result<Matrix<f64>, CustomErrors> {
+    let columns = columns(x)?;
+    if x.m <= ddof {
+        return Err(CustomErrors::BadType(BadTypeError));
+    }
+    let means: Vec<f64> = columns
+        .iter()
+        .map(|c| c.iter().sum::<f64>() / x.m as f64)
+        .collect();
+    Ok(symmetric(x.n, |i, j| {
+        let sum: f64 = columns[i]
+            .iter()
+            .zip(&columns[j])
+            .map(|(a, b)| (a - means[i]) * (b - means[j]))
+            .sum();
+        sum / (x.m - ddof) as f64
+    }))
+}
+
+/// Correlation between the columns of `x`, rows being observations.
+pub fn correlation_matrix(
+    x: &Matrix<f64>,
+    method: Correlation,
+) -> Result<Matrix<f64>, CustomErrors> {
+    let mut columns = columns(x)?;
+    if method == Correlation::Spearman {
+        columns = columns.iter().map(|c| rank(c)).collect();
+    }
+    Ok(symmetric(x.n, |i, j| match method {
+        Correlation::Kendall => kendall(&columns[i], &columns[j]),
+        _ => pearson(&columns[i], &columns[j]),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        correlation, correlation_matrix, covariance_matrix, kurtosis, median, mode, quantile, rank,
+        skewness, variance, weighted_mean, weighted_variance, Correlation, Interpolation,
+        RunningStats,
+    };
+    use crate::matrix::Matrix;
+
+    #[test]
+    fn test_moments_and_quantiles() {
+        let x = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+        assert_eq!(variance(&x, 0).unwrap(), 4.0);
+        assert!((variance(&x, 1).unwrap() - 32.0 / 7.0).abs() < 1e-12);
+        assert!(variance(&[1.0], 1).is_err());
+        assert_eq!(median(&x).unwrap(), 4.5);
+        assert_eq!(mode(&x).unwrap(), 4.0);
+        assert_eq!(mode(&[3.0, 1.0, 3.0, 1.0]).unwrap(), 1.0);
+
+        // numpy.quantile([1, 2, 3, 4], 0.4, method=...)
+        let y = [4.0, 1.0, 3.0, 2.0];
+        let expected = [
+            (Interpolation::Linear, 2.2),
+            (Interpolation::Lower, 2.0),
+            (Interpolation::Higher, 3.0),
+            (Interpolation::Nearest, 2.0),
+            (Interpolation::Midpoint, 2.5),
+        ];
+        for (method, q) in expected {
+            assert!((quantile(&y, 0.4, method).unwrap() - q).abs() < 1e-12);
+        }
+
+        // scipy.stats.skew and kurtosis
+        let z = [1.0, 2.0, 3.0, 10.0];
+        assert!((skewness(&z).unwrap() - 1.0182337649086284).abs() < 1e-12);
+        assert!((kurtosis(&z).unwrap() + 0.7696).abs() < 1e-12);
+
+        assert_eq!(weighted_mean(&[1.0, 3.0], &[3.0, 1.0]).unwrap(), 1.5);
+        // same as variance([1, 1, 1, 3], 1)
+        assert_eq!(weighted_variance(&[1.0, 3.0], &[3.0, 1.0], 1).unwrap(), 1.0);
+        assert!(weighted_mean(&[1.0], &[-1.0]).is_err());
+    }
+
+    #[test]
+    fn test_running_stats() {
+        let x = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+        let mut left = RunningStats::new();
+        left.extend(x[..3].iter().copied());
+        let mut right = RunningStats::new();
+        right.extend(x[3..].iter().copied());
+        left.merge(&right);
+
+        assert_eq!(left.count(), 8);
+        assert_eq!(left.mean().unwrap(), 5.0);
+        assert!((left.variance(0).unwrap() - 4.0).abs() < 1e-12);
+        assert_eq!((left.min().unwrap(), left.max().unwrap()), (2.0, 9.0));
+        assert!(RunningStats::new().mean().is_err());
+    }
+
+    #[test]
+    fn test_correlation() {
+        assert_eq!(rank(&[10.0, 20.0, 10.0, 5.0]), vec![2.5, 4.0, 2.5, 1.0]);
+
+        // scipy.stats.pearsonr, spearmanr and kendalltau
+        let x = [1.0, 2.0, 3.0, 4.0, 5.0];
+        let y = [2.0, 1.0, 4.0, 3.0, 7.0];
+        let r = correlation(&x, &y, Correlation::Pearson).unwrap();
+        assert!((r - 0.824163383692134).abs() < 1e-12);
+        assert!((correlation(&x, &y, Correlation::Spearman).unwrap() - 0.8).abs() < 1e-12);
+        assert!((correlation(&x, &y, Correlation::Kendall).unwrap() - 0.6).abs() < 1e-12);
+        let tied = correlation(&[1.0, 1.0, 2.0], &[1.0, 2.0, 3.0], Correlation::Kendall);
+        assert!((tied.unwrap() - 0.8164965809277261).abs() < 1e-12);
+
+        let data =
+            Matrix::new(x.iter().zip(y.iter()).map(|(a, b)| vec![*a, *b]).collect()).unwrap();
+        let cov = covariance_matrix(&data, 1).unwrap();
+        assert_eq!(
+            (cov.rows[0][0], cov.rows[0][1], cov.rows[1][0]),
+            (2.5, 3.0, 3.0)
+        );
+        assert!((cov.rows[1][1] - 5.3).abs() < 1e-12);
+        let corr = correlation_matrix(&data, Correlation::Pearson).unwrap();
+        assert_eq!(corr.rows[0][0], 1.0);
+        assert!((corr.rows[1][0] - r).abs() < 1e-12);
+    }
+}
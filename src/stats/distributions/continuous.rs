@@ -0,0 +1,581 @@
+use std::f64::consts::PI;
+
+use rand::rngs::StdRng;
+use rand::Rng;
+
+use crate::{
+    error::{BadTypeError, CustomErrors},
+    special::{beta_inc, erfc, gamma_p, ln_beta, ln_gamma, normal_quantile, xlogy},
+};
+
+use super::{
+    check_positive, check_probability, invert_cdf, standard_gamma, standard_normal, Continuous,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Normal {
+    mean: f64,
+    std: f64,
+}
+
+impl Normal {
+    pub fn new(mean: f64, std: f64) -> Result<Normal, CustomErrors> {
+        if !mean.is_finite() {
+            return Err(CustomErrors::BadType(BadTypeError));
+        }
+        check_positive(std)?;
+        Ok(Normal { mean, std })
+    }
+
+    pub fn standard() -> Normal {
+        Normal {
+            mean: 0.0,
+            std: 1.0,
+        }
+    }
+
+    pub fn std(&self) -> f64 {
+        self.std
+    }
+}
+
+impl Continuous for Normal {
+    fn log_pdf(&self, x: f64) -> f64 {
+        let z = (x - self.mean) / self.std;
+        -0.5 * z * z - self.std.ln() - 0.5 * (2.0 * PI).ln()
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        0.5 * erfc(-(x - self.mean) / (self.std * 2f64.sqrt()))
+    }
+
+    fn quantile(&self, p: f64) -> Result<f64, CustomErrors> {
+        check_probability(p)?;
+        Ok(self.mean + self.std * normal_quantile(p))
+    }
+
+    fn mean(&self) -> f64 {
+        self.mean
+    }
+
+    fn variance(&self) -> f64 {
+        self.std * self.std
+    }
+
+    fn sample(&self, rng: &mut StdRng) -> f64 {
+        self.mean + self.std * standard_normal(rng)
+    }
+}
+
+/// Uniform on `[low, high)`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Uniform {
+    low: f64,
+    high: f64,
+}
+
+impl Uniform {
+    pub fn new(low: f64, high: f64) -> Result<Uniform, CustomErrors> {
+        if !low.is_finite() || !high.is_finite() || low >= high {
+            return Err(CustomErrors::BadType(BadTypeError));
+        }
+        Ok(Uniform { low, high })
+    }
+}
+
+impl Continuous for Uniform {
+    fn log_pdf(&self, x: f64) -> f64 {
+        if x < self.low || x > self.high {
+            return f64::NEG_INFINITY;
+        }
+        -(self.high - self.low).ln()
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        ((x - self.low) / (self.high - self.low)).clamp(0.0, 1.0)
+    }
+
+    fn quantile(&self, p: f64) -> Result<f64, CustomErrors> {
+        check_probability(p)?;
+        Ok(self.low + p * (self.high - self.low))
+    }
+
+    fn mean(&self) -> f64 {
+        (self.low + self.high) / 2.0
+    }
+
+    fn variance(&self) -> f64 {
+        (self.high - self.low).powi(2) / 12.0
+    }
+
+    fn sample(&self, rng: &mut StdRng) -> f64 {
+        self.low + rng.gen::<f64>() * (self.high - self.low)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Exponential {
+    rate: f64,
+}
+
+impl Exponential {
+    pub fn new(rate: f64) -> Result<Exponential, CustomErrors> {
+        check_positive(rate)?;
+        Ok(Exponential { rate })
+    }
+}
+
+impl Continuous for Exponential {
+    fn log_pdf(&self, x: f64) -> f64 {
+        if x < 0.0 {
+            return f64::NEG_INFINITY;
+        }
+        self.rate.ln() - self.rate * x
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            return 0.0;
+        }
+        -(-self.rate * x).exp_m1()
+    }
+
+    fn quantile(&self, p: f64) -> Result<f64, CustomErrors> {
+        check_probability(p)?;
+        Ok(-(-p).ln_1p() / self.rate)
+    }
+
+    fn mean(&self) -> f64 {
+        1.0 / self.rate
+    }
+
+    fn variance(&self) -> f64 {
+        1.0 / (self.rate * self.rate)
+    }
+
+    fn sample(&self, rng: &mut StdRng) -> f64 {
+        -(1.0 - rng.gen::<f64>()).ln() / self.rate
+    }
+}
+
+/// Gamma distribution with a `shape` and a `scale`, the mean is `shape * scale`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Gamma {
+    shape: f64,
+    scale: f64,
+}
+
+impl Gamma {
+    pub fn new(shape: f64, scale: f64) -> Result<Gamma, CustomErrors> {
+        check_positive(shape)?;
+        check_positive(scale)?;
+        Ok(Gamma { shape, scale })
+    }
+}
+
+impl Continuous for Gamma {
+    fn log_pdf(&self, x: f64) -> f64 {
+        if x < 0.0 {
+            return f64::NEG_INFINITY;
+        }
+        xlogy(self.shape - 1.0, x)
+            - x / self.scale
+            - ln_gamma(self.shape)
+            - self.shape * self.scale.ln()
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            return 0.0;
+        }
+        gamma_p(self.shape, x / self.scale)
+    }
+
+    fn quantile(&self, p: f64) -> Result<f64, CustomErrors> {
+        check_probability(p)?;
+        let x = invert_cdf(|x| gamma_p(self.shape, x), p, 0.0, f64::INFINITY);
+        Ok(x * self.scale)
+    }
+
+    fn mean(&self) -> f64 {
+        self.shape * self.scale
+    }
+
+    fn variance(&self) -> f64 {
+        self.shape * self.scale * self.scale
+    }
+
+    fn sample(&self, rng: &mut StdRng) -> f64 {
+        standard_gamma(self.shape, rng) * self.scale
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Beta {
+    alpha: f64,
+    beta: f64,
+}
+
+impl Beta {
+    pub fn new(alpha: f64, beta: f64) -> Result<Beta, CustomErrors> {
+        check_positive(alpha)?;
+        check_positive(beta)?;
+        Ok(Beta { alpha, beta })
+    }
+}
+
+impl Continuous for Beta {
+    fn log_pdf(&self, x: f64) -> f64 {
+        if !(0.0..=1.0).contains(&x) {
+            return f64::NEG_INFINITY;
+        }
+        xlogy(self.alpha - 1.0, x) + xlogy(self.beta - 1.0, 1.0 - x)
+            - ln_beta(self.alpha, self.beta)
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        beta_inc(self.alpha, self.beta, x.clamp(0.0, 1.0))
+    }
+
+    fn quantile(&self, p: f64) -> Result<f64, CustomErrors> {
+        check_probability(p)?;
+        Ok(invert_cdf(|x| self.cdf(x), p, 0.0, 1.0))
+    }
+
+    fn mean(&self) -> f64 {
+        self.alpha / (self.alpha + self.beta)
+    }
+
+    fn variance(&self) -> f64 {
+        let total = self.alpha + self.beta;
+        self.alpha * self.beta / (total * total * (total + 1.0))
+    }
+
+    fn sample(&self, rng: &mut StdRng) -> f64 {
+        let x = standard_gamma(self.alpha, rng);
+        let y = standard_gamma(self.beta, rng);
+        x / (x + y)
+    }
+}
+
+/// Chi-squared distribution with `df` degrees of freedom, `Gamma(df / 2, 2)`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChiSquared {
+    df: f64,
+    gamma: Gamma,
+}
+
+impl ChiSquared {
+    pub fn new(df: f64) -> Result<ChiSquared, CustomErrors> {
+        Ok(ChiSquared {
+            df,
+            gamma: Gamma::new(df / 2.0, 2.0)?,
+        })
+    }
+
+    pub fn df(&self) -> f64 {
+        self.df
+    }
+}
+
+impl Continuous for ChiSquared {
+    fn log_pdf(&self, x: f64) -> f64 {
+        self.gamma.log_pdf(x)
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        self.gamma.cdf(x)
+    }
+
+    fn quantile(&self, p: f64) -> Result<f64, CustomErrors> {
+        self.gamma.quantile(p)
+    }
+
+    fn mean(&self) -> f64 {
+        self.gamma.mean()
+    }
+
+    fn variance(&self) -> f64 {
+        self.gamma.variance()
+    }
+
+    fn sample(&self, rng: &mut StdRng) -> f64 {
+        self.gamma.sample(rng)
+    }
+}
+
+/// Student's t distribution with `df` degrees of freedom.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StudentT {
+    df: f64,
+}
+
+impl StudentT {
+    pub fn new(df: f64) -> Result<StudentT, CustomErrors> {
+        check_positive(df)?;
+        Ok(StudentT { df })
+    }
+
+    pub fn df(&self) -> f64 {
+        self.df
+    }
+}
+
+impl Continuous for StudentT {
+    fn log_pdf(&self, x: f64) -> f64 {
+        let df = self.df;
+        ln_gamma((df + 1.0) / 2.0)
+            - ln_gamma(df / 2.0)
+            - 0.5 * (df * PI).ln()
+            - (df + 1.0) / 2.0 * (x * x / df).ln_1p()
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        if x.is_infinite() {
+            return if x > 0.0 { 1.0 } else { 0.0 };
+        }
+        let tail = 0.5 * beta_inc(self.df / 2.0, 0.5, self.df / (self.df + x * x));
+        if x > 0.0 {
+            1.0 - tail
+        } else {
+            tail
+        }
+    }
+
+    fn quantile(&self, p: f64) -> Result<f64, CustomErrors> {
+        check_probability(p)?;
+        // symmetric, invert the lower half where the cdf is most accurate
+        if p > 0.5 {
+            return Ok(-self.quantile(1.0 - p)?);
+        }
+        if p == 0.0 {
+            return Ok(f64::NEG_INFINITY);
+        }
+        Ok(invert_cdf(|x| self.cdf(x), p, f64::NEG_INFINITY, 0.0))
+    }
+
+    /// NaN for `df <= 1`.
+    fn mean(&self) -> f64 {
+        if self.df > 1.0 {
+            0.0
+        } else {
+            f64::NAN
+        }
+    }
+
+    /// Infinite for `1 < df <= 2`, NaN for `df <= 1`.
+    fn variance(&self) -> f64 {
+        if self.df > 2.0 {
+            self.df / (self.df - 2.0)
+        } else if self.df > 1.0 {
+            f64::INFINITY
+        } else {
+            f64::NAN
+        }
+    }
+
+    fn sample(&self, rng: &mut StdRng) -> f64 {
+        let z = standard_normal(rng);
+        let v = 2.0 * standard_gamma(self.df / 2.0, rng);
+        z / (v / self.df).sqrt()
+    }
+}
+
+/// F distribution, the ratio of two scaled chi-squared variables with `d1` and
+/// `d2` degrees of freedom.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FisherF {
+    d1: f64,
+    d2: f64,
+}
+
+impl FisherF {
+    pub fn new(d1: f64, d2: f64) -> Result<FisherF, CustomErrors> {
+        check_positive(d1)?;
+        check_positive(d2)?;
+        Ok(FisherF { d1, d2 })
+    }
+}
+
+impl Continuous for FisherF {
+    fn log_pdf(&self, x: f64) -> f64 {
+        if x < 0.0 {
+            return f64::NEG_INFINITY;
+        }
+        let (d1, d2) = (self.d1, self.d2);
+        0.5 * (xlogy(d1, d1 * x) + d2 * d2.ln() - (d1 + d2) * (d1 * x + d2).ln())
+            - x.ln()
+            - ln_beta(d1 / 2.0, d2 / 2.0)
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            return 0.0;
+        }
+        if x.is_infinite() {
+            return 1.0;
+        }
+        let y = self.d1 * x / (self.d1 * x + self.d2);
+        beta_inc(self.d1 / 2.0, self.d2 / 2.0, y)
+    }
+
+    fn quantile(&self, p: f64) -> Result<f64, CustomErrors> {
+        check_probability(p)?;
+        if p == 1.0 {
+            return Ok(f64::INFINITY);
+        }
+        Ok(invert_cdf(|x| self.cdf(x), p, 0.0, f64::INFINITY))
+    }
+
+    /// NaN for `d2 <= 2`.
+    fn mean(&self) -> f64 {
+        if self.d2 > 2.0 {
+            self.d2 / (self.d2 - 2.0)
+        } else {
+            f64::NAN
+        }
+    }
+
+    /// NaN for `d2 <= 4`.
+    fn variance(&self) -> f64 {
+        let (d1, d2) = (self.d1, self.d2);
+        if d2 > 4.0 {
+            2.0 * d2 * d2 * (d1 + d2 - 2.0) / (d1 * (d2 - 2.0).powi(2) * (d2 - 4.0))
+        } else {
+            f64::NAN
+        }
+    }
+
+    fn sample(&self, rng: &mut StdRng) -> f64 {
+        let x = standard_gamma(self.d1 / 2.0, rng) / self.d1;
+        let y = standard_gamma(self.d2 / 2.0, rng) / self.d2;
+        x / y
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Beta, ChiSquared, Exponential, FisherF, Gamma, Normal, StudentT, Uniform};
+    use crate::stats::distributions::Continuous;
+
+    fn close(a: f64, b: f64, tol: f64) -> bool {
+        (a - b).abs() <= tol * b.abs().max(1.0)
+    }
+
+    #[test]
+    fn test_densities_and_quantiles() {
+        // (distribution, x, pdf(x), cdf(x), p, quantile(p)) from mpmath
+        let cases: Vec<(Box<dyn Continuous>, [f64; 5])> = vec![
+            (
+                Box::new(Normal::new(1.0, 2.0).unwrap()),
+                [
+                    2.0,
+                    0.17603266338214974,
+                    0.6914624612740131,
+                    0.975,
+                    4.919927969080108,
+                ],
+            ),
+            (
+                Box::new(Uniform::new(-1.0, 3.0).unwrap()),
+                [0.0, 0.25, 0.25, 0.5, 1.0],
+            ),
+            (
+                Box::new(Exponential::new(2.0).unwrap()),
+                [
+                    0.5,
+                    0.7357588823428847,
+                    0.6321205588285577,
+                    0.99,
+                    std::f64::consts::LN_10,
+                ],
+            ),
+            (
+                Box::new(Gamma::new(2.0, 3.0).unwrap()),
+                [
+                    6.0,
+                    0.09022352215774178,
+                    0.5939941502901619,
+                    0.05,
+                    1.0660845320959862,
+                ],
+            ),
+            (
+                Box::new(Beta::new(2.0, 3.0).unwrap()),
+                [0.4, 1.728, 0.5248, 0.9, 0.6795394162781817],
+            ),
+            (
+                Box::new(ChiSquared::new(3.0).unwrap()),
+                [
+                    2.0,
+                    0.2075537487102974,
+                    0.4275932955291202,
+                    0.95,
+                    7.814727903251178,
+                ],
+            ),
+            (
+                Box::new(StudentT::new(10.0).unwrap()),
+                [
+                    -2.0,
+                    0.0611457663212182,
+                    0.03669401738537018,
+                    0.975,
+                    2.2281388519862742,
+                ],
+            ),
+            (
+                Box::new(FisherF::new(5.0, 10.0).unwrap()),
+                [
+                    1.5,
+                    0.2864586266610567,
+                    0.7267134845242297,
+                    0.95,
+                    3.325834530413011,
+                ],
+            ),
+        ];
+        for (dist, [x, pdf, cdf, p, q]) in cases {
+            assert!(close(dist.pdf(x), pdf, 1e-12), "pdf {}", dist.pdf(x));
+            assert!(close(dist.cdf(x), cdf, 1e-12), "cdf {}", dist.cdf(x));
+            let quantile = dist.quantile(p).unwrap();
+            assert!(close(quantile, q, 1e-10), "quantile {}", quantile);
+            assert!(close(dist.cdf(quantile), p, 1e-12));
+        }
+
+        assert!(Normal::new(0.0, 0.0).is_err());
+        assert!(Uniform::new(1.0, 1.0).is_err());
+        assert!(Gamma::new(1.0, 1.0).unwrap().quantile(1.5).is_err());
+        assert!(StudentT::new(1.0).unwrap().variance().is_nan());
+    }
+
+    #[test]
+    fn test_sampling() {
+        let dists: Vec<Box<dyn Continuous>> = vec![
+            Box::new(Normal::new(1.0, 2.0).unwrap()),
+            Box::new(Uniform::new(-1.0, 3.0).unwrap()),
+            Box::new(Exponential::new(2.0).unwrap()),
+            Box::new(Gamma::new(0.5, 3.0).unwrap()),
+            Box::new(Beta::new(2.0, 3.0).unwrap()),
+            Box::new(ChiSquared::new(3.0).unwrap()),
+            Box::new(StudentT::new(10.0).unwrap()),
+            Box::new(FisherF::new(5.0, 10.0).unwrap()),
+        ];
+        for dist in dists {
+            let samples = dist.sample_n(20000, 7);
+            assert_eq!(samples, dist.sample_n(20000, 7));
+            let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+            // within 5 standard errors
+            let error = (dist.variance() / samples.len() as f64).sqrt();
+            assert!((mean - dist.mean()).abs() < 5.0 * error, "mean {}", mean);
+        }
+    }
+}
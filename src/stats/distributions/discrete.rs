@@ -0,0 +1,312 @@
+use crate::{
+    error::{BadTypeError, CustomErrors, EmptyVectorError},
+    special::{beta_inc, gamma_q, ln_gamma, xlogy},
+};
+
+use super::{check_probability, search_cdf, Discrete};
+
+/// `ln(n choose k)`.
+fn ln_choose(n: u64, k: u64) -> f64 {
+    ln_gamma(n as f64 + 1.0) - ln_gamma(k as f64 + 1.0) - ln_gamma((n - k) as f64 + 1.0)
+}
+
+/// 1 with probability `p`, 0 otherwise.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Bernoulli {
+    p: f64,
+}
+
+impl Bernoulli {
+    pub fn new(p: f64) -> Result<Bernoulli, CustomErrors> {
+        check_probability(p)?;
+        Ok(Bernoulli { p })
+    }
+}
+
+impl Discrete for Bernoulli {
+    fn log_pmf(&self, k: u64) -> f64 {
+        match k {
+            0 => (1.0 - self.p).ln(),
+            1 => self.p.ln(),
+            _ => f64::NEG_INFINITY,
+        }
+    }
+
+    fn cdf(&self, k: u64) -> f64 {
+        if k == 0 {
+            1.0 - self.p
+        } else {
+            1.0
+        }
+    }
+
+    fn quantile(&self, p: f64) -> Result<u64, CustomErrors> {
+        check_probability(p)?;
+        Ok(search_cdf(|k| self.cdf(k), p, 1))
+    }
+
+    fn mean(&self) -> f64 {
+        self.p
+    }
+
+    fn variance(&self) -> f64 {
+        self.p * (1.0 - self.p)
+    }
+}
+
+/// Number of successes in `n` independent trials with success probability `p`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Binomial {
+    n: u64,
+    p: f64,
+}
+
+impl Binomial {
+    pub fn new(n: u64, p: f64) -> Result<Binomial, CustomErrors> {
+        check_probability(p)?;
+        Ok(Binomial { n, p })
+    }
+}
+
+impl Discrete for Binomial {
+    fn log_pmf(&self, k: u64) -> f64 {
+        if k > self.n {
+            return f64::NEG_INFINITY;
+        }
+        ln_choose(self.n, k) + xlogy(k as f64, self.p) + xlogy((self.n - k) as f64, 1.0 - self.p)
+    }
+
+    fn cdf(&self, k: u64) -> f64 {
+        if k >= self.n {
+            return 1.0;
+        }
+        if self.p == 0.0 {
+            return 1.0;
+        }
+        if self.p == 1.0 {
+            return 0.0;
+        }
+        beta_inc((self.n - k) as f64, k as f64 + 1.0, 1.0 - self.p)
+    }
+
+    fn quantile(&self, p: f64) -> Result<u64, CustomErrors> {
+        check_probability(p)?;
+        Ok(search_cdf(|k| self.cdf(k), p, self.n))
+    }
+
+    fn mean(&self) -> f64 {
+        self.n as f64 * self.p
+    }
+
+    fn variance(&self) -> f64 {
+        self.n as f64 * self.p * (1.0 - self.p)
+    }
+}
+
+/// Number of events in an interval when they happen at `rate` on average.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Poisson {
+    rate: f64,
+}
+
+impl Poisson {
+    pub fn new(rate: f64) -> Result<Poisson, CustomErrors> {
+        if !rate.is_finite() || rate < 0.0 {
+            return Err(CustomErrors::BadType(BadTypeError));
+        }
+        Ok(Poisson { rate })
+    }
+}
+
+impl Discrete for Poisson {
+    fn log_pmf(&self, k: u64) -> f64 {
+        xlogy(k as f64, self.rate) - self.rate - ln_gamma(k as f64 + 1.0)
+    }
+
+    fn cdf(&self, k: u64) -> f64 {
+        if self.rate == 0.0 {
+            return 1.0;
+        }
+        gamma_q(k as f64 + 1.0, self.rate)
+    }
+
+    /// `u64::MAX` for `p = 1`, the support has no upper bound.
+    fn quantile(&self, p: f64) -> Result<u64, CustomErrors> {
+        check_probability(p)?;
+        if p == 1.0 && self.rate > 0.0 {
+            return Ok(u64::MAX);
+        }
+        Ok(search_cdf(|k| self.cdf(k), p, u64::MAX))
+    }
+
+    fn mean(&self) -> f64 {
+        self.rate
+    }
+
+    fn variance(&self) -> f64 {
+        self.rate
+    }
+}
+
+/// One of `0..k` with the given probabilities.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Categorical {
+    probs: Vec<f64>,
+    cumulative: Vec<f64>,
+}
+
+impl Categorical {
+    /// Takes non-negative weights and normalizes them to sum to 1.
+    pub fn new(weights: &[f64]) -> Result<Categorical, CustomErrors> {
+        if weights.is_empty() {
+            return Err(CustomErrors::EmptyVector(EmptyVectorError));
+        }
+        let total: f64 = weights.iter().sum();
+        if weights.iter().any(|w| !w.is_finite() || *w < 0.0) || total <= 0.0 {
+            return Err(CustomErrors::BadType(BadTypeError));
+        }
+
+        let probs: Vec<f64> = weights.iter().map(|w| w / total).collect();
+        let mut cumulative = vec![];
+        let mut sum = 0.0;
+        for p in &probs {
+            sum += p;
+            cumulative.push(sum);
+        }
+        // rounding must not leave the last category out of reach
+        *cumulative.last_mut().unwrap() = 1.0;
+        Ok(Categorical { probs, cumulative })
+    }
+
+    pub fn probs(&self) -> &Vec<f64> {
+        &self.probs
+    }
+}
+
+impl Discrete for Categorical {
+    fn log_pmf(&self, k: u64) -> f64 {
+        match self.probs.get(k as usize) {
+            Some(p) => p.ln(),
+            None => f64::NEG_INFINITY,
+        }
+    }
+
+    fn cdf(&self, k: u64) -> f64 {
+        match self.cumulative.get(k as usize) {
+            Some(c) => *c,
+            None => 1.0,
+        }
+    }
+
+    fn quantile(&self, p: f64) -> Result<u64, CustomErrors> {
+        check_probability(p)?;
+        // zero probability categories are never picked, even for p = 0
+        let k = self.cumulative.partition_point(|c| *c < p);
+        let k = (k..self.probs.len())
+            .find(|k| self.probs[*k] > 0.0)
+            .unwrap_or(k);
+        Ok(k as u64)
+    }
+
+    fn mean(&self) -> f64 {
+        self.probs
+            .iter()
+            .enumerate()
+            .map(|(k, p)| k as f64 * p)
+            .sum()
+    }
+
+    fn variance(&self) -> f64 {
+        let mean = self.mean();
+        self.probs
+            .iter()
+            .enumerate()
+            .map(|(k, p)| (k as f64 - mean).powi(2) * p)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Bernoulli, Binomial, Categorical, Poisson};
+    use crate::stats::distributions::Discrete;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() <= 1e-12 * b.abs().max(1.0)
+    }
+
+    #[test]
+    fn test_pmf_cdf_quantile() {
+        let binomial = Binomial::new(10, 0.3).unwrap();
+        assert!(close(binomial.pmf(3), 0.266827932));
+        assert!(close(binomial.cdf(3), 0.6496107184));
+        assert_eq!(binomial.quantile(0.6).unwrap(), 3);
+        assert_eq!(binomial.quantile(0.65).unwrap(), 4);
+        assert_eq!(binomial.pmf(11), 0.0);
+        assert!(close(Binomial::new(5, 1.0).unwrap().pmf(5), 1.0));
+
+        let poisson = Poisson::new(4.0).unwrap();
+        assert!(close(poisson.pmf(2), 8.0 * (-4.0f64).exp()));
+        assert!(close(poisson.cdf(2), 13.0 * (-4.0f64).exp()));
+        assert_eq!(poisson.quantile(0.5).unwrap(), 4);
+
+        let bernoulli = Bernoulli::new(0.25).unwrap();
+        assert_eq!((bernoulli.pmf(1), bernoulli.cdf(0)), (0.25, 0.75));
+        assert!(Bernoulli::new(1.5).is_err());
+
+        let categorical = Categorical::new(&[1.0, 0.0, 3.0]).unwrap();
+        assert_eq!(categorical.probs(), &vec![0.25, 0.0, 0.75]);
+        assert_eq!(categorical.quantile(0.25).unwrap(), 0);
+        assert_eq!(categorical.quantile(0.26).unwrap(), 2);
+        assert_eq!(categorical.mean(), 1.5);
+    }
+
+    #[test]
+    fn test_quantile_search() {
+        // the first k reaching p, as a linear search finds it
+        let linear = |dist: &dyn Discrete, p: f64| (0..).find(|k| dist.cdf(*k) >= p).unwrap();
+        let dists: Vec<Box<dyn Discrete>> = vec![
+            Box::new(Binomial::new(37, 0.4).unwrap()),
+            Box::new(Binomial::new(0, 0.4).unwrap()),
+            Box::new(Poisson::new(12.5).unwrap()),
+            Box::new(Poisson::new(0.0).unwrap()),
+        ];
+        for dist in dists {
+            for p in (0..100).map(|k| k as f64 / 100.0).chain([0.3, 0.999999]) {
+                assert_eq!(
+                    dist.quantile(p).unwrap(),
+                    linear(dist.as_ref(), p),
+                    "p {}",
+                    p
+                );
+            }
+        }
+
+        let binomial = Binomial::new(1_000_000, 0.5).unwrap();
+        assert_eq!(binomial.quantile(0.5).unwrap(), 500_000);
+        assert_eq!(binomial.sample_n(200, 0).len(), 200);
+        let poisson = Poisson::new(1e5).unwrap();
+        assert_eq!(poisson.quantile(0.999).unwrap(), 100_979);
+    }
+
+    #[test]
+    fn test_sampling() {
+        let dists: Vec<Box<dyn Discrete>> = vec![
+            Box::new(Bernoulli::new(0.3).unwrap()),
+            Box::new(Binomial::new(20, 0.3).unwrap()),
+            Box::new(Poisson::new(3.5).unwrap()),
+            Box::new(Categorical::new(&[0.2, 0.5, 0.3]).unwrap()),
+        ];
+        for dist in dists {
+            let samples = dist.sample_n(20000, 3);
+            assert_eq!(samples, dist.sample_n(20000, 3));
+            let mean = samples.iter().sum::<u64>() as f64 / samples.len() as f64;
+            let error = (dist.variance() / samples.len() as f64).sqrt();
+            assert!((mean - dist.mean()).abs() < 5.0 * error, "mean {}", mean);
+        }
+    }
+}
@@ -0,0 +1,164 @@
+//! Probability distributions with densities, distribution functions,
+//! quantiles, moments and seeded sampling.
+
+pub mod continuous;
+pub mod discrete;
+pub mod multivariate;
+
+use std::f64::consts::PI;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::error::{BadTypeError, CustomErrors};
+
+pub use continuous::{Beta, ChiSquared, Exponential, FisherF, Gamma, Normal, StudentT, Uniform};
+pub use discrete::{Bernoulli, Binomial, Categorical, Poisson};
+pub use multivariate::MultivariateNormal;
+
+pub trait Continuous {
+    /// Natural log of the density, `-inf` outside the support.
+    fn log_pdf(&self, x: f64) -> f64;
+    fn cdf(&self, x: f64) -> f64;
+    /// Inverse of `cdf`, `p` must be in `[0, 1]`.
+    fn quantile(&self, p: f64) -> Result<f64, CustomErrors>;
+    fn mean(&self) -> f64;
+    fn variance(&self) -> f64;
+    fn sample(&self, rng: &mut StdRng) -> f64;
+
+    fn pdf(&self, x: f64) -> f64 {
+        self.log_pdf(x).exp()
+    }
+
+    fn sample_n(&self, n: usize, seed: u64) -> Vec<f64> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        (0..n).map(|_| self.sample(&mut rng)).collect()
+    }
+}
+
+/// Distributions over `0, 1, 2, ..`.
+pub trait Discrete {
+    /// Natural log of the probability of `k`, `-inf` outside the support.
+    fn log_pmf(&self, k: u64) -> f64;
+    /// Probability of a value `<= k`.
+    fn cdf(&self, k: u64) -> f64;
+    /// Smallest `k` with `cdf(k) >= p`, `p` must be in `[0, 1]`.
+    fn quantile(&self, p: f64) -> Result<u64, CustomErrors>;
+    fn mean(&self) -> f64;
+    fn variance(&self) -> f64;
+
+    fn pmf(&self, k: u64) -> f64 {
+        self.log_pmf(k).exp()
+    }
+
+    /// Inverse transform sampling, one uniform draw per sample.
+    fn sample(&self, rng: &mut StdRng) -> u64 {
+        // quantile only fails outside [0, 1]
+        self.quantile(rng.gen::<f64>()).unwrap()
+    }
+
+    fn sample_n(&self, n: usize, seed: u64) -> Vec<u64> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        (0..n).map(|_| self.sample(&mut rng)).collect()
+    }
+}
+
+fn check_probability(p: f64) -> Result<(), CustomErrors> {
+    if p.is_nan() || !(0.0..=1.0).contains(&p) {
+        return Err(CustomErrors::BadType(BadTypeError));
+    }
+    Ok(())
+}
+
+/// Finite and positive, for scales, rates and shapes.
+fn check_positive(x: f64) -> Result<(), CustomErrors> {
+    if !x.is_finite() || x <= 0.0 {
+        return Err(CustomErrors::BadType(BadTypeError));
+    }
+    Ok(())
+}
+
+/// Inverts an increasing `cdf` by bisection, starting from `[lo, hi]` and
+/// doubling an infinite bound until it brackets `p`.
+fn invert_cdf(cdf: impl Fn(f64) -> f64, p: f64, mut lo: f64, mut hi: f64) -> f64 {
+    if hi.is_infinite() {
+        hi = lo.max(0.0) + 1.0;
+        while cdf(hi) < p && hi.is_finite() {
+            hi *= 2.0;
+        }
+    }
+    if lo.is_infinite() {
+        lo = hi.min(0.0) - 1.0;
+        while cdf(lo) > p && lo.is_finite() {
+            lo *= 2.0;
+        }
+    }
+    for _ in 0..200 {
+        let mid = lo + (hi - lo) / 2.0;
+        if mid <= lo || mid >= hi {
+            break;
+        }
+        if cdf(mid) < p {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    lo + (hi - lo) / 2.0
+}
+
+/// Smallest `k` in `0..=max` with `cdf(k) >= p`, `max` if there is none. The
+/// bound is doubled until it passes `p`, then bisected, so large supports take
+/// logarithmically many `cdf` calls.
+fn search_cdf(cdf: impl Fn(u64) -> f64, p: f64, max: u64) -> u64 {
+    if max == 0 || cdf(0) >= p {
+        return 0;
+    }
+    // cdf(lo) < p throughout
+    let mut lo = 0;
+    let mut hi = 1;
+    while hi < max && cdf(hi) < p {
+        lo = hi;
+        hi = hi.saturating_mul(2);
+    }
+    let mut hi = hi.min(max);
+    while hi - lo > 1 {
+        let mid = lo + (hi - lo) / 2;
+        if cdf(mid) < p {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    hi
+}
+
+/// Standard normal sample by the Box-Muller transform.
+pub(crate) fn standard_normal(rng: &mut StdRng) -> f64 {
+    // keep away from ln(0)
+    let u: f64 = 1.0 - rng.gen::<f64>();
+    let v: f64 = rng.gen();
+    (-2.0 * u.ln()).sqrt() * (2.0 * PI * v).cos()
+}
+
+/// Gamma sample with unit scale, Marsaglia and Tsang's method.
+fn standard_gamma(shape: f64, rng: &mut StdRng) -> f64 {
+    if shape < 1.0 {
+        // Gamma(a) = Gamma(a + 1) * U^(1 / a)
+        let u: f64 = 1.0 - rng.gen::<f64>();
+        return standard_gamma(shape + 1.0, rng) * u.powf(1.0 / shape);
+    }
+    let d = shape - 1.0 / 3.0;
+    let c = 1.0 / (9.0 * d).sqrt();
+    loop {
+        let x = standard_normal(rng);
+        let v = (1.0 + c * x).powi(3);
+        if v <= 0.0 {
+            continue;
+        }
+        let u: f64 = rng.gen();
+        if u.ln() < 0.5 * x * x + d - d * v + d * v.ln() {
+            return d * v;
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use std::f64::consts::PI;
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use crate::{
+    error::{CustomErrors, EmptyVectorError, MismatchError},
+    matrix::{
+        decomposition::{cholesky, solve_lower_triangular},
+        Matrix,
+    },
+};
+
+use super::standard_normal;
+
+/// Multivariate normal distribution, sampled and evaluated through the
+/// Cholesky factor of its covariance.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MultivariateNormal {
+    mean: Vec<f64>,
+    covariance: Matrix<f64>,
+    factor: Matrix<f64>,
+}
+
+impl MultivariateNormal {
+    /// Fails unless `covariance` is symmetric positive definite with the
+    /// dimension of `mean`.
+    pub fn new(
+        mean: Vec<f64>,
+        covariance: &Matrix<f64>,
+    ) -> Result<MultivariateNormal, CustomErrors> {
+        if mean.is_empty() {
+            return Err(CustomErrors::EmptyVector(EmptyVectorError));
+        }
+        if covariance.m != mean.len() || covariance.n != mean.len() {
+            return Err(CustomErrors::Mismatch(MismatchError));
+        }
+        Ok(MultivariateNormal {
+            factor: cholesky(covariance)?,
+            covariance: covariance.clone(),
+            mean,
+        })
+    }
+
+    pub fn mean(&self) -> &Vec<f64> {
+        &self.mean
+    }
+
+    pub fn covariance(&self) -> &Matrix<f64> {
+        &self.covariance
+    }
+
+    pub fn log_pdf(&self, x: &[f64]) -> Result<f64, CustomErrors> {
+        if x.len() != self.mean.len() {
+            return Err(CustomErrors::Mismatch(MismatchError));
+        }
+        let diff: Vec<f64> = x.iter().zip(&self.mean).map(|(a, b)| a - b).collect();
+        let z = solve_lower_triangular(&self.factor, &diff)?;
+        let maha: f64 = z.iter().map(|v| v * v).sum();
+        let log_det: f64 = (0..self.mean.len())
+            .map(|i| 2.0 * self.factor.rows[i][i].ln())
+            .sum();
+        Ok(-0.5 * (self.mean.len() as f64 * (2.0 * PI).ln() + log_det + maha))
+    }
+
+    pub fn pdf(&self, x: &[f64]) -> Result<f64, CustomErrors> {
+        Ok(self.log_pdf(x)?.exp())
+    }
+
+    /// `mean + L z` for the Cholesky factor `L` and standard normal `z`.
+    pub fn sample(&self, rng: &mut StdRng) -> Vec<f64> {
+        let z: Vec<f64> = (0..self.mean.len()).map(|_| standard_normal(rng)).collect();
+        self.factor
+            .rows
+            .iter()
+            .zip(&self.mean)
+            .map(|(row, mu)| mu + row.iter().zip(&z).map(|(l, v)| l * v).sum::<f64>())
+            .collect()
+    }
+
+    /// `n` samples as the rows of a matrix.
+    pub fn sample_n(&self, n: usize, seed: u64) -> Matrix<f64> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        Matrix {
+            rows: (0..n).map(|_| self.sample(&mut rng)).collect(),
+            m: n,
+            n: self.mean.len(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MultivariateNormal;
+    use crate::{
+        error::{CustomErrors, MismatchError},
+        matrix::Matrix,
+        stats::descriptive::covariance_matrix,
+    };
+
+    #[test]
+    fn test_multivariate_normal() {
+        let cov = Matrix::new(vec![vec![2.0, 0.6], vec![0.6, 1.0]]).unwrap();
+        let dist = MultivariateNormal::new(vec![1.0, -1.0], &cov).unwrap();
+
+        // mpmath, exp(-x' S^-1 x / 2) / (2 pi sqrt(det S))
+        assert!((dist.pdf(&[1.5, 0.0]).unwrap() - 0.07514963890270896).abs() < 1e-14);
+        assert_eq!(
+            dist.log_pdf(&[1.0]).unwrap_err(),
+            CustomErrors::Mismatch(MismatchError)
+        );
+
+        let samples = dist.sample_n(20000, 5);
+        assert_eq!(samples, dist.sample_n(20000, 5));
+        let sample_cov = covariance_matrix(&samples, 1).unwrap();
+        for (row, expected) in sample_cov.rows.iter().zip(&cov.rows) {
+            for (a, b) in row.iter().zip(expected) {
+                assert!((a - b).abs() < 0.05);
+            }
+        }
+
+        let singular = Matrix::new(vec![vec![1.0, 1.0], vec![1.0, 1.0]]).unwrap();
+        assert!(MultivariateNormal::new(vec![0.0, 0.0], &singular).is_err());
+    }
+}
@@ -0,0 +1,5 @@
+//! Statistics beyond the `sum`, `mean` and `stddev` of `vector::operations`.
+
+pub mod descriptive;
+pub mod distributions;
+pub mod tests;
@@ -0,0 +1,533 @@
+//! Hypothesis tests. P-values are two-sided where the test has two sides.
+
+use crate::{
+    error::{BadTypeError, CustomErrors, EmptyVectorError, MismatchError},
+    matrix::Matrix,
+    special::{beta_inc, gamma_q, normal_cdf, normal_quantile},
+    stats::{
+        descriptive::{correlation, mean, rank, variance, Correlation},
+        distributions::Continuous,
+    },
+};
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TestResult {
+    pub statistic: f64,
+    pub p_value: f64,
+    /// Degrees of freedom of the reference distribution, two for the F
+    /// distribution and none for tests without one.
+    pub df: Vec<f64>,
+}
+
+/// At least `n` values.
+fn check_len(x: &[f64], n: usize) -> Result<(), CustomErrors> {
+    if x.is_empty() {
+        return Err(CustomErrors::EmptyVector(EmptyVectorError));
+    }
+    if x.len() < n {
+        return Err(CustomErrors::BadType(BadTypeError));
+    }
+    Ok(())
+}
+
+/// Two-sided p-value of Student's t with `df` degrees of freedom.
+fn t_p_value(t: f64, df: f64) -> f64 {
+    if t.is_nan() {
+        return f64::NAN;
+    }
+    beta_inc(df / 2.0, 0.5, df / (df + t * t))
+}
+
+fn chi2_sf(x: f64, df: f64) -> f64 {
+    gamma_q(df / 2.0, x / 2.0)
+}
+
+fn f_sf(x: f64, d1: f64, d2: f64) -> f64 {
+    beta_inc(d2 / 2.0, d1 / 2.0, d2 / (d2 + d1 * x))
+}
+
+fn t_result(t: f64, df: f64) -> TestResult {
+    TestResult {
+        statistic: t,
+        p_value: t_p_value(t, df),
+        df: vec![df],
+    }
+}
+
+/// Whether the mean of `x` differs from `mu`.
+pub fn ttest_one_sample(x: &[f64], mu: f64) -> Result<TestResult, CustomErrors> {
+    check_len(x, 2)?;
+    let n = x.len() as f64;
+    let t = (mean(x)? - mu) / (variance(x, 1)? / n).sqrt();
+    Ok(t_result(t, n - 1.0))
+}
+
+/// Whether two independent samples with equal variances have the same mean.
+pub fn ttest_two_sample(x: &[f64], y: &[f64]) -> Result<TestResult, CustomErrors> {
+    check_len(x, 1)?;
+    check_len(y, 1)?;
+    let (nx, ny) = (x.len() as f64, y.len() as f64);
+    let df = nx + ny - 2.0;
+    if df < 1.0 {
+        return Err(CustomErrors::BadType(BadTypeError));
+    }
+    let pooled = (variance(x, 0)? * nx + variance(y, 0)? * ny) / df;
+    let t = (mean(x)? - mean(y)?) / (pooled * (1.0 / nx + 1.0 / ny)).sqrt();
+    Ok(t_result(t, df))
+}
+
+/// Welch's t-test, like `ttest_two_sample` without assuming equal variances.
+pub fn welch_ttest(x: &[f64], y: &[f64]) -> Result<TestResult, CustomErrors> {
+    check_len(x, 2)?;
+    check_len(y, 2)?;
+    let vx = variance(x, 1)? / x.len() as f64;
+    let vy = variance(y, 1)? / y.len() as f64;
+    let t = (mean(x)? - mean(y)?) / (vx + vy).sqrt();
+    // Welch-Satterthwaite
+    let df = (vx + vy).powi(2) / (vx * vx / (x.len() - 1) as f64 + vy * vy / (y.len() - 1) as f64);
+    Ok(t_result(t, df))
+}
+
+/// Whether paired observations differ on average, the one sample test on
+/// `x - y`.
+pub fn ttest_paired(x: &[f64], y: &[f64]) -> Result<TestResult, CustomErrors> {
+    if x.len() != y.len() {
+        return Err(CustomErrors::Mismatch(MismatchError));
+    }
+    let diff: Vec<f64> = x.iter().zip(y).map(|(a, b)| a - b).collect();
+    ttest_one_sample(&diff, 0.0)
+}
+
+/// Pearson's chi-squared goodness of fit of counts to `expected` counts, or to
+/// equal counts without them. Both must have the same total.
+pub fn chi_square(observed: &[f64], expected: Option<&[f64]>) -> Result<TestResult, CustomErrors> {
+    check_len(observed, 2)?;
+    let total: f64 = observed.iter().sum();
+    let expected = match expected {
+        Some(expected) if expected.len() != observed.len() => {
+            return Err(CustomErrors::Mismatch(MismatchError))
+        }
+        Some(expected) => expected.to_vec(),
+        None => vec![total / observed.len() as f64; observed.len()],
+    };
+    let expected_total: f64 = expected.iter().sum();
+    if expected.iter().any(|e| *e <= 0.0)
+        || (total - expected_total).abs() > 1e-8 * total.abs().max(1.0)
+    {
+        return Err(CustomErrors::BadType(BadTypeError));
+    }
+
+    let statistic: f64 = observed
+        .iter()
+        .zip(&expected)
+        .map(|(o, e)| (o - e).powi(2) / e)
+        .sum();
+    let df = (observed.len() - 1) as f64;
+    Ok(TestResult {
+        statistic,
+        p_value: chi2_sf(statistic, df),
+        df: vec![df],
+    })
+}
+
+/// Chi-squared test of independence of the rows and columns of a contingency
+/// table of counts, without Yates' continuity correction.
+pub fn chi_square_independence(table: &Matrix<f64>) -> Result<TestResult, CustomErrors> {
+    if table.m == 0 || table.n == 0 {
+        return Err(CustomErrors::EmptyVector(EmptyVectorError));
+    }
+    if table.m < 2 || table.n < 2 {
+        return Err(CustomErrors::BadType(BadTypeError));
+    }
+    let row_sums: Vec<f64> = table.rows.iter().map(|r| r.iter().sum()).collect();
+    let col_sums: Vec<f64> = (0..table.n)
+        .map(|j| table.rows.iter().map(|r| r[j]).sum())
+        .collect();
+    let total: f64 = row_sums.iter().sum();
+    if row_sums.iter().chain(&col_sums).any(|s| *s <= 0.0) {
+        return Err(CustomErrors::BadType(BadTypeError));
+    }
+
+    let mut statistic = 0.0;
+    for (row, r) in table.rows.iter().zip(&row_sums) {
+        for (o, c) in row.iter().zip(&col_sums) {
+            let e = r * c / total;
+            statistic += (o - e).powi(2) / e;
+        }
+    }
+    let df = ((table.m - 1) * (table.n - 1)) as f64;
+    Ok(TestResult {
+        statistic,
+        p_value: chi2_sf(statistic, df),
+        df: vec![df],
+    })
+}
+
+/// One-way ANOVA, whether the groups have the same mean. The statistic is F
+/// with `k - 1` and `n - k` degrees of freedom.
+pub fn anova_one_way(groups: &[Vec<f64>]) -> Result<TestResult, CustomErrors> {
+    if groups.is_empty() || groups.iter().any(|g| g.is_empty()) {
+        return Err(CustomErrors::EmptyVector(EmptyVectorError));
+    }
+    let k = groups.len() as f64;
+    let n: f64 = groups.iter().map(|g| g.len() as f64).sum();
+    if k < 2.0 || n <= k {
+        return Err(CustomErrors::BadType(BadTypeError));
+    }
+
+    let grand = groups.iter().flatten().sum::<f64>() / n;
+    let mut between = 0.0;
+    let mut within = 0.0;
+    for group in groups {
+        let mn = mean(group)?;
+        between += group.len() as f64 * (mn - grand).powi(2);
+        within += group.iter().map(|v| (v - mn).powi(2)).sum::<f64>();
+    }
+    let (d1, d2) = (k - 1.0, n - k);
+    let statistic = (between / d1) / (within / d2);
+    Ok(TestResult {
+        statistic,
+        p_value: f_sf(statistic, d1, d2),
+        df: vec![d1, d2],
+    })
+}
+
+/// Kolmogorov distribution survival function, `P(K > lambda)`.
+fn kolmogorov_sf(lambda: f64) -> f64 {
+    let mut sum = 0.0;
+    let mut previous: f64 = 0.0;
+    let mut sign = 2.0;
+    for j in 1..=100 {
+        let term = sign * (-2.0 * (j * j) as f64 * lambda * lambda).exp();
+        sum += term;
+        if term.abs() <= 1e-3 * previous || term.abs() <= 1e-10 * sum {
+            return sum.clamp(0.0, 1.0);
+        }
+        sign = -sign;
+        previous = term.abs();
+    }
+    // no convergence, only for a tiny `lambda`
+    1.0
+}
+
+/// Asymptotic p-value of the largest distance `d` between distribution
+/// functions over `n` effective samples, with Stephens' correction.
+fn ks_p_value(d: f64, n: f64) -> f64 {
+    let sqrt_n = n.sqrt();
+    kolmogorov_sf((sqrt_n + 0.12 + 0.11 / sqrt_n) * d)
+}
+
+fn sorted(x: &[f64]) -> Vec<f64> {
+    let mut sorted = x.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    sorted
+}
+
+/// Kolmogorov-Smirnov test of `x` against a distribution. The statistic is the
+/// largest distance between the empirical and the given distribution function.
+pub fn ks_one_sample(x: &[f64], dist: &impl Continuous) -> Result<TestResult, CustomErrors> {
+    check_len(x, 1)?;
+    let n = x.len() as f64;
+    let mut d: f64 = 0.0;
+    for (i, v) in sorted(x).iter().enumerate() {
+        let f = dist.cdf(*v);
+        d = d.max((i + 1) as f64 / n - f).max(f - i as f64 / n);
+    }
+    Ok(TestResult {
+        statistic: d,
+        p_value: ks_p_value(d, n),
+        df: vec![],
+    })
+}
+
+/// Two sample Kolmogorov-Smirnov test, whether `x` and `y` come from the same
+/// distribution.
+pub fn ks_two_sample(x: &[f64], y: &[f64]) -> Result<TestResult, CustomErrors> {
+    check_len(x, 1)?;
+    check_len(y, 1)?;
+    let (x, y) = (sorted(x), sorted(y));
+    let (nx, ny) = (x.len() as f64, y.len() as f64);
+
+    let (mut i, mut j) = (0, 0);
+    let mut d: f64 = 0.0;
+    while i < x.len() && j < y.len() {
+        // step past every copy of the next value in both samples
+        let v = x[i].min(y[j]);
+        while i < x.len() && x[i] <= v {
+            i += 1;
+        }
+        while j < y.len() && y[j] <= v {
+            j += 1;
+        }
+        d = d.max((i as f64 / nx - j as f64 / ny).abs());
+    }
+    Ok(TestResult {
+        statistic: d,
+        p_value: ks_p_value(d, nx * ny / (nx + ny)),
+        df: vec![],
+    })
+}
+
+/// Mann-Whitney U test, whether values of `x` tend to be larger or smaller
+/// than those of `y`. The statistic is `U` of `x`, the p-value comes from the
+/// normal approximation with tie and continuity corrections.
+pub fn mann_whitney_u(x: &[f64], y: &[f64]) -> Result<TestResult, CustomErrors> {
+    check_len(x, 1)?;
+    check_len(y, 1)?;
+    let (nx, ny) = (x.len() as f64, y.len() as f64);
+    let n = nx + ny;
+    let combined: Vec<f64> = x.iter().chain(y).copied().collect();
+    let ranks = rank(&combined);
+    let u = ranks[..x.len()].iter().sum::<f64>() - nx * (nx + 1.0) / 2.0;
+
+    // sum of t^3 - t over groups of t tied values
+    let sorted = sorted(&combined);
+    let mut ties = 0.0;
+    let mut i = 0;
+    while i < sorted.len() {
+        let t = sorted[i..].iter().take_while(|v| **v == sorted[i]).count();
+        ties += (t * t * t - t) as f64;
+        i += t;
+    }
+    let sigma = (nx * ny / 12.0 * ((n + 1.0) - ties / (n * (n - 1.0)))).sqrt();
+    let z = ((u - nx * ny / 2.0).abs() - 0.5) / sigma;
+    Ok(TestResult {
+        statistic: u,
+        p_value: (2.0 * normal_cdf(-z)).min(1.0),
+        df: vec![],
+    })
+}
+
+/// Evaluates a polynomial with coefficients in increasing order.
+fn poly(coeffs: &[f64], x: f64) -> f64 {
+    coeffs.iter().rev().fold(0.0, |acc, c| acc * x + c)
+}
+
+/// Shapiro-Wilk test of normality for 3 to 5000 values, Royston's (1995)
+/// approximation of the coefficients and of the p-value. Small p-values mean
+/// `x` is unlikely to be normal.
+pub fn shapiro_wilk(x: &[f64]) -> Result<TestResult, CustomErrors> {
+    check_len(x, 3)?;
+    if x.len() > 5000 {
+        return Err(CustomErrors::BadType(BadTypeError));
+    }
+    let x = sorted(x);
+    let n = x.len();
+    let nf = n as f64;
+    if x[n - 1] - x[0] < 1e-19 * x[0].abs().max(1.0) {
+        // constant data
+        return Err(CustomErrors::BadType(BadTypeError));
+    }
+
+    // coefficients `a`, antisymmetric so only the upper half is computed
+    let mut a = vec![0.0; n];
+    if n == 3 {
+        a[2] = 0.5f64.sqrt();
+    } else {
+        let m: Vec<f64> = (1..=n)
+            .map(|i| normal_quantile((i as f64 - 0.375) / (nf + 0.25)))
+            .collect();
+        let mm: f64 = m.iter().map(|v| v * v).sum();
+        let u = 1.0 / nf.sqrt();
+        let c1 = [0.0, 0.221157, -0.147981, -2.071190, 4.434685, -2.706056];
+        let c2 = [0.0, 0.042981, -0.293762, -1.752461, 5.682633, -3.582633];
+        a[n - 1] = m[n - 1] / mm.sqrt() + poly(&c1, u);
+        let (first, phi) = if n > 5 {
+            a[n - 2] = m[n - 2] / mm.sqrt() + poly(&c2, u);
+            let phi = (mm - 2.0 * m[n - 1].powi(2) - 2.0 * m[n - 2].powi(2))
+                / (1.0 - 2.0 * a[n - 1].powi(2) - 2.0 * a[n - 2].powi(2));
+            (2, phi)
+        } else {
+            let phi = (mm - 2.0 * m[n - 1].powi(2)) / (1.0 - 2.0 * a[n - 1].powi(2));
+            (1, phi)
+        };
+        for i in first..n - first {
+            a[i] = m[i] / phi.sqrt();
+        }
+    }
+    for i in 0..n / 2 {
+        a[i] = -a[n - 1 - i];
+    }
+
+    let mn = x.iter().sum::<f64>() / nf;
+    let ss: f64 = x.iter().map(|v| (v - mn).powi(2)).sum();
+    let w = (a.iter().zip(&x).map(|(a, v)| a * v).sum::<f64>().powi(2) / ss).min(1.0);
+
+    let p_value = if n == 3 {
+        let p = 6.0 / std::f64::consts::PI * (w.sqrt().asin() - 0.75f64.sqrt().asin());
+        p.max(0.0)
+    } else {
+        let y = (1.0 - w).ln();
+        let z = if n <= 11 {
+            let gamma = poly(&[-2.273, 0.459], nf);
+            if y >= gamma {
+                return Ok(TestResult {
+                    statistic: w,
+                    p_value: 0.0,
+                    df: vec![],
+                });
+            }
+            let mu = poly(&[0.5440, -0.39978, 0.025054, -6.714e-4], nf);
+            let sigma = poly(&[1.3822, -0.77857, 0.062767, -0.0020322], nf).exp();
+            (-(gamma - y).ln() - mu) / sigma
+        } else {
+            let ln_n = nf.ln();
+            let mu = poly(&[-1.5861, -0.31082, -0.083751, 0.0038915], ln_n);
+            let sigma = poly(&[-0.4803, -0.082676, 0.0030302], ln_n).exp();
+            (y - mu) / sigma
+        };
+        normal_cdf(-z)
+    };
+    Ok(TestResult {
+        statistic: w,
+        p_value,
+        df: vec![],
+    })
+}
+
+/// Whether the correlation of `x` and `y` differs from zero, by a t-test with
+/// `n - 2` degrees of freedom. The statistic is the correlation itself.
+/// Only Pearson and Spearman correlations are supported.
+pub fn correlation_test(
+    x: &[f64],
+    y: &[f64],
+    method: Correlation,
+) -> Result<TestResult, CustomErrors> {
+    if method == Correlation::Kendall {
+        return Err(CustomErrors::BadType(BadTypeError));
+    }
+    check_len(x, 3)?;
+    let r = correlation(x, y, method)?;
+    let df = (x.len() - 2) as f64;
+    let t = r * (df / (1.0 - r * r)).sqrt();
+    Ok(TestResult {
+        statistic: r,
+        p_value: if r.abs() >= 1.0 {
+            0.0
+        } else {
+            t_p_value(t, df)
+        },
+        df: vec![df],
+    })
+}
+
+#[cfg(test)]
+mod hypothesis_tests {
+    use super::{
+        anova_one_way, chi_square, chi_square_independence, correlation_test, ks_one_sample,
+        ks_two_sample, mann_whitney_u, shapiro_wilk, ttest_one_sample, ttest_paired,
+        ttest_two_sample, welch_ttest,
+    };
+    use crate::{
+        matrix::Matrix,
+        stats::{descriptive::Correlation, distributions::Normal},
+    };
+
+    fn close(a: f64, b: f64, tol: f64) -> bool {
+        (a - b).abs() <= tol * b.abs().max(1e-300)
+    }
+
+    #[test]
+    fn test_t_tests() {
+        // reference values from the textbook formulas in mpmath
+        let x = [5.1, 4.9, 5.6, 5.8, 6.0, 5.2, 5.5, 5.3];
+        let y = [4.8, 5.0, 4.6, 5.1, 4.7, 4.9];
+
+        let one = ttest_one_sample(&x, 5.0).unwrap();
+        assert!(close(one.statistic, 3.254477383029296, 1e-12));
+        assert!(close(one.p_value, 0.01397056582265143, 1e-10));
+        assert_eq!(one.df, vec![7.0]);
+
+        let two = ttest_two_sample(&x, &y).unwrap();
+        assert!(close(two.statistic, 3.469571540564457, 1e-12));
+        assert!(close(two.p_value, 0.004634619426398482, 1e-10));
+
+        let welch = welch_ttest(&x, &y).unwrap();
+        assert!(close(welch.statistic, 3.800793839468677, 1e-12));
+        assert!(close(welch.df[0], 10.833298875668196, 1e-12));
+        assert!(close(welch.p_value, 0.003019355643314807, 1e-10));
+
+        let a = [12.1, 14.3, 11.8, 13.0, 12.6];
+        let b = [11.5, 13.9, 11.9, 12.1, 12.0];
+        let paired = ttest_paired(&a, &b).unwrap();
+        assert!(close(paired.statistic, 2.899786047984851, 1e-12));
+        assert!(close(paired.p_value, 0.04412741532805892, 1e-10));
+
+        assert!(ttest_one_sample(&[1.0], 0.0).is_err());
+        assert!(ttest_paired(&a, &x).is_err());
+    }
+
+    #[test]
+    fn test_chi_square_and_anova() {
+        // the examples of scipy.stats.chisquare and f_oneway
+        let gof = chi_square(&[16.0, 18.0, 16.0, 14.0, 12.0, 12.0], None).unwrap();
+        assert!(close(gof.statistic, 2.0, 1e-12));
+        assert!(close(gof.p_value, 0.8491450360846096, 1e-10));
+        assert!(chi_square(&[1.0, 2.0], Some(&[1.0, 1.0])).is_err());
+
+        let table = Matrix::new(vec![vec![12.0, 5.0, 9.0], vec![7.0, 15.0, 6.0]]).unwrap();
+        let independence = chi_square_independence(&table).unwrap();
+        assert!(close(independence.statistic, 6.851113360323887, 1e-12));
+        assert!(close(independence.p_value, 0.03253116649254951, 1e-10));
+        assert_eq!(independence.df, vec![2.0]);
+
+        let groups = vec![
+            vec![
+                0.0571, 0.0813, 0.0831, 0.0976, 0.0817, 0.0859, 0.0735, 0.0659, 0.0923, 0.0836,
+            ],
+            vec![
+                0.0873, 0.0662, 0.0672, 0.0819, 0.0749, 0.0649, 0.0835, 0.0725,
+            ],
+            vec![0.0974, 0.1352, 0.0817, 0.1016, 0.0968, 0.1064, 0.105],
+            vec![
+                0.1033, 0.0915, 0.0781, 0.0685, 0.0677, 0.0697, 0.0764, 0.0689,
+            ],
+            vec![0.0703, 0.1026, 0.0956, 0.0973, 0.1039, 0.1045],
+        ];
+        let anova = anova_one_way(&groups).unwrap();
+        assert!(close(anova.statistic, 7.121019471642444, 1e-12));
+        assert!(close(anova.p_value, 0.0002812242314534558, 1e-10));
+        assert_eq!(anova.df, vec![4.0, 34.0]);
+    }
+
+    #[test]
+    fn test_nonparametric_and_correlation() {
+        let ks = ks_one_sample(
+            &[-1.2, -0.4, 0.1, 0.3, 0.9, 1.6, 2.2, 2.5],
+            &Normal::standard(),
+        )
+        .unwrap();
+        assert!(close(ks.statistic, 0.320200708300442, 1e-12));
+        assert!(close(ks.p_value, 0.3195210430721068, 1e-9));
+
+        let ks =
+            ks_two_sample(&[0.1, 0.5, 0.9, 1.3, 2.0], &[1.1, 1.8, 2.4, 3.0, 3.3, 4.1]).unwrap();
+        assert!(close(ks.statistic, 2.0 / 3.0, 1e-12));
+        assert!(close(ks.p_value, 0.09925778048530919, 1e-9));
+
+        let u = mann_whitney_u(&[1.1, 2.3, 3.3, 4.0, 5.5], &[2.3, 6.1, 7.2, 8.0]).unwrap();
+        assert_eq!(u.statistic, 3.5);
+        assert!(close(u.p_value, 0.1399825237583975, 1e-10));
+
+        // Shapiro and Wilk's (1965) example, as R's shapiro.test
+        let weights = [
+            148.0, 154.0, 158.0, 160.0, 161.0, 162.0, 166.0, 170.0, 182.0, 195.0, 236.0,
+        ];
+        let sw = shapiro_wilk(&weights).unwrap();
+        assert!(close(sw.statistic, 0.78881, 1e-5));
+        assert!(close(sw.p_value, 0.006704, 1e-4));
+        let sw3 = shapiro_wilk(&[1.0, 2.0, 3.0]).unwrap();
+        assert!(close(sw3.statistic, 1.0, 1e-12) && close(sw3.p_value, 1.0, 1e-12));
+
+        let a = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+        let b = [3.0, 1.0, 4.0, 1.5, 5.0, 9.0];
+        let pearson = correlation_test(&a, &b, Correlation::Pearson).unwrap();
+        assert!(close(pearson.statistic, 0.7266896484441149, 1e-12));
+        assert!(close(pearson.p_value, 0.10183987915753603, 1e-10));
+        let spearman = correlation_test(&a, &b, Correlation::Spearman).unwrap();
+        assert!(close(spearman.statistic, 5.0 / 7.0, 1e-12));
+        assert!(close(spearman.p_value, 0.1107871720116618, 1e-10));
+        assert!(correlation_test(&a, &b, Correlation::Kendall).is_err());
+    }
+}
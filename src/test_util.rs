@@ -0,0 +1,9 @@
+//! Helpers shared by the unit tests.
+
+use std::{env, path::PathBuf, process};
+
+/// Path in the temp directory unique to this process and `name`, so parallel
+/// tests and concurrent runs don't overwrite each other's files.
+pub(crate) fn temp_path(name: &str) -> PathBuf {
+    env::temp_dir().join(format!("pikus_{}_{}", process::id(), name))
+}
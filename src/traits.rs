@@ -0,0 +1,93 @@
+use crate::error::CustomErrors;
+
+/// Learns parameters from training data. Unsupervised models use `()` as target.
+pub trait Fit {
+    type Input: ?Sized;
+    type Target: ?Sized;
+    /// Whatever the fit reports back, e.g. cluster labels or training losses.
+    type Output;
+
+    fn fit(&mut self, x: &Self::Input, y: &Self::Target) -> Result<Self::Output, CustomErrors>;
+}
+
+pub trait Predict {
+    type Input: ?Sized;
+    type Output;
+
+    fn predict(&self, x: &Self::Input) -> Result<Self::Output, CustomErrors>;
+}
+
+pub trait PredictProba {
+    type Input: ?Sized;
+    type Output;
+
+    fn predict_proba(&self, x: &Self::Input) -> Result<Self::Output, CustomErrors>;
+}
+
+/// Applies fitted parameters to new data.
+pub trait Transform {
+    type Input: ?Sized;
+    type Output;
+
+    fn transform(&self, x: &Self::Input) -> Result<Self::Output, CustomErrors>;
+}
+
+/// Fit and transform the same data in one call, available on every `Fit + Transform`.
+pub trait FitTransform: Fit + Transform<Input = <Self as Fit>::Input> {
+    fn fit_transform(
+        &mut self,
+        x: &<Self as Fit>::Input,
+        y: &<Self as Fit>::Target,
+    ) -> Result<<Self as Transform>::Output, CustomErrors> {
+        self.fit(x, y)?;
+        self.transform(x)
+    }
+}
+
+impl<T: Fit + Transform<Input = <T as Fit>::Input>> FitTransform for T {}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        error::CustomErrors,
+        matrix::Matrix,
+        models::{
+            cluster::KMeans,
+            linear::{penalized::Ridge, LinearRegression},
+            neighbors::knn::KNeighborsRegressor,
+        },
+    };
+
+    use super::{Fit, Predict};
+
+    fn fit_predict<M>(model: &mut M, x: &Matrix<f64>, y: &[f64]) -> Result<Vec<f64>, CustomErrors>
+    where
+        M: Fit<Input = Matrix<f64>, Target = [f64]>
+            + Predict<Input = Matrix<f64>, Output = Vec<f64>>,
+    {
+        model.fit(x, y)?;
+        model.predict(x)
+    }
+
+    #[test]
+    fn test_generic_estimators() {
+        let x = Matrix::new(vec![vec![0.0], vec![1.0], vec![2.0], vec![3.0]]).unwrap();
+        let y = vec![1.0, 3.0, 5.0, 7.0];
+
+        let ols = fit_predict(&mut LinearRegression::new(), &x, &y).unwrap();
+        let ridge = fit_predict(&mut Ridge::new(0.0), &x, &y).unwrap();
+        let knn = fit_predict(&mut KNeighborsRegressor::new(1), &x, &y).unwrap();
+        for ((a, b), c) in ols.iter().zip(ridge.iter()).zip(knn.iter()) {
+            assert!((a - c).abs() < 1e-10 && (b - c).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_unsupervised_fit() {
+        let x = Matrix::new(vec![vec![0.0], vec![0.1], vec![5.0], vec![5.1]]).unwrap();
+        let mut model = KMeans::new(2).with_seed(3);
+
+        let result = Fit::fit(&mut model, &x, &()).unwrap();
+        assert_eq!(Predict::predict(&model, &x).unwrap(), result.labels);
+    }
+}
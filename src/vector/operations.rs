@@ -79,10 +79,9 @@ pub fn sum<T: Copy + From<u8> + Add<Output = T> + std::ops::AddAssign>(
 }
 
 pub fn mean<T: Copy + Into<f64> + From<u8> + Add<Output = T> + std::ops::AddAssign>(
-    vec_1: &Vec<T>,
+    vec_1: &[T],
 ) -> Result<f64, CustomErrors> {
     let l1 = vec_1.len();
-    let n_elements: u8 = vec_1.len().try_into().unwrap();
     if l1 == 0 {
         return Err(CustomErrors::EmptyVector(EmptyVectorError));
     }
@@ -101,39 +100,30 @@ pub fn mean<T: Copy + Into<f64> + From<u8> + Add<Output = T> + std::ops::AddAssi
         _ => return Err(CustomErrors::BadType(BadTypeError)),
     };
 
-    let n_elements: f64 = match n_elements.try_into() {
-        Ok(val) => val,
-        _ => return Err(CustomErrors::BadType(BadTypeError)),
-    };
-
-    let mean = sum / n_elements;
+    let mean = sum / l1 as f64;
     return Ok(mean);
 }
 
+/// Sum of squared deviations over `n - ddof`, `ddof = 0` for the population
+/// variance and `ddof = 1` for the unbiased sample variance.
+pub fn variance(vec: &[f64], ddof: usize) -> Result<f64, CustomErrors> {
+    let mn = mean(vec)?;
+    if vec.len() <= ddof {
+        return Err(CustomErrors::BadType(BadTypeError));
+    }
+    let sqsum: f64 = vec.iter().map(|v| (v - mn).powi(2)).sum();
+    Ok(sqsum / (vec.len() - ddof) as f64)
+}
+
+/// Population standard deviation.
 pub fn stddev<T: Copy + From<u8> + Into<f64> + Add<Output = T> + AddAssign>(
     vec_1: &Vec<T>,
 ) -> Result<f64, CustomErrors> {
-    let l1 = vec_1.len();
-    let mut n = 0.0;
-    let mn = match mean(&vec_1) {
-        Ok(v) => v,
-        Err(_) => {
-            return Err(CustomErrors::Mismatch(MismatchError));
-        }
-    };
-
-    let mut sqsum = 0.0;
-
-    for i in 0..l1 {
-        let v: f64 = vec_1[i].into();
-        let sq = (v - mn).powf(2.0);
-        sqsum += sq;
-        n += 1.0;
+    let values: Vec<f64> = vec_1.iter().map(|v| (*v).into()).collect();
+    match variance(&values, 0) {
+        Ok(v) => Ok(v.sqrt()),
+        Err(_) => Err(CustomErrors::Mismatch(MismatchError)),
     }
-
-    sqsum /= n;
-
-    Ok(sqsum.powf(0.5))
 }
 
 pub fn dot_product<
@@ -229,7 +219,8 @@ pub fn cosine_similarity<
         Ok(val) => val,
         Err(err) => return Err(err),
     };
-    let mag_prod = magnitude_a * magnitude_b;
+    // `magnitude` returns the squared norm
+    let mag_prod = (magnitude_a * magnitude_b).sqrt();
 
     Ok(dot_prod / mag_prod)
 }
@@ -318,3 +309,78 @@ pub fn sub_from_scalar<T: std::ops::Sub<Output = T> + Copy>(scalar: T, vec: &Vec
     }
     new
 }
+
+/// Sorted copy of `vec` and the fractional rank of its `q`-th quantile, for
+/// the other interpolations of `stats::descriptive::quantile`.
+pub(crate) fn quantile_rank(vec: &[f64], q: f64) -> Result<(Vec<f64>, f64), CustomErrors> {
+    if vec.is_empty() {
+        return Err(CustomErrors::EmptyVector(EmptyVectorError));
+    }
+    if q.is_nan() || !(0.0..=1.0).contains(&q) {
+        return Err(CustomErrors::BadType(BadTypeError));
+    }
+
+    let mut sorted = vec.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let pos = q * (sorted.len() - 1) as f64;
+    Ok((sorted, pos))
+}
+
+/// Value at fractional rank `pos` of `sorted`, linearly interpolated.
+pub(crate) fn interpolate_rank(sorted: &[f64], pos: f64) -> f64 {
+    let lo = pos.floor() as usize;
+    let hi = pos.ceil() as usize;
+    sorted[lo] + (sorted[hi] - sorted[lo]) * (pos - lo as f64)
+}
+
+/// `q`-th quantile (0 <= q <= 1), linearly interpolated between the closest ranks.
+pub fn quantile(vec: &[f64], q: f64) -> Result<f64, CustomErrors> {
+    let (sorted, pos) = quantile_rank(vec, q)?;
+    Ok(interpolate_rank(&sorted, pos))
+}
+
+/// Sum of the non-NaN elements, 0 if there are none.
+pub fn nansum(vec: &[f64]) -> f64 {
+    vec.iter().filter(|v| !v.is_nan()).sum()
+}
+
+/// Mean of the non-NaN elements.
+pub fn nanmean(vec: &[f64]) -> Result<f64, CustomErrors> {
+    let count = vec.iter().filter(|v| !v.is_nan()).count();
+    if count == 0 {
+        return Err(CustomErrors::EmptyVector(EmptyVectorError));
+    }
+    Ok(nansum(vec) / count as f64)
+}
+
+/// Population standard deviation of the non-NaN elements.
+pub fn nanstd(vec: &[f64]) -> Result<f64, CustomErrors> {
+    let mn = nanmean(vec)?;
+    let present: Vec<&f64> = vec.iter().filter(|v| !v.is_nan()).collect();
+    let sqsum: f64 = present.iter().map(|v| (*v - mn).powi(2)).sum();
+    Ok((sqsum / present.len() as f64).sqrt())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{mean, quantile, stddev, variance};
+
+    #[test]
+    fn test_mean_long_vector() {
+        // the length used to be converted to u8 and panicked past 255
+        let values: Vec<f64> = (0..1000).map(f64::from).collect();
+        assert_eq!(mean(&values).unwrap(), 499.5);
+    }
+
+    #[test]
+    fn test_variance_and_quantile() {
+        let values = vec![2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+        assert_eq!(variance(&values, 0).unwrap(), 4.0);
+        assert_eq!(variance(&values, 1).unwrap(), 32.0 / 7.0);
+        assert_eq!(stddev(&values).unwrap(), 2.0);
+        assert!(variance(&[1.0], 1).is_err());
+        assert_eq!(quantile(&values, 0.5).unwrap(), 4.5);
+        assert_eq!(quantile(&values, 0.9).unwrap(), 7.6);
+        assert!(quantile(&values, 1.5).is_err());
+    }
+}